@@ -0,0 +1,161 @@
+//! SoA batch kernels in `simd` against the scalar versions
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+
+fn bench_tri3(c: &mut Criterion) {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let num_tri = 10000;
+    let vtx2xyz: Vec<f32> = (0..num_tri * 9).map(|_| rng.gen::<f32>()).collect();
+    let soa = del_geo_core::simd::to_soa::<f32, 9>(&vtx2xyz);
+    let soa: [&[f32]; 9] = std::array::from_fn(|i| soa[i].as_slice());
+    let ray_org = [0.5, 0.5, -1.0];
+    let ray_dir = [0.1, 0.05, 1.0];
+    let mut hits = vec![false; num_tri];
+    let mut ts = vec![0f32; num_tri];
+    let mut group = c.benchmark_group("tri3_intersection_against_line");
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (i_tri, p) in vtx2xyz.chunks(9).enumerate() {
+                let t = del_geo_core::tri3::intersection_against_line(
+                    arrayref::array_ref![p, 0, 3],
+                    arrayref::array_ref![p, 3, 3],
+                    arrayref::array_ref![p, 6, 3],
+                    &ray_org,
+                    &ray_dir,
+                );
+                hits[i_tri] = t.is_some();
+                ts[i_tri] = t.unwrap_or(0.);
+            }
+            black_box(&ts);
+        })
+    });
+    macro_rules! lanes {
+        ($l:literal) => {
+            group.bench_function(BenchmarkId::new("lanes", $l), |b| {
+                b.iter(|| {
+                    del_geo_core::simd::tri3_intersection_against_line::<f32, $l>(
+                        &soa, &ray_org, &ray_dir, &mut hits, &mut ts,
+                    );
+                    black_box(&ts);
+                })
+            });
+        };
+    }
+    lanes!(4);
+    lanes!(8);
+    lanes!(16);
+    group.finish();
+}
+
+fn bench_aabb3(c: &mut Criterion) {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let num_aabb = 10000;
+    let aabbs: Vec<f32> = (0..num_aabb)
+        .flat_map(|_| {
+            let c: [f32; 3] = std::array::from_fn(|_| rng.gen::<f32>());
+            let r = rng.gen::<f32>() * 0.1;
+            [c[0] - r, c[1] - r, c[2] - r, c[0] + r, c[1] + r, c[2] + r]
+        })
+        .collect();
+    let soa = del_geo_core::simd::to_soa::<f32, 6>(&aabbs);
+    let soa: [&[f32]; 6] = std::array::from_fn(|i| soa[i].as_slice());
+    let ray_org = [0.5, 0.5, -1.0];
+    let ray_dir = [0.1, 0.05, 1.0];
+    let mut hits = vec![false; num_aabb];
+    let mut tmins = vec![0f32; num_aabb];
+    let mut tmaxs = vec![0f32; num_aabb];
+    let mut group = c.benchmark_group("aabb3_intersections_against_ray");
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (i_aabb, aabb) in aabbs.chunks(6).enumerate() {
+                let res = del_geo_core::aabb::intersections_against_ray::<f32, 3, 6>(
+                    arrayref::array_ref![aabb, 0, 6],
+                    &ray_org,
+                    &ray_dir,
+                );
+                hits[i_aabb] = res.is_some();
+                (tmins[i_aabb], tmaxs[i_aabb]) = res.unwrap_or((0., 0.));
+            }
+            black_box(&tmins);
+        })
+    });
+    macro_rules! lanes {
+        ($l:literal) => {
+            group.bench_function(BenchmarkId::new("lanes", $l), |b| {
+                b.iter(|| {
+                    del_geo_core::simd::aabb3_intersections_against_ray::<f32, $l>(
+                        &soa, &ray_org, &ray_dir, &mut hits, &mut tmins, &mut tmaxs,
+                    );
+                    black_box(&tmins);
+                })
+            });
+        };
+    }
+    lanes!(4);
+    lanes!(8);
+    lanes!(16);
+    group.finish();
+}
+
+fn bench_transform_homogeneous(c: &mut Criterion) {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let num_point = 10000;
+    let xyz: Vec<f32> = (0..num_point * 3).map(|_| rng.gen::<f32>()).collect();
+    let soa = del_geo_core::simd::to_soa::<f32, 3>(&xyz);
+    let soa: [&[f32]; 3] = std::array::from_fn(|i| soa[i].as_slice());
+    let transform = del_geo_core::mat4_col_major::from_translate(&[0.1, 0.2, 0.3]);
+    let mut out = [
+        vec![0f32; num_point],
+        vec![0f32; num_point],
+        vec![0f32; num_point],
+    ];
+    let mut valids = vec![false; num_point];
+    let mut group = c.benchmark_group("mat4_col_major_transform_homogeneous");
+    group.bench_function("scalar", |b| {
+        b.iter(|| {
+            for (i_point, p) in xyz.chunks(3).enumerate() {
+                let q = del_geo_core::mat4_col_major::transform_homogeneous(
+                    &transform,
+                    arrayref::array_ref![p, 0, 3],
+                );
+                valids[i_point] = q.is_some();
+                let q = q.unwrap_or([0.; 3]);
+                for i_dim in 0..3 {
+                    out[i_dim][i_point] = q[i_dim];
+                }
+            }
+            black_box(&out);
+        })
+    });
+    macro_rules! lanes {
+        ($l:literal) => {
+            group.bench_function(BenchmarkId::new("lanes", $l), |b| {
+                b.iter(|| {
+                    let [o0, o1, o2] = &mut out;
+                    let mut xyz_out = [o0.as_mut_slice(), o1.as_mut_slice(), o2.as_mut_slice()];
+                    del_geo_core::simd::mat4_col_major_transform_homogeneous::<f32, $l>(
+                        &transform,
+                        &soa,
+                        &mut xyz_out,
+                        &mut valids,
+                    );
+                    black_box(&out);
+                })
+            });
+        };
+    }
+    lanes!(4);
+    lanes!(8);
+    lanes!(16);
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_tri3,
+    bench_aabb3,
+    bench_transform_homogeneous
+);
+criterion_main!(benches);
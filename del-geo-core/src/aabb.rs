@@ -84,6 +84,13 @@ where
 
 // -----------------------------
 
+/// borrowed view of an AABB.
+/// It serializes the same as `[Real; SIZE_AABB]`, so deserialize into the array and take a view of it
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+#[cfg_attr(
+    feature = "serde",
+    serde(transparent, bound = "[Real; SIZE_AABB]: serde::Serialize")
+)]
 pub struct AABB<'a, Real, const NDIM: usize, const SIZE_AABB: usize> {
     pub aabb: &'a [Real; SIZE_AABB],
 }
@@ -117,3 +124,12 @@ where
         center(self.aabb)
     }
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let aabb = [0., 1., 2., 3.];
+    let s = serde_json::to_string(&AABB::<f64, 2, 4> { aabb: &aabb }).unwrap();
+    assert_eq!(s, "[0.0,1.0,2.0,3.0]");
+    assert_eq!(serde_json::from_str::<[f64; 4]>(&s).unwrap(), aabb);
+}
@@ -1,7 +1,11 @@
 //! methods for 2D Axis-aligned Bounding Box (AABB)
 
 use num_traits::AsPrimitive;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+#[cfg(feature = "rand")]
 use rand::distributions::Standard;
+#[cfg(feature = "rand")]
 use rand::prelude::Distribution;
 
 pub fn from_point<T>(p: &[T; 2], rad: T) -> [T; 4]
@@ -103,6 +107,7 @@ where
     ]
 }
 
+#[cfg(feature = "rand")]
 pub fn sample<Reng, T>(aabb: &[T; 4], reng: &mut Reng) -> [T; 2]
 where
     Reng: rand::Rng,
@@ -204,33 +209,53 @@ where
     ]
 }
 
+/// indices of the tiles overlapping with the AABB in ascending order without allocation.
+/// the tile at `(ix, iy)` covers `[ix * tile_size, (ix + 1) * tile_size] x [iy * tile_size, (iy + 1) * tile_size]`
+/// and its index is `iy * tile_shape.0 + ix`
+pub fn overlapping_tiles_iter(
+    aabb: &[f32; 4],
+    tile_size: usize,
+    tile_shape: (usize, usize),
+) -> impl Iterator<Item = usize> {
+    let range = |lo: f32, hi: f32, n: usize| {
+        let i0 = (lo / tile_size as f32).floor() as i32;
+        let i1 = (hi / tile_size as f32).floor() as i32 + 1;
+        (i0.max(0) as usize)..(i1.clamp(0, n as i32) as usize)
+    };
+    let range_x = range(aabb[0], aabb[2], tile_shape.0);
+    let range_y = range(aabb[1], aabb[3], tile_shape.1);
+    range_y.flat_map(move |iy| range_x.clone().map(move |ix| iy * tile_shape.0 + ix))
+}
+
+/// see `overlapping_tiles_iter`
+#[cfg(feature = "std")]
 pub fn overlapping_tiles(
     aabb: &[f32; 4],
     tile_size: usize,
     tile_shape: (usize, usize),
 ) -> std::collections::BTreeSet<usize> {
-    let ix0 = (aabb[0] / tile_size as f32).floor() as i32;
-    let iy0 = (aabb[1] / tile_size as f32).floor() as i32;
-    let ix1 = (aabb[2] / tile_size as f32).floor() as i32 + 1;
-    let iy1 = (aabb[3] / tile_size as f32).floor() as i32 + 1;
-    let mut tiles = std::collections::BTreeSet::<usize>::new();
-    for ix in ix0..ix1 {
-        assert_ne!(ix, ix1);
-        if ix < 0 || ix >= (tile_shape.0 as i32) {
-            continue;
-        }
-        let ix = ix as usize;
-        for iy in iy0..iy1 {
-            assert_ne!(iy, iy1);
-            if iy < 0 || iy >= (tile_shape.1 as i32) {
-                continue;
-            }
-            let iy = iy as usize;
-            let i_tile = iy * tile_shape.0 + ix;
-            tiles.insert(i_tile);
-        }
-    }
-    tiles
+    overlapping_tiles_iter(aabb, tile_size, tile_shape).collect()
+}
+
+#[test]
+fn test_overlapping_tiles() {
+    let tiles = overlapping_tiles(&[10., 20., 40., 33.], 16, (4, 3));
+    assert_eq!(
+        tiles.into_iter().collect::<Vec<_>>(),
+        vec![4, 5, 6, 8, 9, 10]
+    );
+    // partially outside
+    let tiles: Vec<_> = overlapping_tiles_iter(&[-10., -5., 5., 70.], 16, (4, 3)).collect();
+    assert_eq!(tiles, vec![0, 4, 8]);
+    // fully outside
+    assert_eq!(
+        overlapping_tiles_iter(&[70., 0., 80., 5.], 16, (4, 3)).count(),
+        0
+    );
+    assert_eq!(
+        overlapping_tiles_iter(&[-30., 0., -20., 5.], 16, (4, 3)).count(),
+        0
+    );
 }
 
 // -------------------------------------------------------------------------------
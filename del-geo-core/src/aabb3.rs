@@ -1,13 +1,16 @@
 //! methods for 3D Axis-aligned Bounding Box (AABB)
 
+#[cfg(feature = "rand")]
 use rand::distributions::{Distribution, Standard};
 /// trait for 3D Axis-aligned Bounding Box (AABB)
+#[cfg(feature = "rand")]
 pub trait AABB3Trait<T> {
     fn sample<Reng>(self, reng: &mut Reng) -> [T; 3]
     where
         Reng: rand::Rng,
         Standard: Distribution<T>;
 }
+#[cfg(feature = "rand")]
 impl<T> AABB3Trait<T> for [T; 6]
 where
     T: num_traits::Float,
@@ -29,12 +32,8 @@ where
     assert_eq!(i1.len(), 6);
     let mut o = [T::zero(); 6];
     for i in 0..3 {
-        o[i] = if i0[i] < i1[i] { i0[i] } else { i1[i] };
-        o[i + 3] = if i0[i + 3] > i1[i + 3] {
-            i0[i + 3]
-        } else {
-            i1[i + 3]
-        };
+        o[i] = i0[i].min(i1[i]);
+        o[i + 3] = i0[i + 3].max(i1[i + 3]);
     }
     o
 }
@@ -162,6 +161,7 @@ where
 }
 
 /// return a vec3 sampled inside a aabb
+#[cfg(feature = "rand")]
 pub fn sample<Reng, T>(aabb: &[T; 6], reng: &mut Reng) -> [T; 3]
 where
     Reng: rand::Rng,
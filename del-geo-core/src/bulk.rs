@@ -0,0 +1,226 @@
+//! parallel bulk operations over the flat arrays using `rayon`.
+//! Enabled by the `rayon` feature
+//! * `vtx2xyz` - coordinates of the points `[x0, y0, z0, x1, y1, z1, ...]`
+//! * `tri2vtx` - vertex indices of the triangles `[i0, j0, k0, i1, j1, k1, ...]`
+
+use rayon::prelude::*;
+
+/// coordinates of the three corners of the `i_tri`-th triangle
+fn tri_xyz<'a, Real>(tri2vtx: &[usize], vtx2xyz: &'a [Real], i_tri: usize) -> [&'a [Real; 3]; 3] {
+    core::array::from_fn(|i_node| arrayref::array_ref![vtx2xyz, tri2vtx[i_tri * 3 + i_node] * 3, 3])
+}
+
+/// AABB of the point cloud, inflated by `eps`.
+/// The returned AABB is not active (see `aabb3::is_active`) if there is no point
+pub fn aabb3_from_vtx2xyz<Real>(vtx2xyz: &[Real], eps: Real) -> [Real; 6]
+where
+    Real: num_traits::Float + Send + Sync,
+{
+    let inf = Real::infinity();
+    let init = || [inf, inf, inf, -inf, -inf, -inf];
+    vtx2xyz
+        .par_chunks_exact(3)
+        .fold(init, |mut aabb, xyz| {
+            crate::aabb3::add_point(&mut aabb, arrayref::array_ref![xyz, 0, 3], eps);
+            aabb
+        })
+        .reduce(init, |a, b| crate::aabb3::from_two_aabbs(&a, &b))
+}
+
+/// transform all the points in place with a homogeneous transformation (see `mat4_col_major::transform_homogeneous`).
+/// Return `false` if any point is mapped to the infinity, which is left unchanged
+pub fn transform_homogeneous_vtx2xyz<Real>(transform: &[Real; 16], vtx2xyz: &mut [Real]) -> bool
+where
+    Real: num_traits::Float + Send + Sync,
+{
+    vtx2xyz
+        .par_chunks_exact_mut(3)
+        .map(|xyz| {
+            let p = arrayref::array_mut_ref![xyz, 0, 3];
+            match crate::mat4_col_major::transform_homogeneous(transform, p) {
+                Some(q) => {
+                    *p = q;
+                    true
+                }
+                None => false,
+            }
+        })
+        .reduce(|| true, |a, b| a & b)
+}
+
+/// normal vector of each triangle (see `tri3::normal`). The length is twice the area
+pub fn tri2normal<Real>(tri2vtx: &[usize], vtx2xyz: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float + Send + Sync,
+{
+    let mut tri2normal = vec![Real::zero(); tri2vtx.len()];
+    tri2normal
+        .par_chunks_exact_mut(3)
+        .enumerate()
+        .for_each(|(i_tri, n)| {
+            let [p0, p1, p2] = tri_xyz(tri2vtx, vtx2xyz, i_tri);
+            n.copy_from_slice(&crate::tri3::normal(p0, p1, p2));
+        });
+    tri2normal
+}
+
+/// area of each triangle (see `tri3::area`)
+pub fn tri2area<Real>(tri2vtx: &[usize], vtx2xyz: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float + Send + Sync,
+{
+    (0..tri2vtx.len() / 3)
+        .into_par_iter()
+        .map(|i_tri| {
+            let [p0, p1, p2] = tri_xyz(tri2vtx, vtx2xyz, i_tri);
+            crate::tri3::area(p0, p1, p2)
+        })
+        .collect()
+}
+
+/// nearest intersection of a ray against the triangles
+/// * `ray_dir` - direction of ray (general non-zero vector, not necessarily unitary)
+///
+/// * Return
+///     * `None`: if there is no intersection
+///     * `(t, i_tri)`: the intersection is at `ray_org + t * ray_dir` on the `i_tri`-th triangle
+pub fn nearest_intersection_against_ray<Real>(
+    tri2vtx: &[usize],
+    vtx2xyz: &[Real],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+) -> Option<(Real, usize)>
+where
+    Real: num_traits::Float + Send + Sync,
+{
+    (0..tri2vtx.len() / 3)
+        .into_par_iter()
+        .filter_map(|i_tri| {
+            let [p0, p1, p2] = tri_xyz(tri2vtx, vtx2xyz, i_tri);
+            crate::tri3::intersection_against_line(p0, p1, p2, ray_org, ray_dir)
+                .filter(|&t| t >= Real::zero())
+                .map(|t| (t, i_tri))
+        })
+        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap().then(a.1.cmp(&b.1)))
+}
+
+/// nearest intersection of each ray against the triangles (see `nearest_intersection_against_ray`).
+/// The rays are processed in parallel
+/// * `ray2org` - origins of the rays `[x0, y0, z0, x1, y1, z1, ...]`
+/// * `ray2dir` - directions of the rays `[x0, y0, z0, x1, y1, z1, ...]`
+pub fn nearest_intersections_against_rays<Real>(
+    tri2vtx: &[usize],
+    vtx2xyz: &[Real],
+    ray2org: &[Real],
+    ray2dir: &[Real],
+) -> Vec<Option<(Real, usize)>>
+where
+    Real: num_traits::Float + Send + Sync,
+{
+    assert_eq!(ray2org.len(), ray2dir.len());
+    ray2org
+        .par_chunks_exact(3)
+        .zip(ray2dir.par_chunks_exact(3))
+        .map(|(org, dir)| {
+            let org = arrayref::array_ref![org, 0, 3];
+            let dir = arrayref::array_ref![dir, 0, 3];
+            (0..tri2vtx.len() / 3)
+                .filter_map(|i_tri| {
+                    let [p0, p1, p2] = tri_xyz(tri2vtx, vtx2xyz, i_tri);
+                    crate::tri3::intersection_against_line(p0, p1, p2, org, dir)
+                        .filter(|&t| t >= Real::zero())
+                        .map(|t| (t, i_tri))
+                })
+                .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap().then(a.1.cmp(&b.1)))
+        })
+        .collect()
+}
+
+#[test]
+fn test_bulk() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let num_vtx = 10000;
+    let vtx2xyz: Vec<f64> = (0..num_vtx * 3).map(|_| rng.gen::<f64>()).collect();
+    // aabb
+    {
+        let aabb = aabb3_from_vtx2xyz(&vtx2xyz, 0.);
+        let mut aabb0 = [f64::MAX, f64::MAX, f64::MAX, f64::MIN, f64::MIN, f64::MIN];
+        for xyz in vtx2xyz.chunks(3) {
+            crate::aabb3::add_point(&mut aabb0, arrayref::array_ref![xyz, 0, 3], 0.);
+        }
+        assert_eq!(aabb, aabb0);
+        assert!(!crate::aabb3::is_active(&aabb3_from_vtx2xyz::<f64>(
+            &[],
+            0.
+        )));
+    }
+    // transformation
+    {
+        let transform = crate::mat4_col_major::from_translate(&[1., 2., 3.]);
+        let mut vtx2xyz1 = vtx2xyz.clone();
+        assert!(transform_homogeneous_vtx2xyz(&transform, &mut vtx2xyz1));
+        for (p0, p1) in vtx2xyz.chunks(3).zip(vtx2xyz1.chunks(3)) {
+            let p0 = crate::mat4_col_major::transform_homogeneous(
+                &transform,
+                arrayref::array_ref![p0, 0, 3],
+            )
+            .unwrap();
+            assert_eq!(&p0, p1);
+        }
+        let mut transform = transform;
+        transform[15] = 0.;
+        let mut vtx2xyz2 = vec![0., 0., 0., 1., 1., 1.];
+        assert!(!transform_homogeneous_vtx2xyz(&transform, &mut vtx2xyz2));
+        assert_eq!(vtx2xyz2[0..3], [0., 0., 0.]);
+    }
+    // triangles
+    let num_tri = 1000;
+    let tri2vtx: Vec<usize> = (0..num_tri * 3)
+        .map(|_| rng.gen_range(0..num_vtx))
+        .collect();
+    {
+        let tri2normal = tri2normal(&tri2vtx, &vtx2xyz);
+        let tri2area = tri2area(&tri2vtx, &vtx2xyz);
+        for i_tri in 0..num_tri {
+            let [p0, p1, p2] = tri_xyz(&tri2vtx, &vtx2xyz, i_tri);
+            assert_eq!(
+                tri2normal[i_tri * 3..i_tri * 3 + 3],
+                crate::tri3::normal(p0, p1, p2)
+            );
+            assert_eq!(tri2area[i_tri], crate::tri3::area(p0, p1, p2));
+        }
+    }
+    // ray casting
+    {
+        let num_ray = 100;
+        let ray2org: Vec<f64> = (0..num_ray)
+            .flat_map(|_| [rng.gen::<f64>(), rng.gen::<f64>(), -1.])
+            .collect();
+        let ray2dir: Vec<f64> = (0..num_ray).flat_map(|_| [0., 0., 1.]).collect();
+        let ray2hit = nearest_intersections_against_rays(&tri2vtx, &vtx2xyz, &ray2org, &ray2dir);
+        assert!(ray2hit.iter().any(|hit| hit.is_some()));
+        for (i_ray, &hit0) in ray2hit.iter().enumerate() {
+            let org = arrayref::array_ref![ray2org, i_ray * 3, 3];
+            let dir = arrayref::array_ref![ray2dir, i_ray * 3, 3];
+            let hit = nearest_intersection_against_ray(&tri2vtx, &vtx2xyz, org, dir);
+            assert_eq!(hit, hit0);
+            let Some((t, i_tri)) = hit else {
+                continue;
+            };
+            // no triangle is closer
+            for j_tri in 0..num_tri {
+                let [p0, p1, p2] = tri_xyz(&tri2vtx, &vtx2xyz, j_tri);
+                if let Some(s) = crate::tri3::intersection_against_line(p0, p1, p2, org, dir) {
+                    assert!(s < 0. || s >= t);
+                }
+            }
+            let [p0, p1, p2] = tri_xyz(&tri2vtx, &vtx2xyz, i_tri);
+            assert_eq!(
+                crate::tri3::intersection_against_line(p0, p1, p2, org, dir),
+                Some(t)
+            );
+        }
+    }
+}
@@ -1,6 +1,6 @@
 //! methods for 3x3 matrix
 
-use std::ops::MulAssign;
+use core::ops::MulAssign;
 
 use crate::vec3::Vec3;
 
@@ -0,0 +1,770 @@
+//! dual number for the forward-mode automatic differentiation.
+//!
+//! `Dual<T>` implements `num_traits::Float` so that any generic function in this crate
+//! can be evaluated with it to compute the derivative.
+//! Nesting the dual number as `Dual<Dual<T>>` (hyper-dual number) gives the second derivative.
+
+/// dual number `v + d ε` where `ε^2 = 0`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Dual<T> {
+    /// value
+    pub v: T,
+    /// derivative
+    pub d: T,
+}
+
+impl<T> Dual<T>
+where
+    T: num_traits::Float,
+{
+    pub fn new(v: T, d: T) -> Self {
+        Dual { v, d }
+    }
+
+    /// constant whose derivative is zero
+    pub fn constant(v: T) -> Self {
+        Dual { v, d: T::zero() }
+    }
+
+    /// independent variable whose derivative is one
+    pub fn variable(v: T) -> Self {
+        Dual { v, d: T::one() }
+    }
+
+    /// apply a function whose value is `f` and derivative is `df` at `self.v` (chain rule)
+    fn chain(self, f: T, df: T) -> Self {
+        Dual {
+            v: f,
+            d: df * self.d,
+        }
+    }
+}
+
+/// hyper-dual number to compute the second derivatives
+pub type HyperDual<T> = Dual<Dual<T>>;
+
+/// value and gradient of a function `f: R^N -> R`
+pub fn gradient<T, F, const N: usize>(f: F, x: &[T; N]) -> (T, [T; N])
+where
+    T: num_traits::Float,
+    F: Fn(&[Dual<T>; N]) -> Dual<T>,
+{
+    let mut w = T::zero();
+    let dw = core::array::from_fn(|i| {
+        let xd =
+            core::array::from_fn(|j| Dual::new(x[j], if i == j { T::one() } else { T::zero() }));
+        let y = f(&xd);
+        w = y.v;
+        y.d
+    });
+    (w, dw)
+}
+
+/// value, gradient and hessian of a function `f: R^N -> R`
+#[allow(clippy::type_complexity)]
+pub fn hessian<T, F, const N: usize>(f: F, x: &[T; N]) -> (T, [T; N], [[T; N]; N])
+where
+    T: num_traits::Float,
+    F: Fn(&[HyperDual<T>; N]) -> HyperDual<T>,
+{
+    let (zero, one) = (T::zero(), T::one());
+    let mut w = zero;
+    let mut dw = [zero; N];
+    let ddw = core::array::from_fn(|i| {
+        core::array::from_fn(|j| {
+            let xd = core::array::from_fn(|k| {
+                Dual::new(
+                    Dual::new(x[k], if k == j { one } else { zero }),
+                    Dual::new(if k == i { one } else { zero }, zero),
+                )
+            });
+            let y = f(&xd);
+            w = y.v.v;
+            dw[i] = y.d.v;
+            y.d.d
+        })
+    });
+    (w, dw, ddw)
+}
+
+// ----------------------------------------
+// arithmetic operators
+
+impl<T: num_traits::Float> core::ops::Neg for Dual<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Dual {
+            v: -self.v,
+            d: -self.d,
+        }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Add for Dual<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Dual {
+            v: self.v + rhs.v,
+            d: self.d + rhs.d,
+        }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Sub for Dual<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Dual {
+            v: self.v - rhs.v,
+            d: self.d - rhs.d,
+        }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Mul for Dual<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Dual {
+            v: self.v * rhs.v,
+            d: self.d * rhs.v + self.v * rhs.d,
+        }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Div for Dual<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        let inv = T::one() / rhs.v;
+        Dual {
+            v: self.v * inv,
+            d: (self.d - self.v * inv * rhs.d) * inv,
+        }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Rem for Dual<T> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        Dual {
+            v: self.v % rhs.v,
+            d: self.d - rhs.d * (self.v / rhs.v).trunc(),
+        }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::AddAssign for Dual<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::SubAssign for Dual<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::MulAssign for Dual<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::DivAssign for Dual<T> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::RemAssign for Dual<T> {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::iter::Sum for Dual<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::constant(T::zero()), |a, b| a + b)
+    }
+}
+
+impl<'a, T: num_traits::Float> core::iter::Sum<&'a Dual<T>> for Dual<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::constant(T::zero()), |a, &b| a + b)
+    }
+}
+
+/// the comparison only looks at the value
+impl<T: num_traits::Float> PartialEq for Dual<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.v == other.v
+    }
+}
+
+/// the comparison only looks at the value
+impl<T: num_traits::Float> PartialOrd for Dual<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        self.v.partial_cmp(&other.v)
+    }
+}
+
+impl<T: num_traits::Float + core::fmt::Display> core::fmt::Display for Dual<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{} + {}ε", self.v, self.d)
+    }
+}
+
+// ----------------------------------------
+// traits in `num_traits`
+
+impl<T: num_traits::Float> num_traits::Zero for Dual<T> {
+    fn zero() -> Self {
+        Self::constant(T::zero())
+    }
+    fn is_zero(&self) -> bool {
+        self.v.is_zero()
+    }
+}
+
+impl<T: num_traits::Float> num_traits::One for Dual<T> {
+    fn one() -> Self {
+        Self::constant(T::one())
+    }
+}
+
+impl<T: num_traits::Float> num_traits::Num for Dual<T> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(str, radix).map(Self::constant)
+    }
+}
+
+impl<T: num_traits::Float> num_traits::ToPrimitive for Dual<T> {
+    fn to_i64(&self) -> Option<i64> {
+        self.v.to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.v.to_u64()
+    }
+    fn to_f32(&self) -> Option<f32> {
+        self.v.to_f32()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        self.v.to_f64()
+    }
+}
+
+impl<T: num_traits::Float> num_traits::NumCast for Dual<T> {
+    fn from<N: num_traits::ToPrimitive>(n: N) -> Option<Self> {
+        T::from(n).map(Self::constant)
+    }
+}
+
+impl<T> num_traits::AsPrimitive<Dual<T>> for f64
+where
+    T: num_traits::Float + 'static,
+    f64: num_traits::AsPrimitive<T>,
+{
+    fn as_(self) -> Dual<T> {
+        Dual::constant(self.as_())
+    }
+}
+
+impl<T> num_traits::AsPrimitive<Dual<T>> for f32
+where
+    T: num_traits::Float + 'static,
+    f32: num_traits::AsPrimitive<T>,
+{
+    fn as_(self) -> Dual<T> {
+        Dual::constant(self.as_())
+    }
+}
+
+macro_rules! float_const {
+    ($($name:ident),*) => {
+        $(
+            fn $name() -> Self {
+                Self::constant(T::$name())
+            }
+        )*
+    };
+}
+
+impl<T: num_traits::Float + num_traits::FloatConst> num_traits::FloatConst for Dual<T> {
+    float_const!(
+        E,
+        FRAC_1_PI,
+        FRAC_1_SQRT_2,
+        FRAC_2_PI,
+        FRAC_2_SQRT_PI,
+        FRAC_PI_2,
+        FRAC_PI_3,
+        FRAC_PI_4,
+        FRAC_PI_6,
+        FRAC_PI_8,
+        LN_10,
+        LN_2,
+        LOG10_E,
+        LOG2_E,
+        PI,
+        SQRT_2,
+        TAU,
+        LOG10_2,
+        LOG2_10
+    );
+}
+
+impl<T: num_traits::Float> num_traits::Float for Dual<T> {
+    fn nan() -> Self {
+        Self::constant(T::nan())
+    }
+    fn infinity() -> Self {
+        Self::constant(T::infinity())
+    }
+    fn neg_infinity() -> Self {
+        Self::constant(T::neg_infinity())
+    }
+    fn neg_zero() -> Self {
+        Self::constant(T::neg_zero())
+    }
+    fn min_value() -> Self {
+        Self::constant(T::min_value())
+    }
+    fn min_positive_value() -> Self {
+        Self::constant(T::min_positive_value())
+    }
+    fn epsilon() -> Self {
+        Self::constant(T::epsilon())
+    }
+    fn max_value() -> Self {
+        Self::constant(T::max_value())
+    }
+    fn is_nan(self) -> bool {
+        self.v.is_nan() || self.d.is_nan()
+    }
+    fn is_infinite(self) -> bool {
+        self.v.is_infinite() || self.d.is_infinite()
+    }
+    fn is_finite(self) -> bool {
+        self.v.is_finite() && self.d.is_finite()
+    }
+    fn is_normal(self) -> bool {
+        self.v.is_normal()
+    }
+    fn classify(self) -> core::num::FpCategory {
+        self.v.classify()
+    }
+    fn floor(self) -> Self {
+        Self::constant(self.v.floor())
+    }
+    fn ceil(self) -> Self {
+        Self::constant(self.v.ceil())
+    }
+    fn round(self) -> Self {
+        Self::constant(self.v.round())
+    }
+    fn trunc(self) -> Self {
+        Self::constant(self.v.trunc())
+    }
+    fn fract(self) -> Self {
+        Dual::new(self.v.fract(), self.d)
+    }
+    fn abs(self) -> Self {
+        if self.v < T::zero() {
+            -self
+        } else {
+            self
+        }
+    }
+    fn signum(self) -> Self {
+        Self::constant(self.v.signum())
+    }
+    fn is_sign_positive(self) -> bool {
+        self.v.is_sign_positive()
+    }
+    fn is_sign_negative(self) -> bool {
+        self.v.is_sign_negative()
+    }
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+    fn recip(self) -> Self {
+        let inv = self.v.recip();
+        self.chain(inv, -inv * inv)
+    }
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            return Self::constant(T::one());
+        }
+        let n_t = T::from(n).unwrap();
+        self.chain(self.v.powi(n), n_t * self.v.powi(n - 1))
+    }
+    fn powf(self, n: Self) -> Self {
+        let v = self.v.powf(n.v);
+        let d0 = n.v * self.v.powf(n.v - T::one()) * self.d;
+        if n.d.is_zero() {
+            // avoid `ln` of a non-positive value when the exponent is constant
+            return Dual::new(v, d0);
+        }
+        Dual::new(v, d0 + v * self.v.ln() * n.d)
+    }
+    fn sqrt(self) -> Self {
+        let s = self.v.sqrt();
+        self.chain(s, T::one() / (s + s))
+    }
+    fn exp(self) -> Self {
+        let e = self.v.exp();
+        self.chain(e, e)
+    }
+    fn exp2(self) -> Self {
+        let e = self.v.exp2();
+        self.chain(e, e * T::from(core::f64::consts::LN_2).unwrap())
+    }
+    fn ln(self) -> Self {
+        self.chain(self.v.ln(), self.v.recip())
+    }
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+    fn log2(self) -> Self {
+        let ln2 = T::from(core::f64::consts::LN_2).unwrap();
+        self.chain(self.v.log2(), (self.v * ln2).recip())
+    }
+    fn log10(self) -> Self {
+        let ln10 = T::from(core::f64::consts::LN_10).unwrap();
+        self.chain(self.v.log10(), (self.v * ln10).recip())
+    }
+    fn max(self, other: Self) -> Self {
+        if self.v >= other.v || other.v.is_nan() {
+            self
+        } else {
+            other
+        }
+    }
+    fn min(self, other: Self) -> Self {
+        if self.v <= other.v || other.v.is_nan() {
+            self
+        } else {
+            other
+        }
+    }
+    fn abs_sub(self, other: Self) -> Self {
+        if self.v > other.v {
+            self - other
+        } else {
+            Self::constant(T::zero())
+        }
+    }
+    fn cbrt(self) -> Self {
+        let c = self.v.cbrt();
+        let three = T::from(3).unwrap();
+        self.chain(c, (three * c * c).recip())
+    }
+    fn hypot(self, other: Self) -> Self {
+        (self * self + other * other).sqrt()
+    }
+    fn sin(self) -> Self {
+        self.chain(self.v.sin(), self.v.cos())
+    }
+    fn cos(self) -> Self {
+        self.chain(self.v.cos(), -self.v.sin())
+    }
+    fn tan(self) -> Self {
+        let t = self.v.tan();
+        self.chain(t, T::one() + t * t)
+    }
+    fn asin(self) -> Self {
+        self.chain(self.v.asin(), (T::one() - self.v * self.v).sqrt().recip())
+    }
+    fn acos(self) -> Self {
+        self.chain(self.v.acos(), -(T::one() - self.v * self.v).sqrt().recip())
+    }
+    fn atan(self) -> Self {
+        self.chain(self.v.atan(), (T::one() + self.v * self.v).recip())
+    }
+    fn atan2(self, other: Self) -> Self {
+        let r2 = self.v * self.v + other.v * other.v;
+        Dual::new(
+            self.v.atan2(other.v),
+            (self.d * other.v - self.v * other.d) / r2,
+        )
+    }
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+    fn exp_m1(self) -> Self {
+        self.chain(self.v.exp_m1(), self.v.exp())
+    }
+    fn ln_1p(self) -> Self {
+        self.chain(self.v.ln_1p(), (T::one() + self.v).recip())
+    }
+    fn sinh(self) -> Self {
+        self.chain(self.v.sinh(), self.v.cosh())
+    }
+    fn cosh(self) -> Self {
+        self.chain(self.v.cosh(), self.v.sinh())
+    }
+    fn tanh(self) -> Self {
+        let t = self.v.tanh();
+        self.chain(t, T::one() - t * t)
+    }
+    fn asinh(self) -> Self {
+        self.chain(self.v.asinh(), (self.v * self.v + T::one()).sqrt().recip())
+    }
+    fn acosh(self) -> Self {
+        self.chain(self.v.acosh(), (self.v * self.v - T::one()).sqrt().recip())
+    }
+    fn atanh(self) -> Self {
+        self.chain(self.v.atanh(), (T::one() - self.v * self.v).recip())
+    }
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.v.integer_decode()
+    }
+}
+
+// ----------------------------------------
+// below: checking hand-written derivatives against the automatic differentiation
+
+#[cfg(test)]
+fn dual_slice<const N: usize>(x: &[f64; N], i: usize) -> [Dual<f64>; N] {
+    core::array::from_fn(|j| Dual::new(x[j], if i == j { 1. } else { 0. }))
+}
+
+#[test]
+fn test_elementary_functions() {
+    use num_traits::Float;
+    type F = fn(Dual<f64>) -> Dual<f64>;
+    let funcs: [(F, f64); 24] = [
+        (|x| x.sqrt(), 0.7),
+        (|x| x.exp(), 0.3),
+        (|x| x.exp2(), 0.3),
+        (|x| x.ln(), 0.7),
+        (|x| x.log2(), 0.7),
+        (|x| x.log10(), 0.7),
+        (|x| x.log(Dual::constant(3.)), 0.7),
+        (|x| x.powi(3), -0.7),
+        (|x| x.powf(Dual::constant(2.5)), 0.7),
+        (|x| x.powf(x), 0.7),
+        (|x| x.recip(), 0.7),
+        (|x| x.cbrt(), 0.7),
+        (|x| x.sin(), 0.7),
+        (|x| x.cos(), 0.7),
+        (|x| x.tan(), 0.7),
+        (|x| x.asin(), 0.3),
+        (|x| x.acos(), 0.3),
+        (|x| x.atan(), 0.3),
+        (|x| x.sinh() * x.cosh(), 0.3),
+        (|x| x.tanh() + x.asinh(), 0.3),
+        (|x| x.acosh(), 1.3),
+        (|x| x.atanh() + x.exp_m1() + x.ln_1p(), 0.3),
+        (
+            |x| x.atan2(Dual::constant(-0.3)) + Dual::constant(0.3).atan2(x),
+            0.4,
+        ),
+        (|x| x.hypot(x * x) % Dual::constant(0.5), 0.7),
+    ];
+    let eps = 1.0e-6;
+    for (f, x) in funcs {
+        let y = f(Dual::variable(x));
+        let yp = f(Dual::constant(x + eps)).v;
+        let ym = f(Dual::constant(x - eps)).v;
+        let diff = (yp - ym) / (2. * eps);
+        assert!(
+            (y.d - diff).abs() < 1.0e-6 * (1. + diff.abs()),
+            "{} {}",
+            y.d,
+            diff
+        );
+    }
+}
+
+#[test]
+fn test_hessian() {
+    // hessian of the dihedral angle with the hyper-dual number
+    let p: [[f64; 3]; 4] = [
+        [0.1, 0.2, 0.0],
+        [1.1, -0.1, 0.2],
+        [0.4, 1.0, 0.3],
+        [0.6, -0.9, 0.5],
+    ];
+    let x0: [f64; 12] = core::array::from_fn(|k| p[k / 3][k % 3]);
+    let (w0, dw0, ddw0) = crate::tri3::wdwddw_dihedral_angle(&p);
+    let (w1, dw1, ddw1) = hessian(
+        |x| {
+            let p = core::array::from_fn(|i| core::array::from_fn(|j| x[i * 3 + j]));
+            crate::tri3::wdwddw_dihedral_angle(&p).0
+        },
+        &x0,
+    );
+    assert!((w0 - w1).abs() < 1.0e-12);
+    for i in 0..12 {
+        assert!((dw0[i] - dw1[i]).abs() < 1.0e-10);
+        for j in 0..12 {
+            assert!((ddw0[i][j] - ddw1[i][j]).abs() < 1.0e-10);
+        }
+    }
+    // stable Neo-Hookean energy of a tetrahedron
+    let p_rest = [
+        [0.1, 0.0, 0.2],
+        [1.2, 0.1, 0.0],
+        [0.2, 0.9, 0.1],
+        [0.0, 0.2, 1.1],
+    ];
+    let p = [
+        [0.0, 0.1, 0.2],
+        [1.0, 0.2, -0.1],
+        [0.3, 1.1, 0.1],
+        [0.1, 0.1, 0.9],
+    ];
+    let x0: [f64; 12] = core::array::from_fn(|k| p[k / 3][k % 3]);
+    let (w0, dw0, ddw0) = crate::tet::wdwddw_stable_neo_hookean(&p, &p_rest, 1.3, 0.7, false);
+    let (w1, dw1, ddw1) = hessian(
+        |x| {
+            let p = core::array::from_fn(|i| core::array::from_fn(|j| x[i * 3 + j]));
+            let p_rest = p_rest.map(|q| q.map(|v| Dual::constant(Dual::constant(v))));
+            let lambda = Dual::constant(Dual::constant(1.3));
+            let myu = Dual::constant(Dual::constant(0.7));
+            crate::tet::wdwddw_stable_neo_hookean(&p, &p_rest, lambda, myu, false).0
+        },
+        &x0,
+    );
+    assert!((w0 - w1).abs() < 1.0e-12);
+    for i in 0..12 {
+        assert!((dw0[i] - dw1[i]).abs() < 1.0e-10);
+        for j in 0..12 {
+            assert!((ddw0[i][j] - ddw1[i][j]).abs() < 1.0e-10);
+        }
+    }
+}
+
+#[test]
+fn test_dldw_area() {
+    let p: [[f64; 2]; 3] = [[0.1, -0.2], [1.3, 0.2], [0.6, 0.45]];
+    let dldarea = 1.3;
+    let dl = crate::tri2::dldw_area(&p[0], &p[1], &p[2], dldarea);
+    let dl = [dl.0, dl.1, dl.2];
+    let x0: [f64; 6] = core::array::from_fn(|k| p[k / 2][k % 2]);
+    let (_, dw) = gradient(
+        |x| {
+            let l = crate::tri2::area(
+                arrayref::array_ref![x, 0, 2],
+                arrayref::array_ref![x, 2, 2],
+                arrayref::array_ref![x, 4, 2],
+            );
+            l * Dual::constant(dldarea)
+        },
+        &x0,
+    );
+    for k in 0..6 {
+        assert!((dw[k] - dl[k / 2][k % 2]).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn test_dldw_intersection_edge2() {
+    let (dldr0, dldr1) = (0.3f64, 0.65);
+    let p: [[f64; 2]; 4] = [[0.01, 0.03], [1.02, 0.05], [0.03, -0.102], [0.203, 0.105]];
+    let dl = crate::edge2::dldw_intersection_edge2(&p[0], &p[1], &p[2], &p[3], dldr0, dldr1);
+    let dl = [dl.0, dl.1, dl.2, dl.3];
+    let x0: [f64; 8] = core::array::from_fn(|k| p[k / 2][k % 2]);
+    let (_, dw) = gradient(
+        |x| {
+            let p: [[Dual<f64>; 2]; 4] = core::array::from_fn(|i| [x[i * 2], x[i * 2 + 1]]);
+            let (r0, r1) = crate::edge2::intersection_edge2(&p[0], &p[1], &p[2], &p[3]).unwrap();
+            r0 * Dual::constant(dldr0) + r1 * Dual::constant(dldr1)
+        },
+        &x0,
+    );
+    for k in 0..8 {
+        assert!((dw[k] - dl[k / 2][k % 2]).abs() < 1.0e-10);
+    }
+}
+
+#[test]
+fn test_wdw_angle_between_two_vecs() {
+    let uv = [3f64.sqrt(), 1.0, -1.0, 1.0];
+    let (w0, dw0) = crate::vec2::wdw_angle_between_two_vecs(&[uv[0], uv[1]], &[uv[2], uv[3]]);
+    for i in 0..4 {
+        let x = dual_slice(&uv, i);
+        let (w1, _) = crate::vec2::wdw_angle_between_two_vecs(&[x[0], x[1]], &[x[2], x[3]]);
+        assert!((w0 - w1.v).abs() < 1.0e-12);
+        assert!((dw0[i / 2][i % 2] - w1.d).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn test_wdw_projected_spd_mat3() {
+    let p_mat = [1., 2., 4., 3., 2., 0.];
+    let quat0 = crate::quaternion::normalized(&[-3., -2., 0., -1.]);
+    let d0: [f64; 3] = [0.1, 3.0, 1.0];
+    let (abc0, dabcdt) = crate::mat2_sym::wdw_projected_spd_mat3(&p_mat, &quat0, &d0);
+    let xyz0 = crate::mat2_sym::safe_inverse(&abc0);
+    let dxyzdt = crate::mat2_sym::wdw_inverse(&dabcdt, &xyz0);
+    // the last three parameters are the infinitesimal rotation `R = exp([w]x) R0`
+    let x0 = [d0[0], d0[1], d0[2], 0., 0., 0.];
+    for i in 0..6 {
+        let x = dual_slice(&x0, i);
+        let qw = crate::quaternion::from_axisangle(&[x[3], x[4], x[5]]);
+        let quat = crate::quaternion::mult_quaternion(&qw, &quat0.map(Dual::constant));
+        let p_mat = p_mat.map(Dual::constant);
+        let (abc, _) = crate::mat2_sym::wdw_projected_spd_mat3(&p_mat, &quat, &[x[0], x[1], x[2]]);
+        let xyz = crate::mat2_sym::safe_inverse(&abc);
+        for k in 0..3 {
+            assert!((abc[k].v - abc0[k]).abs() < 1.0e-10);
+            assert!(
+                (abc[k].d - dabcdt[k][i]).abs() < 1.0e-8,
+                "{} {} {}",
+                k,
+                i,
+                abc[k].d
+            );
+            assert!((xyz[k].d - dxyzdt[k][i]).abs() < 1.0e-8);
+        }
+    }
+}
+
+#[test]
+fn test_generic_functions() {
+    // derivative of the triangle area w.r.t. the vertex position
+    let p: [[f64; 3]; 3] = [[0.1, 0.2, 0.3], [1.2, 0.1, -0.2], [0.3, 0.9, 0.4]];
+    let x0: [f64; 9] = core::array::from_fn(|k| p[k / 3][k % 3]);
+    let (a0, da) = gradient(
+        |x| {
+            crate::tri3::area(
+                arrayref::array_ref![x, 0, 3],
+                arrayref::array_ref![x, 3, 3],
+                arrayref::array_ref![x, 6, 3],
+            )
+        },
+        &x0,
+    );
+    assert!((a0 - crate::tri3::area(&p[0], &p[1], &p[2])).abs() < 1.0e-12);
+    // the gradient of the area is perpendicular to the normal
+    let n = crate::tri3::normal(&p[0], &p[1], &p[2]);
+    for i in 0..3 {
+        let g = [da[i * 3], da[i * 3 + 1], da[i * 3 + 2]];
+        assert!(crate::vec3::dot(&g, &n).abs() < 1.0e-12);
+    }
+    // rotation matrix from the quaternion stays orthogonal along the derivative
+    let q0 = crate::quaternion::normalized(&[0.3, -0.2, 0.5, 0.8f64]);
+    let q = core::array::from_fn(|i| Dual::new(q0[i], [0.1, 0.2, -0.3, 0.0][i]));
+    let q = crate::quaternion::normalized(&q);
+    let r = crate::quaternion::to_mat3_col_major(&q);
+    let rtr = crate::mat3_col_major::mult_mat_col_major(&crate::mat3_col_major::transpose(&r), &r);
+    for (k, v) in rtr.iter().enumerate() {
+        let id = if k % 4 == 0 { 1. } else { 0. };
+        assert!((v.v - id).abs() < 1.0e-12);
+        assert!(v.d.abs() < 1.0e-12);
+    }
+    // derivative of the inverse of a symmetric matrix `d(A^-1) = -A^-1 dA A^-1`
+    let abc = [
+        Dual::new(2.0f64, 0.3),
+        Dual::new(0.5, -0.2),
+        Dual::new(1.5, 0.1),
+    ];
+    let inv = crate::mat2_sym::inverse(&abc).unwrap();
+    let (x, y, z) = (inv[0].v, inv[1].v, inv[2].v);
+    let (da, db, dc) = (-abc[0].d, -abc[1].d, -abc[2].d);
+    assert!((inv[0].d - (x * da * x + 2. * x * db * y + y * dc * y)).abs() < 1.0e-12);
+    assert!((inv[1].d - (x * da * y + z * db * x + y * db * y + y * dc * z)).abs() < 1.0e-12);
+    assert!((inv[2].d - (y * da * y + 2. * z * db * y + z * dc * z)).abs() < 1.0e-12);
+}
@@ -2,7 +2,7 @@
 
 pub fn length<T, const N: usize>(p0: &[T; N], p1: &[T; N]) -> T
 where
-    T: num_traits::Float + std::ops::AddAssign,
+    T: num_traits::Float + core::ops::AddAssign,
 {
     let mut x = T::zero();
     for i in 0..N {
@@ -1,6 +1,8 @@
 //! methods for 2D edge (line segment)
 
 use num_traits::AsPrimitive;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
 pub fn length<T>(ps: &[T; 2], pe: &[T; 2]) -> T
 where
@@ -110,7 +112,7 @@ pub fn dldw_intersection_edge2<T>(
     dldr1: T,
 ) -> ([T; 2], [T; 2], [T; 2], [T; 2])
 where
-    T: num_traits::Float + std::fmt::Debug,
+    T: num_traits::Float + core::fmt::Debug,
 {
     let one = T::one();
     let a1 = crate::tri2::area(s0, e0, s1);
@@ -187,14 +189,14 @@ where
     if a.is_zero() {
         return (
             0.5f64.as_(),
-            std::array::from_fn(|i| (ps[i] + pe[i]) * 0.5f64.as_()),
+            core::array::from_fn(|i| (ps[i] + pe[i]) * 0.5f64.as_()),
         );
     }
     let b = crate::vec2::dot(&d, ps);
     let r0 = (-b / a).clamp(0f64.as_(), 1f64.as_());
     (
         r0,
-        std::array::from_fn(|i| (-r0 + 1f64.as_()) * ps[i] + r0 * pe[i]),
+        core::array::from_fn(|i| (-r0 + 1f64.as_()) * ps[i] + r0 * pe[i]),
     )
 }
 
@@ -241,7 +243,7 @@ pub fn intersection_length_against_aabb2(ps: &[f32; 2], pe: &[f32; 2], aabb2: &[
 
 #[test]
 fn test_intersection_length_against_aabb2() {
-    use std::f32::consts::SQRT_2;
+    use core::f32::consts::SQRT_2;
     // inside AABB
     let ps = [1.0, 1.0];
     let pe = [2.0, 2.0];
@@ -292,43 +294,80 @@ fn test_intersection_length_against_aabb2() {
     assert!((length - SQRT_2).abs() < 1e-6);
 }
 
+/// list the pixels overlapping with the segment in the order along the segment.
+/// the pixel `(ix, iy)` covers the region `[ix, ix+1] x [iy, iy+1]`
+/// # return
+/// pixel indices `iy * img_width + ix`
+#[cfg(feature = "std")]
 pub fn overlapping_pixels_dda<Real>(
     (img_width, img_height): (usize, usize),
     p0: &[Real; 2],
     p1: &[Real; 2],
 ) -> Vec<usize>
 where
-    Real: num_traits::Float + 'static + Copy + AsPrimitive<usize> + std::fmt::Debug,
-    usize: AsPrimitive<Real>,
+    Real: num_traits::Float,
 {
-    let width_f: Real = img_width.as_();
-    let height_f: Real = img_height.as_();
-    let zero = Real::zero();
-    let dx = p1[0] - p0[0];
-    let dy = p1[1] - p0[1];
-    let step = if dx.abs() > dy.abs() {
-        dx.abs()
-    } else {
-        dy.abs()
-    };
-    let slope_y = dy / step;
-    let slope_x = dx / step;
-    let mut x = p0[0];
-    let mut y = p0[1];
-    let mut res = vec![];
-    while (x - p0[0]).abs() <= (p1[0] - p0[0]).abs() && (y - p0[1]).abs() <= (p1[1] - p0[1]).abs() {
-        if x >= zero && x < width_f && y >= zero && y < height_f {
-            let ix: usize = x.as_();
-            let iy: usize = y.as_();
-            res.push(iy * img_width + ix);
+    pixels_along_segment((img_width, img_height), p0, p1)
+        .into_iter()
+        .map(|v| v.0)
+        .collect()
+}
+
+/// exact traversal of the pixels intersected by the segment (Amanatides & Woo 1987).
+/// the pixel `(ix, iy)` covers the region `[ix, ix+1] x [iy, iy+1]`
+/// # return
+/// pixel indices `iy * img_width + ix` with the parameters where the segment enters and exits the pixels.
+/// the parameter is `0` at `p0` and `1` at `p1`
+#[cfg(feature = "std")]
+pub fn pixels_along_segment<Real>(
+    (img_width, img_height): (usize, usize),
+    p0: &[Real; 2],
+    p1: &[Real; 2],
+) -> Vec<(usize, Real, Real)>
+where
+    Real: num_traits::Float,
+{
+    let dir = [p1[0] - p0[0], p1[1] - p0[1]];
+    crate::voxel::traverse_grid(
+        &[Real::zero(); 2],
+        &[Real::one(); 2],
+        &[img_width, img_height],
+        p0,
+        &dir,
+        (Real::zero(), Real::one()),
+    )
+    .into_iter()
+    .map(|(idx, t0, t1)| (idx[1] * img_width + idx[0], t0, t1))
+    .collect()
+}
+
+#[test]
+fn test_pixels_along_segment() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let (w, h) = (16usize, 12usize);
+    for _itr in 0..100 {
+        let p0 = [rng.gen::<f32>() * 20. - 2., rng.gen::<f32>() * 16. - 2.];
+        let p1 = [rng.gen::<f32>() * 20. - 2., rng.gen::<f32>() * 16. - 2.];
+        let pixels = overlapping_pixels_dda((w, h), &p0, &p1);
+        assert!(pixels.iter().all(|&i_pix| i_pix < w * h));
+        for i in 0..1000 {
+            let t = i as f32 / 999.;
+            let q = [p0[0] + (p1[0] - p0[0]) * t, p0[1] + (p1[1] - p0[1]) * t];
+            if q[0] < 0. || q[1] < 0. || q[0] >= w as f32 || q[1] >= h as f32 {
+                continue;
+            }
+            let i_pix = (q[1] as usize) * w + (q[0] as usize);
+            assert!(pixels.contains(&i_pix));
         }
-        x = x + slope_x;
-        y = y + slope_y;
-    }
-    {
-        let ix: usize = x.as_();
-        let iy: usize = y.as_();
-        res.push(iy * img_width + ix);
     }
-    res
+    // the segment clipping the corner of a pixel
+    let pixels = pixels_along_segment((4, 4), &[0.5f64, 1.8], &[1.8, 0.5]);
+    assert_eq!(
+        pixels.iter().map(|v| v.0).collect::<Vec<_>>(),
+        vec![4, 5, 1]
+    );
+    assert!((pixels[1].1 - 0.5 / 1.3).abs() < 1.0e-6);
+    assert!((pixels[1].2 - 0.8 / 1.3).abs() < 1.0e-6);
 }
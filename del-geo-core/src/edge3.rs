@@ -9,7 +9,7 @@ pub trait Edge3Trait<T> {
 }
 impl<Real> Edge3Trait<Real> for [Real; 3]
 where
-    Real: num_traits::Float + 'static + std::ops::MulAssign,
+    Real: num_traits::Float + 'static + core::ops::MulAssign,
     f64: AsPrimitive<Real>,
 {
     fn length(&self, other: &Self) -> Real {
@@ -44,20 +44,76 @@ where
 
 pub fn nearest_to_point3<T>(p0: &[T; 3], p1: &[T; 3], point_pos: &[T; 3]) -> [T; 3]
 where
-    T: num_traits::Float + 'static + Copy + PartialOrd + std::ops::MulAssign,
+    T: num_traits::Float + 'static + Copy + PartialOrd + core::ops::MulAssign,
     f64: AsPrimitive<T>,
 {
     use crate::vec3::Vec3;
-    let d = std::array::from_fn(|i| p1[i] - p0[i]);
+    let d = core::array::from_fn(|i| p1[i] - p0[i]);
     let t = {
-        if d.dot(&d) > T::epsilon() {
-            let ps = std::array::from_fn(|i| p0[i] - point_pos[i]);
+        if d.dot(&d) <= T::epsilon() {
+            0.5_f64.as_()
+        } else {
+            let ps = core::array::from_fn(|i| p0[i] - point_pos[i]);
             let a = d.dot(&d);
             let b = d.dot(&ps);
             (-b / a).clamp(0f64.as_(), 1f64.as_())
-        } else {
-            0.5_f64.as_()
         }
     };
-    std::array::from_fn(|i| p0[i] + t * d[i])
+    core::array::from_fn(|i| p0[i] + t * d[i])
+}
+
+/// overlap test between a line segment and an axis-aligned box
+/// using the separating axis theorem. touching is regarded as overlap
+pub fn is_intersect_aabb3<T>(p0: &[T; 3], p1: &[T; 3], aabb: &[T; 6]) -> bool
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let two = T::one() + T::one();
+    let c = crate::aabb3::center(aabb);
+    let h = crate::aabb3::size(aabb).map(|v| v / two);
+    // segment as its midpoint `m` and half of the direction `d`
+    let m = p0.add(p1).scale(T::one() / two).sub(&c);
+    let d = p1.sub(p0).scale(T::one() / two);
+    for i in 0..3 {
+        if m[i].abs() > h[i] + d[i].abs() {
+            return false;
+        }
+    }
+    // the cross products of the segment direction and the box axes
+    for i in 0..3 {
+        let (j, k) = ((i + 1) % 3, (i + 2) % 3);
+        let r = h[j] * d[k].abs() + h[k] * d[j].abs();
+        if (m[j] * d[k] - m[k] * d[j]).abs() > r {
+            return false;
+        }
+    }
+    true
+}
+
+#[test]
+fn test_is_intersect_aabb3() {
+    let aabb = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0f64];
+    assert!(is_intersect_aabb3(&[-1., 0.5, 0.5], &[2., 0.5, 0.5], &aabb));
+    assert!(is_intersect_aabb3(
+        &[0.2, 0.5, 0.5],
+        &[0.3, 0.5, 0.5],
+        &aabb
+    ));
+    assert!(!is_intersect_aabb3(
+        &[1.5, 0.5, 0.5],
+        &[2., 0.5, 0.5],
+        &aabb
+    ));
+    // passing near the edge of the box
+    assert!(!is_intersect_aabb3(
+        &[2.1, 0.0, 0.5],
+        &[0.0, 2.1, 0.5],
+        &aabb
+    ));
+    assert!(is_intersect_aabb3(
+        &[1.4, 0.0, 0.5],
+        &[0.0, 1.4, 0.5],
+        &aabb
+    ));
 }
@@ -0,0 +1,325 @@
+//! solid ellipse `(x - center)^T A (x - center) <= 1` where the symmetric positive definite
+//! matrix `A` is parameterized as `[a,b,c]` (see `mat2_sym`)
+
+/// lengths of the semi-axes in the descending order and the unit directions of the axes
+pub fn axes<Real>(abc: &[Real; 3]) -> ([Real; 2], [[Real; 2]; 2])
+where
+    Real: num_traits::Float,
+{
+    let (lam, dirs) = crate::mat2_sym::principal_directions(abc);
+    let dirs = dirs.map(|d| {
+        let l = (d[0] * d[0] + d[1] * d[1]).sqrt();
+        [d[0] / l, d[1] / l]
+    });
+    let radii = lam.map(|l| Real::one() / l.sqrt());
+    if radii[0] < radii[1] {
+        ([radii[1], radii[0]], [dirs[1], dirs[0]])
+    } else {
+        (radii, dirs)
+    }
+}
+
+pub fn is_include_point2<Real>(center: &[Real; 2], abc: &[Real; 3], p: &[Real; 2]) -> bool
+where
+    Real: num_traits::Float,
+{
+    let d = [p[0] - center[0], p[1] - center[1]];
+    crate::mat2_sym::mult_vec_from_both_sides(abc, &d, &d) <= Real::one()
+}
+
+/// axis-aligned bounding box `[min_x, min_y, max_x, max_y]`
+pub fn aabb2<Real>(center: &[Real; 2], abc: &[Real; 3]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    let det = abc[0] * abc[2] - abc[1] * abc[1];
+    let hx = (abc[2] / det).sqrt();
+    let hy = (abc[0] / det).sqrt();
+    [
+        center[0] - hx,
+        center[1] - hy,
+        center[0] + hx,
+        center[1] + hy,
+    ]
+}
+
+/// nearest point on the axis-aligned ellipse with the semi-axes `e0 >= e1` from the point `y`
+/// in the first quadrant. D. Eberly "Distance from a Point to an Ellipse, an Ellipsoid, or a Hyperellipsoid"
+fn nearest_in_first_quadrant<Real>(e0: Real, e1: Real, y0: Real, y1: Real) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    let two = one + one;
+    if y1 > zero {
+        if y0 <= zero {
+            return [zero, e1];
+        }
+        let (z0, z1) = (y0 / e0, y1 / e1);
+        let g = z0 * z0 + z1 * z1 - one;
+        if g.is_zero() {
+            return [y0, y1];
+        }
+        let r0 = (e0 / e1) * (e0 / e1);
+        let n0 = r0 * z0;
+        // bisection for `(n0 / (s + r0))^2 + (z1 / (s + 1))^2 = 1`
+        let mut s0 = z1 - one;
+        let mut s1 = if g < zero {
+            zero
+        } else {
+            (n0 * n0 + z1 * z1).sqrt() - one
+        };
+        let mut s = zero;
+        for _ in 0..1100 {
+            s = (s0 + s1) / two;
+            if s == s0 || s == s1 {
+                break;
+            }
+            let (ratio0, ratio1) = (n0 / (s + r0), z1 / (s + one));
+            let g = ratio0 * ratio0 + ratio1 * ratio1 - one;
+            if g > zero {
+                s0 = s;
+            } else if g < zero {
+                s1 = s;
+            } else {
+                break;
+            }
+        }
+        return [r0 * y0 / (s + r0), y1 / (s + one)];
+    }
+    let numer0 = e0 * y0;
+    let denom0 = e0 * e0 - e1 * e1;
+    if numer0 < denom0 {
+        let xde0 = numer0 / denom0;
+        [e0 * xde0, e1 * (one - xde0 * xde0).sqrt()]
+    } else {
+        [e0, zero]
+    }
+}
+
+/// nearest point on the boundary of the ellipse from the point `p`
+pub fn nearest_to_point2<Real>(center: &[Real; 2], abc: &[Real; 3], p: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let (radii, dirs) = axes(abc);
+    let d = [p[0] - center[0], p[1] - center[1]];
+    let y = dirs.map(|u| u[0] * d[0] + u[1] * d[1]);
+    let x = nearest_in_first_quadrant(radii[0], radii[1], y[0].abs(), y[1].abs());
+    let x = [x[0].copysign(y[0]), x[1].copysign(y[1])];
+    [
+        center[0] + dirs[0][0] * x[0] + dirs[1][0] * x[1],
+        center[1] + dirs[0][1] * x[0] + dirs[1][1] * x[1],
+    ]
+}
+
+/// distance from the point `p` to the boundary of the ellipse. negative if `p` is inside
+pub fn signed_distance_to_point2<Real>(center: &[Real; 2], abc: &[Real; 3], p: &[Real; 2]) -> Real
+where
+    Real: num_traits::Float,
+{
+    let q = nearest_to_point2(center, abc, p);
+    let dist = ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt();
+    if is_include_point2(center, abc, p) {
+        -dist
+    } else {
+        dist
+    }
+}
+
+/// exact overlap test between the solid ellipse and the axis-aligned box `[min_x, min_y, max_x, max_y]`
+pub fn is_intersect_aabb2<Real>(center: &[Real; 2], abc: &[Real; 3], aabb: &[Real; 4]) -> bool
+where
+    Real: num_traits::Float,
+{
+    if crate::aabb2::is_include_point2(aabb, center) {
+        return true;
+    }
+    // the minimum of the convex quadratic form over the box is on the boundary
+    let corners = [
+        [aabb[0], aabb[1]],
+        [aabb[2], aabb[1]],
+        [aabb[2], aabb[3]],
+        [aabb[0], aabb[3]],
+    ];
+    (0..4).any(|i| {
+        let p = corners[i];
+        let q = corners[(i + 1) % 4];
+        let d = [p[0] - center[0], p[1] - center[1]];
+        let e = [q[0] - p[0], q[1] - p[1]];
+        let eae = crate::mat2_sym::mult_vec_from_both_sides(abc, &e, &e);
+        let t = if eae > Real::zero() {
+            let t = -crate::mat2_sym::mult_vec_from_both_sides(abc, &e, &d) / eae;
+            t.max(Real::zero()).min(Real::one())
+        } else {
+            Real::zero()
+        };
+        let r = [d[0] + t * e[0], d[1] + t * e[1]];
+        crate::mat2_sym::mult_vec_from_both_sides(abc, &r, &r) <= Real::one()
+    })
+}
+
+/// exact overlap test between two solid ellipses
+pub fn is_intersect_ellipse<Real>(
+    center0: &[Real; 2],
+    abc0: &[Real; 3],
+    center1: &[Real; 2],
+    abc1: &[Real; 3],
+) -> bool
+where
+    Real: num_traits::Float,
+{
+    // coordinate `y` such that `x = center0 + U diag(radii) y` maps the first ellipse to the unit disk
+    let (radii, dirs) = axes(abc0);
+    let d = [center1[0] - center0[0], center1[1] - center0[1]];
+    let c1: [Real; 2] =
+        core::array::from_fn(|k| (dirs[k][0] * d[0] + dirs[k][1] * d[1]) / radii[k]);
+    let m = |k: usize, l: usize| {
+        crate::mat2_sym::mult_vec_from_both_sides(abc1, &dirs[k], &dirs[l]) * radii[k] * radii[l]
+    };
+    let abc1 = [m(0, 0), m(0, 1), m(1, 1)];
+    let origin = [Real::zero(); 2];
+    if is_include_point2(&c1, &abc1, &origin) {
+        return true;
+    }
+    let q = nearest_to_point2(&c1, &abc1, &origin);
+    q[0] * q[0] + q[1] * q[1] <= Real::one()
+}
+
+/// indices of the tiles overlapping with the ellipse. tighter than `aabb2::overlapping_tiles`
+#[cfg(feature = "std")]
+pub fn overlapping_tiles<Real>(
+    center: &[Real; 2],
+    abc: &[Real; 3],
+    tile_size: usize,
+    tile_shape: (usize, usize),
+) -> std::collections::BTreeSet<usize>
+where
+    Real: num_traits::Float,
+{
+    let aabb = aabb2(center, abc).map(|v| v.to_f32().unwrap());
+    let ts = Real::from(tile_size).unwrap();
+    crate::aabb2::overlapping_tiles(&aabb, tile_size, tile_shape)
+        .into_iter()
+        .filter(|&i_tile| {
+            let ix = Real::from(i_tile % tile_shape.0).unwrap();
+            let iy = Real::from(i_tile / tile_shape.0).unwrap();
+            let tile = [
+                ix * ts,
+                iy * ts,
+                (ix + Real::one()) * ts,
+                (iy + Real::one()) * ts,
+            ];
+            is_intersect_aabb2(center, abc, &tile)
+        })
+        .collect()
+}
+
+#[test]
+fn test_nearest_to_point2() {
+    let center = [0.3, -0.2];
+    let abc = [2.0f64, 0.7, 0.5];
+    let (radii, dirs) = axes(&abc);
+    for k in 0..2 {
+        let p = [
+            center[0] + dirs[k][0] * radii[k],
+            center[1] + dirs[k][1] * radii[k],
+        ];
+        assert!(
+            (crate::mat2_sym::mult_vec_from_both_sides(&abc, &dirs[k], &dirs[k])
+                * radii[k]
+                * radii[k]
+                - 1.)
+                .abs()
+                < 1.0e-12
+        );
+        assert!(signed_distance_to_point2(&center, &abc, &p).abs() < 1.0e-10);
+    }
+    // compare with the brute-force search over the boundary
+    let boundary: Vec<[f64; 2]> = (0..10000)
+        .map(|i| {
+            let t = i as f64 * 2. * core::f64::consts::PI / 10000.;
+            let (s, c) = t.sin_cos();
+            [
+                center[0] + dirs[0][0] * radii[0] * c + dirs[1][0] * radii[1] * s,
+                center[1] + dirs[0][1] * radii[0] * c + dirs[1][1] * radii[1] * s,
+            ]
+        })
+        .collect();
+    for p in [
+        [2.0, 1.0],
+        [0.4, -0.1],
+        [-1.5, 0.3],
+        [0.3, -0.2],
+        [0.0, 3.0],
+    ] {
+        let q = nearest_to_point2(&center, &abc, &p);
+        let dist = ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt();
+        let dist1 = boundary
+            .iter()
+            .map(|q| ((p[0] - q[0]).powi(2) + (p[1] - q[1]).powi(2)).sqrt())
+            .fold(f64::MAX, f64::min);
+        assert!((dist - dist1).abs() < 1.0e-3, "{} {}", dist, dist1);
+        let sd = signed_distance_to_point2(&center, &abc, &p);
+        assert!((sd.abs() - dist).abs() < 1.0e-12);
+        assert_eq!(sd < 0., is_include_point2(&center, &abc, &p));
+    }
+}
+
+#[test]
+fn test_is_intersect() {
+    let center = [0.0f64, 0.0];
+    // elongated along `x = y`
+    let abc = [2.5, -2.0, 2.5];
+    let aabb = aabb2(&center, &abc);
+    // corner of the bounding box that does not touch the ellipse
+    let corner = [aabb[0], aabb[3]];
+    let box_corner = [corner[0], corner[1] - 0.1, corner[0] + 0.1, corner[1]];
+    assert!(!is_intersect_aabb2(&center, &abc, &box_corner));
+    assert!(is_intersect_aabb2(&center, &abc, &[0.5, 0.5, 2.0, 2.0]));
+    assert!(is_intersect_aabb2(&center, &abc, &[-0.1, -0.1, 0.1, 0.1]));
+    assert!(is_intersect_aabb2(&center, &abc, &[-5.0, -0.1, 5.0, 0.1]));
+    // two thin ellipses whose bounding boxes overlap but themselves do not
+    let abc1 = [2.5, 2.0, 2.5];
+    assert!(!is_intersect_ellipse(&center, &abc, &[1.5, 1.5], &abc1));
+    assert!(is_intersect_ellipse(&center, &abc, &[1.2, 1.2], &abc1));
+    assert!(is_intersect_ellipse(
+        &center,
+        &abc,
+        &[0.1, 0.1],
+        &[100., 0., 100.]
+    ));
+    for i in 0..100 {
+        let d = -2.0 + i as f64 * 0.04;
+        let c1 = [d, -d];
+        // the ellipses overlap iff the boundary of the second one is close to the first one
+        let (radii, dirs) = axes(&abc1);
+        let hit = (0..1000).any(|j| {
+            let t = j as f64 * 2. * core::f64::consts::PI / 1000.;
+            let (s, c) = t.sin_cos();
+            let p = [
+                c1[0] + dirs[0][0] * radii[0] * c + dirs[1][0] * radii[1] * s,
+                c1[1] + dirs[0][1] * radii[0] * c + dirs[1][1] * radii[1] * s,
+            ];
+            is_include_point2(&center, &abc, &p)
+        });
+        if (d.abs() - 4. / 3.).abs() > 0.02 {
+            assert_eq!(
+                hit,
+                is_intersect_ellipse(&center, &abc, &c1, &abc1),
+                "{}",
+                d
+            );
+        }
+    }
+    // tile culling
+    let center = [24.0, 24.0];
+    let abc = [0.01, -0.009, 0.01];
+    let tiles0 =
+        crate::aabb2::overlapping_tiles(&aabb2(&center, &abc).map(|v| v as f32), 16, (4, 4));
+    let tiles1 = overlapping_tiles(&center, &abc, 16, (4, 4));
+    assert!(tiles1.is_subset(&tiles0));
+    assert!(tiles1.len() < tiles0.len());
+    assert!(tiles1.contains(&5));
+}
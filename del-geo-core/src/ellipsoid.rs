@@ -0,0 +1,243 @@
+//! solid ellipsoid `center + R diag(radii) s` with `|s| <= 1`
+//! where `R` is the rotation matrix of the quaternion `quat` (see `quaternion`)
+
+/// coordinate of the point `p` where the ellipsoid is the unit ball
+fn to_unit_ball<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    radii: &[Real; 3],
+    p: &[Real; 3],
+) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    core::array::from_fn(|k| {
+        (0..3).fold(Real::zero(), |s, i| s + r[k * 3 + i] * (p[i] - center[i])) / radii[k]
+    })
+}
+
+pub fn is_include_point3<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    radii: &[Real; 3],
+    p: &[Real; 3],
+) -> bool
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let y = to_unit_ball(center, quat, radii, p);
+    y.dot(&y) <= Real::one()
+}
+
+/// axis-aligned bounding box `[min_x, min_y, min_z, max_x, max_y, max_z]`
+pub fn aabb3<Real>(center: &[Real; 3], quat: &[Real; 4], radii: &[Real; 3]) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    let h: [Real; 3] = core::array::from_fn(|i| {
+        (0..3)
+            .fold(Real::zero(), |s, k| s + (r[k * 3 + i] * radii[k]).powi(2))
+            .sqrt()
+    });
+    [
+        center[0] - h[0],
+        center[1] - h[1],
+        center[2] - h[2],
+        center[0] + h[0],
+        center[1] + h[1],
+        center[2] + h[2],
+    ]
+}
+
+/// parameter `t` of the first intersection between the ray `ray_src + t * ray_dir` (`t >= 0`)
+/// and the surface of the ellipsoid. see `sphere::intersection_ray`
+pub fn intersection_ray<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    radii: &[Real; 3],
+    ray_src: &[Real; 3],
+    ray_dir: &[Real; 3],
+) -> Option<Real>
+where
+    Real: num_traits::Float + core::ops::MulAssign,
+{
+    // the ray parameter is invariant under the affine map to the unit ball
+    let src = to_unit_ball(center, quat, radii, ray_src);
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    let dir: [Real; 3] = core::array::from_fn(|k| {
+        (0..3).fold(Real::zero(), |s, i| s + r[k * 3 + i] * ray_dir[i]) / radii[k]
+    });
+    crate::sphere::intersection_ray(Real::one(), &[Real::zero(); 3], &src, &dir)
+}
+
+/// exact outline of the ellipsoid on the screen under the perspective projection
+/// # return
+/// `(center, abc)` of the ellipse in the pixel coordinate (see `ellipse`).
+/// `None` if the center is outside the depth range of the camera (e.g., behind the camera)
+/// or the ellipsoid crosses the plane of the camera center
+pub fn projected_outline<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    radii: &[Real; 3],
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+) -> Option<([Real; 2], [Real; 3])>
+where
+    Real: num_traits::Float,
+{
+    // the same culling as `gaussian_splat::project`
+    let pos_ndc = crate::mat4_col_major::transform_homogeneous(transform_world2ndc, center)?;
+    if pos_ndc[2].is_nan() || pos_ndc[2].abs() > Real::one() {
+        return None;
+    }
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    // the dual quadric is `H diag(1,1,1,-1) H^T` where `H` maps the unit ball to the ellipsoid
+    let cols = [
+        [
+            r[0] * radii[0],
+            r[1] * radii[0],
+            r[2] * radii[0],
+            Real::zero(),
+        ],
+        [
+            r[3] * radii[1],
+            r[4] * radii[1],
+            r[5] * radii[1],
+            Real::zero(),
+        ],
+        [
+            r[6] * radii[2],
+            r[7] * radii[2],
+            r[8] * radii[2],
+            Real::zero(),
+        ],
+        [center[0], center[1], center[2], Real::one()],
+    ];
+    // columns of `H` mapped to the homogeneous pixel coordinate
+    let g = cols.map(|x| {
+        crate::mat4_col_major::transform_to_pix_homogeneous(transform_world2ndc, img_shape, &x)
+    });
+    // dual conic of the outline
+    let dc = |i: usize, j: usize| {
+        g[0][i] * g[0][j] + g[1][i] * g[1][j] + g[2][i] * g[2][j] - g[3][i] * g[3][j]
+    };
+    let c22 = dc(2, 2);
+    if c22 >= Real::zero() {
+        return None;
+    }
+    // the dual conic is proportional to `[[A^-1 - x0 x0^T, -x0], [-x0^T, -1]]`
+    let x0 = [dc(0, 2) / c22, dc(1, 2) / c22];
+    let ainv = [
+        x0[0] * x0[0] - dc(0, 0) / c22,
+        x0[0] * x0[1] - dc(0, 1) / c22,
+        x0[1] * x0[1] - dc(1, 1) / c22,
+    ];
+    let abc = crate::mat2_sym::inverse(&ainv)?;
+    Some((x0, abc))
+}
+
+#[test]
+fn test_intersection_ray() {
+    let center = [0.1, -0.2, 0.3f64];
+    let quat = crate::quaternion::normalized(&[0.3, -0.2, 0.5, 0.8]);
+    let radii = [0.5, 0.2, 0.1];
+    let ray_src = [1.0, 0.5, 2.0];
+    let ray_dir = [-0.9, -0.7, -1.7];
+    let t = intersection_ray(&center, &quat, &radii, &ray_src, &ray_dir).unwrap();
+    let p = core::array::from_fn(|i| ray_src[i] + t * ray_dir[i]);
+    let y = to_unit_ball(&center, &quat, &radii, &p);
+    use crate::vec3::Vec3;
+    assert!((y.norm() - 1.0).abs() < 1.0e-10);
+    assert!(!is_include_point3(&center, &quat, &radii, &ray_src));
+    let p = core::array::from_fn(|i| ray_src[i] + (t + 1.0e-3) * ray_dir[i]);
+    assert!(is_include_point3(&center, &quat, &radii, &p));
+    assert!(intersection_ray(&center, &quat, &radii, &ray_src, &[0., 0., 1.]).is_none());
+    // same as the sphere for the equal radii
+    let t0 = intersection_ray(&center, &quat, &[0.3; 3], &ray_src, &ray_dir).unwrap();
+    let t1 = crate::sphere::intersection_ray(0.3, &center, &ray_src, &ray_dir).unwrap();
+    assert!((t0 - t1).abs() < 1.0e-12);
+    // bounding box touches the surface
+    let aabb = aabb3(&center, &quat, &radii);
+    let mut aabb1 = [f64::MAX, f64::MAX, f64::MAX, f64::MIN, f64::MIN, f64::MIN];
+    let r = crate::quaternion::to_mat3_col_major(&quat);
+    for i in 0..100 {
+        for j in 0..200 {
+            let theta = i as f64 * core::f64::consts::PI / 99.;
+            let phi = j as f64 * 2. * core::f64::consts::PI / 200.;
+            let s = [
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            ];
+            let p: [f64; 3] = core::array::from_fn(|i| {
+                center[i] + (0..3).fold(0., |a, k| a + r[k * 3 + i] * radii[k] * s[k])
+            });
+            for i in 0..3 {
+                aabb1[i] = aabb1[i].min(p[i]);
+                aabb1[i + 3] = aabb1[i + 3].max(p[i]);
+            }
+        }
+    }
+    for i in 0..6 {
+        assert!((aabb[i] - aabb1[i]).abs() < 1.0e-3);
+    }
+}
+
+#[test]
+fn test_projected_outline() {
+    let (transform_world2ndc, img_shape) = crate::mat4_col_major::sample_camera();
+    let center = [0.3, -0.2, 0.3];
+    let quat = crate::quaternion::normalized(&[0.1, 0.7, -0.3, 0.6]);
+    let radii = [0.3, 0.1, 0.2];
+    let (c, abc) =
+        projected_outline(&center, &quat, &radii, &transform_world2ndc, img_shape).unwrap();
+    // the projected points of the surface are inside the outline and the outline is tight
+    let r = crate::quaternion::to_mat3_col_major(&quat);
+    let mut max_q = 0f64;
+    for i in 0..100 {
+        for j in 0..200 {
+            let theta = i as f64 * core::f64::consts::PI / 99.;
+            let phi = j as f64 * 2. * core::f64::consts::PI / 200.;
+            let s = [
+                theta.sin() * phi.cos(),
+                theta.sin() * phi.sin(),
+                theta.cos(),
+            ];
+            let p: [f64; 3] = core::array::from_fn(|i| {
+                center[i] + (0..3).fold(0., |a, k| a + r[k * 3 + i] * radii[k] * s[k])
+            });
+            let ndc =
+                crate::mat4_col_major::transform_homogeneous(&transform_world2ndc, &p).unwrap();
+            let pix = [(ndc[0] + 1.) * 32., (1. - ndc[1]) * 24.];
+            let d = [pix[0] - c[0], pix[1] - c[1]];
+            max_q = max_q.max(crate::mat2_sym::mult_vec_from_both_sides(&abc, &d, &d));
+        }
+    }
+    assert!(max_q < 1. + 1.0e-10, "{}", max_q);
+    assert!(max_q > 0.999, "{}", max_q);
+    // the projection of the center is inside the outline
+    let ndc = crate::mat4_col_major::transform_homogeneous(&transform_world2ndc, &center).unwrap();
+    let pix = [(ndc[0] + 1.) * 32., (1. - ndc[1]) * 24.];
+    assert!(crate::ellipse::is_include_point2(&c, &abc, &pix));
+    // no outline if the camera is inside the ellipsoid
+    assert!(projected_outline(
+        &[0., 0., 2.],
+        &quat,
+        &radii,
+        &transform_world2ndc,
+        img_shape
+    )
+    .is_none());
+    // behind the camera
+    assert!(projected_outline(
+        &[0.3, -0.2, 3.],
+        &quat,
+        &radii,
+        &transform_world2ndc,
+        img_shape
+    )
+    .is_none());
+}
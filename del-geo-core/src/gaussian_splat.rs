@@ -0,0 +1,220 @@
+//! projection of a 3D Gaussian to the screen for the 3D Gaussian splatting.
+//!
+//! The 3D Gaussian has the mean `mean`, the rotation `quat` (see `quaternion`) and the scale `scale`
+//! such that the covariance is `R diag(scale)^2 R^T`.
+//! The camera is given by the transformation from the world to the NDC `transform_world2ndc`
+//! and the image shape `(width, height)`.
+
+/// 3D Gaussian projected on the screen
+#[derive(Debug, Clone, Copy)]
+pub struct Splat2<Real> {
+    /// center in the pixel coordinate
+    pub pos_pix: [Real; 2],
+    /// inverse of the 2D covariance `[a,b,c]` (see `mat2_sym`)
+    pub conic: [Real; 3],
+    /// bounding box of the 3-sigma ellipse in the pixel coordinate
+    pub aabb: [Real; 4],
+    /// z-coordinate in the NDC used for the depth sorting
+    pub depth: Real,
+}
+
+/// jacobian of the map from the world coordinate to the pixel coordinate (2x3 column major)
+fn jacobian_world2pix<Real>(
+    mean: &[Real; 3],
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+) -> [Real; 6]
+where
+    Real: num_traits::Float + core::fmt::Debug,
+{
+    let m = crate::mat3_col_major::from_transform_ndc2pix_generic::<Real>(img_shape);
+    // linear part of the map from the NDC to the pixel coordinate. The NDC z does not contribute
+    let ndc2pix = [m[0], m[1], m[3], m[4], Real::zero(), Real::zero()];
+    let dndcdw = crate::mat4_col_major::jacobian_transform(transform_world2ndc, mean);
+    crate::mat2x3_col_major::mult_mat3_col_major(&ndc2pix, &dndcdw)
+}
+
+/// project the 3D Gaussian on the screen.
+/// # return
+/// `None` if the mean is not in the depth range of `transform_world2ndc`, i.e.,
+/// the z-coordinate in the NDC is not in `[-1, 1]`.
+/// This culls the Gaussians behind the camera, whose projection would be mirrored
+pub fn project<Real>(
+    mean: &[Real; 3],
+    quat: &[Real; 4],
+    scale: &[Real; 3],
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+) -> Option<Splat2<Real>>
+where
+    Real: num_traits::Float + core::ops::AddAssign + core::fmt::Debug,
+{
+    let three = Real::from(3).unwrap();
+    let pos_ndc = crate::mat4_col_major::transform_homogeneous(transform_world2ndc, mean)?;
+    // the points behind the camera are mapped outside of `[-1, 1]` by the perspective division
+    if pos_ndc[2].is_nan() || pos_ndc[2].abs() > Real::one() {
+        return None;
+    }
+    let ndc2pix = crate::mat3_col_major::from_transform_ndc2pix_generic(img_shape);
+    let pos_pix =
+        crate::mat3_col_major::transform_homogeneous(&ndc2pix, &[pos_ndc[0], pos_ndc[1]])?;
+    let p_mat = jacobian_world2pix(mean, transform_world2ndc, img_shape);
+    let (sigma, _) = crate::mat2_sym::wdw_projected_spd_mat3(&p_mat, quat, scale);
+    let conic = crate::mat2_sym::safe_inverse(&sigma);
+    let aabb = crate::mat2_sym::aabb2(&conic);
+    let aabb = crate::aabb2::scale(&aabb, three);
+    let aabb = crate::aabb2::translate(&aabb, &pos_pix);
+    Some(Splat2 {
+        pos_pix,
+        conic,
+        aabb,
+        depth: pos_ndc[2],
+    })
+}
+
+impl<Real> Splat2<Real>
+where
+    Real: num_traits::Float,
+{
+    /// indices of the tiles overlapping with the bounding box. see `aabb2::overlapping_tiles`
+    #[cfg(feature = "std")]
+    pub fn overlapping_tiles(
+        &self,
+        tile_size: usize,
+        tile_shape: (usize, usize),
+    ) -> std::collections::BTreeSet<usize> {
+        let aabb = self.aabb.map(|v| v.to_f32().unwrap());
+        crate::aabb2::overlapping_tiles(&aabb, tile_size, tile_shape)
+    }
+
+    /// value of the Gaussian `exp(-d^T conic d / 2)` at the pixel position `p`
+    pub fn eval(&self, p: &[Real; 2]) -> Real {
+        let d = [p[0] - self.pos_pix[0], p[1] - self.pos_pix[1]];
+        let half = Real::one() / (Real::one() + Real::one());
+        (-half * crate::mat2_sym::mult_vec_from_both_sides(&self.conic, &d, &d)).exp()
+    }
+}
+
+/// backward pass of [`project`]
+/// * `dldpos_pix` - gradient of the loss w.r.t. the center in the pixel coordinate
+/// * `dldconic` - gradient of the loss w.r.t. the conic
+///
+/// # return
+/// `(dldmean, dldrot, dldscale)` where `dldrot` is the gradient w.r.t. the infinitesimal
+/// rotation `w` that updates the rotation as `R <- exp([w]x) R`
+pub fn backward<Real>(
+    mean: &[Real; 3],
+    quat: &[Real; 4],
+    scale: &[Real; 3],
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+    dldpos_pix: &[Real; 2],
+    dldconic: &[Real; 3],
+) -> ([Real; 3], [Real; 3], [Real; 3])
+where
+    Real: num_traits::Float + core::ops::AddAssign + core::fmt::Debug,
+{
+    use crate::dual::Dual;
+    let p_mat = jacobian_world2pix(mean, transform_world2ndc, img_shape);
+    // gradient through the center
+    let mut dldmean: [Real; 3] =
+        core::array::from_fn(|i| dldpos_pix[0] * p_mat[i * 2] + dldpos_pix[1] * p_mat[i * 2 + 1]);
+    // gradient through the conic w.r.t. the scale and the rotation
+    let (sigma, dsigmadt) = crate::mat2_sym::wdw_projected_spd_mat3(&p_mat, quat, scale);
+    let conic = crate::mat2_sym::safe_inverse(&sigma);
+    let dconicdt = crate::mat2_sym::wdw_inverse(&dsigmadt, &conic);
+    let dldt: [Real; 6] = core::array::from_fn(|i| {
+        (0..3).fold(Real::zero(), |s, k| s + dldconic[k] * dconicdt[k][i])
+    });
+    // gradient through the conic w.r.t. the mean since the jacobian depends on the position
+    let transform_world2ndc = transform_world2ndc.map(Dual::constant);
+    let quat = quat.map(Dual::constant);
+    let scale = scale.map(Dual::constant);
+    for (i, dldmean_i) in dldmean.iter_mut().enumerate() {
+        let mean: [Dual<Real>; 3] = core::array::from_fn(|j| {
+            Dual::new(mean[j], if i == j { Real::one() } else { Real::zero() })
+        });
+        let p_mat = jacobian_world2pix(&mean, &transform_world2ndc, img_shape);
+        let (sigma, _) = crate::mat2_sym::wdw_projected_spd_mat3(&p_mat, &quat, &scale);
+        let conic = crate::mat2_sym::safe_inverse(&sigma);
+        for k in 0..3 {
+            *dldmean_i += dldconic[k] * conic[k].d;
+        }
+    }
+    (
+        dldmean,
+        [dldt[3], dldt[4], dldt[5]],
+        [dldt[0], dldt[1], dldt[2]],
+    )
+}
+
+#[test]
+fn test_project() {
+    let (transform_world2ndc, img_shape) = crate::mat4_col_major::sample_camera();
+    let mean = [0.1, -0.2, 0.3];
+    let quat = crate::quaternion::normalized(&[0.3, -0.2, 0.5, 0.8]);
+    let scale = [0.05, 0.1, 0.02];
+    let splat = project(&mean, &quat, &scale, &transform_world2ndc, img_shape).unwrap();
+    // the Gaussian is one at the center and small outside of the bounding box
+    assert!((splat.eval(&splat.pos_pix) - 1.0).abs() < 1.0e-12);
+    for p in [
+        [splat.aabb[0], splat.pos_pix[1]],
+        [splat.aabb[2], splat.pos_pix[1]],
+        [splat.pos_pix[0], splat.aabb[1]],
+        [splat.pos_pix[0], splat.aabb[3]],
+    ] {
+        assert!(splat.eval(&p) <= (-4.5f64).exp() + 1.0e-10);
+    }
+    // culled if behind the camera, closer than the near plane or farther than the far plane
+    for z in [2.5, 1.8, -1.5] {
+        let mean = [mean[0], mean[1], z];
+        assert!(project(&mean, &quat, &scale, &transform_world2ndc, img_shape).is_none());
+    }
+    // in the depth range
+    for z in [-0.9, 1.4] {
+        let mean = [mean[0], mean[1], z];
+        assert!(project(&mean, &quat, &scale, &transform_world2ndc, img_shape).is_some());
+    }
+    let tiles = splat.overlapping_tiles(16, (4, 3));
+    let i_tile = (splat.pos_pix[1] as usize / 16) * 4 + splat.pos_pix[0] as usize / 16;
+    assert!(tiles.contains(&i_tile));
+    // compare the gradient with the finite difference
+    let dldpos_pix = [0.3, -0.7];
+    let dldconic = [1.3, 0.4, -0.8];
+    let loss = |mean: &[f64; 3], quat: &[f64; 4], scale: &[f64; 3]| {
+        let s = project(mean, quat, scale, &transform_world2ndc, img_shape).unwrap();
+        (0..2).fold(0., |a, i| a + dldpos_pix[i] * s.pos_pix[i])
+            + (0..3).fold(0., |a, i| a + dldconic[i] * s.conic[i])
+    };
+    let (dldmean, dldrot, dldscale) = backward(
+        &mean,
+        &quat,
+        &scale,
+        &transform_world2ndc,
+        img_shape,
+        &dldpos_pix,
+        &dldconic,
+    );
+    let l0 = loss(&mean, &quat, &scale);
+    let eps = 1.0e-7;
+    let check = |l1: f64, ana: f64| {
+        let num = (l1 - l0) / eps;
+        assert!(
+            (num - ana).abs() < 1.0e-3 * (1. + ana.abs()),
+            "{} {}",
+            num,
+            ana
+        );
+    };
+    for i in 0..3 {
+        let mut mean1 = mean;
+        mean1[i] += eps;
+        check(loss(&mean1, &quat, &scale), dldmean[i]);
+        let mut scale1 = scale;
+        scale1[i] += eps;
+        check(loss(&mean, &quat, &scale1), dldscale[i]);
+        let dq = crate::quaternion::from_axisangle(&crate::vec3::basis(i, eps));
+        let quat1 = crate::quaternion::mult_quaternion(&dq, &quat);
+        check(loss(&mean, &quat1, &scale), dldrot[i]);
+    }
+}
@@ -0,0 +1,292 @@
+//! geometry of the 2D Gaussian (surfel) for the 2D Gaussian splatting.
+//!
+//! The surfel has the center `center`, the rotation `quat` (see `quaternion`) and the scales
+//! `scale: [su, sv]`. The tangent axes `t_u`, `t_v` and the normal `n` are the columns of the rotation matrix.
+//! The point of the local tangent coordinate `(u, v)` is `center + u * su * t_u + v * sv * t_v`
+//! and the Gaussian there is `exp(-(u^2 + v^2) / 2)`.
+
+/// position of the point with the local tangent coordinate `uv`
+pub fn to_world<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    scale: &[Real; 2],
+    uv: &[Real; 2],
+) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    core::array::from_fn(|i| center[i] + uv[0] * scale[0] * r[i] + uv[1] * scale[1] * r[3 + i])
+}
+
+/// value of the Gaussian at the local tangent coordinate `uv`
+pub fn eval<Real>(uv: &[Real; 2]) -> Real
+where
+    Real: num_traits::Float,
+{
+    let half = Real::one() / (Real::one() + Real::one());
+    (-(uv[0] * uv[0] + uv[1] * uv[1]) * half).exp()
+}
+
+/// center of the surfel in the pixel coordinate
+pub fn pos_pix<Real>(
+    center: &[Real; 3],
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+) -> Option<[Real; 2]>
+where
+    Real: num_traits::Float,
+{
+    let ndc = crate::mat4_col_major::transform_homogeneous(transform_world2ndc, center)?;
+    let ndc2pix = crate::mat3_col_major::from_transform_ndc2pix_generic(img_shape);
+    crate::mat3_col_major::transform_homogeneous(&ndc2pix, &[ndc[0], ndc[1]])
+}
+
+/// homography from the local tangent coordinate `(u, v, 1)` to the homogeneous pixel coordinate
+/// # return
+/// 3x3 matrix (column major)
+pub fn homography_uv2pix<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    scale: &[Real; 2],
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    // columns of the map from `(u, v, 1)` to the world homogeneous coordinate
+    let cols = [
+        [
+            r[0] * scale[0],
+            r[1] * scale[0],
+            r[2] * scale[0],
+            Real::zero(),
+        ],
+        [
+            r[3] * scale[1],
+            r[4] * scale[1],
+            r[5] * scale[1],
+            Real::zero(),
+        ],
+        [center[0], center[1], center[2], Real::one()],
+    ];
+    let [m0, m1, m2] = cols.map(|x| {
+        crate::mat4_col_major::transform_to_pix_homogeneous(transform_world2ndc, img_shape, &x)
+    });
+    crate::mat3_col_major::from_column_vectors(&m0, &m1, &m2)
+}
+
+/// bounding box in the pixel coordinate of the disk `u^2 + v^2 <= k_sigma^2`,
+/// computed from the projection of the dual conic by the homography
+/// # return
+/// `None` if the center is outside the depth range of the camera (e.g., behind the camera)
+/// or the disk crosses the plane of the camera center
+pub fn aabb_screen<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    scale: &[Real; 2],
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+    k_sigma: Real,
+) -> Option<[Real; 4]>
+where
+    Real: num_traits::Float,
+{
+    // the same culling as `gaussian_splat::project`
+    let pos_ndc = crate::mat4_col_major::transform_homogeneous(transform_world2ndc, center)?;
+    if pos_ndc[2].is_nan() || pos_ndc[2].abs() > Real::one() {
+        return None;
+    }
+    let m = homography_uv2pix(center, quat, scale, transform_world2ndc, img_shape);
+    // dual conic of the circle `diag(1, 1, -1/k^2)` mapped by the homography
+    let kk = Real::one() / (k_sigma * k_sigma);
+    let c = |i: usize, j: usize| m[i] * m[j] + m[3 + i] * m[3 + j] - m[6 + i] * m[6 + j] * kk;
+    let c22 = c(2, 2);
+    if c22 >= Real::zero() {
+        return None;
+    }
+    // tangent line `x = s` satisfies `c00 - 2 s c02 + s^2 c22 = 0`
+    let range = |i: usize| {
+        let (c0i, cii) = (c(i, 2), c(i, i));
+        let d = (c0i * c0i - cii * c22).max(Real::zero()).sqrt();
+        let (s0, s1) = ((c0i + d) / c22, (c0i - d) / c22);
+        (s0.min(s1), s0.max(s1))
+    };
+    let (x0, x1) = range(0);
+    let (y0, y1) = range(1);
+    Some([x0, y0, x1, y1])
+}
+
+/// intersection between the ray `ray_org + t * ray_dir` and the plane of the surfel
+/// # return
+/// `(uv, t)` where `uv` is the local tangent coordinate of the hit point.
+/// `None` if the ray is parallel to the surfel
+pub fn ray_intersection<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    scale: &[Real; 2],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+) -> Option<([Real; 2], Real)>
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    let (a, b, n) = ([r[0], r[1], r[2]], [r[3], r[4], r[5]], [r[6], r[7], r[8]]);
+    let nd = n.dot(ray_dir);
+    if nd.is_zero() {
+        return None;
+    }
+    let g = center.sub(ray_org);
+    let t = n.dot(&g) / nd;
+    let e = ray_dir.scale(t).sub(&g);
+    Some(([a.dot(&e) / scale[0], b.dot(&e) / scale[1]], t))
+}
+
+/// [`ray_intersection`] and its derivative
+/// # return
+/// `([u, v, t], d[u, v, t]/d[center, quat, scale])` where the `9` parameters are ordered as
+/// `center` (3), `quat` (4) and `scale` (2)
+#[allow(clippy::type_complexity)]
+pub fn wdw_ray_intersection<Real>(
+    center: &[Real; 3],
+    quat: &[Real; 4],
+    scale: &[Real; 2],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+) -> Option<([Real; 3], [[Real; 9]; 3])>
+where
+    Real: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    let drdq = crate::quaternion::jacobian_to_mat3_col_major(quat);
+    let (a, b, n) = ([r[0], r[1], r[2]], [r[3], r[4], r[5]], [r[6], r[7], r[8]]);
+    let nd = n.dot(ray_dir);
+    if nd.is_zero() {
+        return None;
+    }
+    let g = center.sub(ray_org);
+    let t = n.dot(&g) / nd;
+    let e = ray_dir.scale(t).sub(&g);
+    let uv = [a.dot(&e) / scale[0], b.dot(&e) / scale[1]];
+    // derivative of `t` w.r.t. the center and the normal
+    let dtdp = n.scale(Real::one() / nd);
+    let dtdn = e.scale(-Real::one() / nd);
+    let mut dw = [[Real::zero(); 9]; 3];
+    dw[2][..3].copy_from_slice(&dtdp);
+    for k in 0..4 {
+        dw[2][3 + k] = (0..3).fold(Real::zero(), |s, i| s + dtdn[i] * drdq[6 + i][k]);
+    }
+    for (iw, axis) in [a, b].iter().enumerate() {
+        let s = scale[iw];
+        let ad = axis.dot(ray_dir);
+        for i in 0..3 {
+            // `d(axis.e)/dp = (axis.d) dt/dp - axis`
+            dw[iw][i] = (ad * dtdp[i] - axis[i]) / s;
+        }
+        for k in 0..4 {
+            // `axis.e` depends on the quaternion through the axis and the normal
+            let dda = (0..3).fold(Real::zero(), |acc, i| acc + e[i] * drdq[iw * 3 + i][k]);
+            let ddn = (0..3).fold(Real::zero(), |acc, i| acc + ad * dtdn[i] * drdq[6 + i][k]);
+            dw[iw][3 + k] = (dda + ddn) / s;
+        }
+        dw[iw][7 + iw] = -uv[iw] / s;
+    }
+    Some(([uv[0], uv[1], t], dw))
+}
+
+/// normal of the surfel and its derivative w.r.t. the quaternion
+pub fn wdw_normal<Real>(quat: &[Real; 4]) -> ([Real; 3], [[Real; 4]; 3])
+where
+    Real: num_traits::Float,
+{
+    let r = crate::quaternion::to_mat3_col_major(quat);
+    let drdq = crate::quaternion::jacobian_to_mat3_col_major(quat);
+    ([r[6], r[7], r[8]], [drdq[6], drdq[7], drdq[8]])
+}
+
+#[test]
+fn test_wdw_ray_intersection() {
+    use crate::dual::Dual;
+    let center = [0.1, -0.2, 0.3f64];
+    let quat = crate::quaternion::normalized(&[0.3, -0.2, 0.5, 0.8]);
+    let scale = [0.2, 0.5];
+    let ray_org = [0.5, 0.4, 2.0];
+    let ray_dir = [-0.2, -0.3, -1.0];
+    let (w, dw) = wdw_ray_intersection(&center, &quat, &scale, &ray_org, &ray_dir).unwrap();
+    // the hit point is on the ray
+    let q = to_world(&center, &quat, &scale, &[w[0], w[1]]);
+    for i in 0..3 {
+        assert!((q[i] - (ray_org[i] + w[2] * ray_dir[i])).abs() < 1.0e-12);
+    }
+    let x0: [f64; 9] = [
+        center[0], center[1], center[2], quat[0], quat[1], quat[2], quat[3], scale[0], scale[1],
+    ];
+    for i_out in 0..3 {
+        let (_, dw1) = crate::dual::gradient(
+            |x| {
+                let (uv, t) = ray_intersection(
+                    &[x[0], x[1], x[2]],
+                    &[x[3], x[4], x[5], x[6]],
+                    &[x[7], x[8]],
+                    &ray_org.map(Dual::constant),
+                    &ray_dir.map(Dual::constant),
+                )
+                .unwrap();
+                [uv[0], uv[1], t][i_out]
+            },
+            &x0,
+        );
+        for k in 0..9 {
+            assert!((dw[i_out][k] - dw1[k]).abs() < 1.0e-10, "{} {}", i_out, k);
+        }
+    }
+    let (n, dn) = wdw_normal(&quat);
+    let (_, dn1) = crate::dual::gradient(|x| crate::quaternion::to_mat3_col_major(x)[7], &quat);
+    assert!((n[1] - crate::quaternion::to_mat3_col_major(&quat)[7]).abs() < 1.0e-12);
+    for k in 0..4 {
+        assert!((dn[1][k] - dn1[k]).abs() < 1.0e-12);
+    }
+}
+
+#[test]
+fn test_aabb_screen() {
+    let (transform_world2ndc, img_shape) = crate::mat4_col_major::sample_camera();
+    let center = [0.1, -0.2, 0.3];
+    let quat = crate::quaternion::normalized(&[-0.6, 0.1, 0.2, 0.7]);
+    let scale = [0.05, 0.1];
+    let aabb = aabb_screen(&center, &quat, &scale, &transform_world2ndc, img_shape, 3.).unwrap();
+    let c = pos_pix(&center, &transform_world2ndc, img_shape).unwrap();
+    assert!(crate::aabb2::is_include_point2(&aabb, &c));
+    // the boundary of the disk touches the four sides of the bounding box
+    let mut aabb1 = [f64::MAX, f64::MAX, f64::MIN, f64::MIN];
+    for i in 0..1000 {
+        let theta = i as f64 * 2. * core::f64::consts::PI / 1000.;
+        let uv = [3. * theta.cos(), 3. * theta.sin()];
+        let q = to_world(&center, &quat, &scale, &uv);
+        let p = pos_pix(&q, &transform_world2ndc, img_shape).unwrap();
+        crate::aabb2::add_point(&mut aabb1, &p, 0.);
+    }
+    for i in 0..4 {
+        assert!(
+            (aabb[i] - aabb1[i]).abs() < 1.0e-3,
+            "{:?} {:?}",
+            aabb,
+            aabb1
+        );
+    }
+    // behind the camera
+    assert!(aabb_screen(
+        &[0.1, -0.2, 3.],
+        &quat,
+        &scale,
+        &transform_world2ndc,
+        img_shape,
+        3.
+    )
+    .is_none());
+}
@@ -5,7 +5,7 @@ pub fn shapefunc<Real>(
     r2: Real,
 ) -> ([Real; 8], [[Real; 3]; 8], Real)
 where
-    Real: num_traits::Float + std::ops::AddAssign,
+    Real: num_traits::Float + core::ops::AddAssign,
 {
     let one = Real::one();
     let two = one + one;
@@ -73,7 +73,7 @@ pub fn grad_shapefunc_from_dndr<Real>(
     dndr: &[[Real; 3]; 8],
 ) -> ([[Real; 3]; 8], Real)
 where
-    Real: num_traits::Float + std::ops::AddAssign,
+    Real: num_traits::Float + core::ops::AddAssign,
 {
     let zero = Real::zero();
     let mut dxdr = [[zero; 3]; 3];
@@ -137,7 +137,7 @@ pub fn grad_shapefunc<Real>(
     ir3: usize,
 ) -> ([[Real; 3]; 8], Real)
 where
-    Real: num_traits::Float + std::ops::AddAssign,
+    Real: num_traits::Float + core::ops::AddAssign,
 {
     let r1 = quadrature[ir1][0];
     let r2 = quadrature[ir2][0];
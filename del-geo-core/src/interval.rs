@@ -0,0 +1,780 @@
+//! interval arithmetic for the conservative geometric queries.
+//!
+//! `Interval<T>` implements `num_traits::Float` so that generic functions in this crate
+//! can be evaluated with it to compute a guaranteed enclosure of the result.
+//! Since the rounding mode cannot be changed in Rust, the directed rounding is emulated
+//! by widening the bounds of each operation outward by at least one ulp.
+//!
+//! Two intervals are ordered only if they are disjoint, otherwise `partial_cmp` returns `None`
+//! (so `a < b` and `a >= b` are both `false`). Generic code whose branches depend on
+//! undecided comparisons does not necessarily give an enclosure.
+
+/// closed interval `[lo, hi]`
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Interval<T> {
+    pub lo: T,
+    pub hi: T,
+}
+
+/// round down by at least one ulp
+fn down<T>(x: T) -> T
+where
+    T: num_traits::Float,
+{
+    if x.is_infinite() || x.is_nan() {
+        return x;
+    }
+    x - (x.abs() * T::epsilon() + T::min_positive_value())
+}
+
+/// round up by at least one ulp
+fn up<T>(x: T) -> T
+where
+    T: num_traits::Float,
+{
+    if x.is_infinite() || x.is_nan() {
+        return x;
+    }
+    x + (x.abs() * T::epsilon() + T::min_positive_value())
+}
+
+fn min4<T: num_traits::Float>(a: T, b: T, c: T, d: T) -> T {
+    a.min(b).min(c.min(d))
+}
+
+fn max4<T: num_traits::Float>(a: T, b: T, c: T, d: T) -> T {
+    a.max(b).max(c.max(d))
+}
+
+impl<T> Interval<T>
+where
+    T: num_traits::Float,
+{
+    pub fn new(lo: T, hi: T) -> Self {
+        debug_assert!(lo <= hi || lo.is_nan() || hi.is_nan(), "invalid interval");
+        Interval { lo, hi }
+    }
+
+    /// degenerated interval containing only `v`
+    pub fn point(v: T) -> Self {
+        Interval { lo: v, hi: v }
+    }
+
+    /// the whole real line
+    pub fn entire() -> Self {
+        Interval {
+            lo: T::neg_infinity(),
+            hi: T::infinity(),
+        }
+    }
+
+    /// interval from the (possibly inexact) values `lo` and `hi` widened by one ulp
+    pub fn outward(lo: T, hi: T) -> Self {
+        Interval {
+            lo: down(lo),
+            hi: up(hi),
+        }
+    }
+
+    pub fn width(&self) -> T {
+        self.hi - self.lo
+    }
+
+    pub fn mid(&self) -> T {
+        let half = T::one() / (T::one() + T::one());
+        self.lo * half + self.hi * half
+    }
+
+    pub fn contains(&self, x: T) -> bool {
+        self.lo <= x && x <= self.hi
+    }
+
+    pub fn contains_zero(&self) -> bool {
+        self.contains(T::zero())
+    }
+
+    /// smallest interval containing both intervals
+    pub fn hull(&self, other: &Self) -> Self {
+        Interval {
+            lo: self.lo.min(other.lo),
+            hi: self.hi.max(other.hi),
+        }
+    }
+
+    /// intersection of two intervals. `None` if they are disjoint
+    pub fn intersection(&self, other: &Self) -> Option<Self> {
+        let lo = self.lo.max(other.lo);
+        let hi = self.hi.min(other.hi);
+        if lo > hi {
+            return None;
+        }
+        Some(Interval { lo, hi })
+    }
+
+    /// `true` if all the values in `self` are smaller than all the values in `other`
+    pub fn is_certainly_less(&self, other: &Self) -> bool {
+        self.hi < other.lo
+    }
+
+    /// `true` if some value in `self` is smaller than some value in `other`
+    pub fn is_possibly_less(&self, other: &Self) -> bool {
+        self.lo < other.hi
+    }
+
+    /// enclosure of a monotonically increasing function `f`
+    fn increasing<F: Fn(T) -> T>(self, f: F) -> Self {
+        Self::outward(f(self.lo), f(self.hi))
+    }
+
+    /// enclosure of a monotonically decreasing function `f`
+    fn decreasing<F: Fn(T) -> T>(self, f: F) -> Self {
+        Self::outward(f(self.hi), f(self.lo))
+    }
+
+    /// enclosure of `f` that is decreasing for `x < 0` and increasing for `x > 0`
+    fn even<F: Fn(T) -> T>(self, f: F) -> Self {
+        if self.lo >= T::zero() {
+            self.increasing(f)
+        } else if self.hi <= T::zero() {
+            self.decreasing(f)
+        } else {
+            Self::outward(f(T::zero()), f(self.lo).max(f(self.hi)))
+        }
+    }
+
+    /// conservatively check if the interval contains `phase + period * k` for some integer `k`
+    fn contains_periodic(&self, phase: T, period: T) -> bool {
+        let tol = T::epsilon() * T::from(16).unwrap();
+        let k = ((self.lo - phase) / period).ceil();
+        let x = phase + period * k;
+        x <= self.hi + (self.hi.abs() + period) * tol
+            || (x - period) >= self.lo - (self.lo.abs() + period) * tol
+    }
+
+    fn pi() -> T {
+        T::from(core::f64::consts::PI).unwrap()
+    }
+}
+
+// ----------------------------------------
+// arithmetic operators
+
+impl<T: num_traits::Float> core::ops::Neg for Interval<T> {
+    type Output = Self;
+    fn neg(self) -> Self {
+        Interval {
+            lo: -self.hi,
+            hi: -self.lo,
+        }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Add for Interval<T> {
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::outward(self.lo + rhs.lo, self.hi + rhs.hi)
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Sub for Interval<T> {
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::outward(self.lo - rhs.hi, self.hi - rhs.lo)
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Mul for Interval<T> {
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        let (a, b, c, d) = (
+            self.lo * rhs.lo,
+            self.lo * rhs.hi,
+            self.hi * rhs.lo,
+            self.hi * rhs.hi,
+        );
+        Self::outward(min4(a, b, c, d), max4(a, b, c, d))
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Div for Interval<T> {
+    type Output = Self;
+    fn div(self, rhs: Self) -> Self {
+        if rhs.contains_zero() {
+            return Self::entire();
+        }
+        let (a, b, c, d) = (
+            self.lo / rhs.lo,
+            self.lo / rhs.hi,
+            self.hi / rhs.lo,
+            self.hi / rhs.hi,
+        );
+        Self::outward(min4(a, b, c, d), max4(a, b, c, d))
+    }
+}
+
+impl<T: num_traits::Float> core::ops::Rem for Interval<T> {
+    type Output = Self;
+    fn rem(self, rhs: Self) -> Self {
+        use num_traits::Float;
+        let q = (self / rhs).trunc();
+        if q.lo == q.hi && q.lo.is_finite() {
+            return self - q * rhs;
+        }
+        // the remainder has the sign of `self` and its magnitude is smaller than `rhs`
+        let m = rhs.lo.abs().max(rhs.hi.abs());
+        let lo = if self.lo >= T::zero() { T::zero() } else { -m };
+        let hi = if self.hi <= T::zero() { T::zero() } else { m };
+        Interval { lo, hi }
+    }
+}
+
+impl<T: num_traits::Float> core::ops::AddAssign for Interval<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::SubAssign for Interval<T> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::MulAssign for Interval<T> {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::DivAssign for Interval<T> {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::ops::RemAssign for Interval<T> {
+    fn rem_assign(&mut self, rhs: Self) {
+        *self = *self % rhs;
+    }
+}
+
+impl<T: num_traits::Float> core::iter::Sum for Interval<T> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::point(T::zero()), |a, b| a + b)
+    }
+}
+
+impl<'a, T: num_traits::Float> core::iter::Sum<&'a Interval<T>> for Interval<T> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.fold(Self::point(T::zero()), |a, &b| a + b)
+    }
+}
+
+impl<T: num_traits::Float> PartialEq for Interval<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.lo == other.lo && self.hi == other.hi
+    }
+}
+
+/// intervals are ordered only if they are disjoint (or the same point)
+impl<T: num_traits::Float> PartialOrd for Interval<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
+        if self.hi < other.lo {
+            Some(core::cmp::Ordering::Less)
+        } else if self.lo > other.hi {
+            Some(core::cmp::Ordering::Greater)
+        } else if self.lo == self.hi && other.lo == other.hi && self.lo == other.lo {
+            Some(core::cmp::Ordering::Equal)
+        } else {
+            None
+        }
+    }
+}
+
+impl<T: num_traits::Float + core::fmt::Display> core::fmt::Display for Interval<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "[{}, {}]", self.lo, self.hi)
+    }
+}
+
+// ----------------------------------------
+// traits in `num_traits`
+
+impl<T: num_traits::Float> num_traits::Zero for Interval<T> {
+    fn zero() -> Self {
+        Self::point(T::zero())
+    }
+    fn is_zero(&self) -> bool {
+        self.lo.is_zero() && self.hi.is_zero()
+    }
+}
+
+impl<T: num_traits::Float> num_traits::One for Interval<T> {
+    fn one() -> Self {
+        Self::point(T::one())
+    }
+}
+
+impl<T: num_traits::Float> num_traits::Num for Interval<T> {
+    type FromStrRadixErr = T::FromStrRadixErr;
+    fn from_str_radix(str: &str, radix: u32) -> Result<Self, Self::FromStrRadixErr> {
+        T::from_str_radix(str, radix).map(Self::point)
+    }
+}
+
+/// the conversion to a primitive uses the midpoint
+impl<T: num_traits::Float> num_traits::ToPrimitive for Interval<T> {
+    fn to_i64(&self) -> Option<i64> {
+        self.mid().to_i64()
+    }
+    fn to_u64(&self) -> Option<u64> {
+        self.mid().to_u64()
+    }
+    fn to_f32(&self) -> Option<f32> {
+        self.mid().to_f32()
+    }
+    fn to_f64(&self) -> Option<f64> {
+        self.mid().to_f64()
+    }
+}
+
+impl<T: num_traits::Float> num_traits::NumCast for Interval<T> {
+    fn from<N: num_traits::ToPrimitive>(n: N) -> Option<Self> {
+        T::from(n).map(Self::point)
+    }
+}
+
+impl<T> num_traits::AsPrimitive<Interval<T>> for f64
+where
+    T: num_traits::Float + 'static,
+    f64: num_traits::AsPrimitive<T>,
+{
+    fn as_(self) -> Interval<T> {
+        Interval::point(self.as_())
+    }
+}
+
+impl<T> num_traits::AsPrimitive<Interval<T>> for f32
+where
+    T: num_traits::Float + 'static,
+    f32: num_traits::AsPrimitive<T>,
+{
+    fn as_(self) -> Interval<T> {
+        Interval::point(self.as_())
+    }
+}
+
+macro_rules! float_const {
+    ($($name:ident),*) => {
+        $(
+            fn $name() -> Self {
+                let v = T::$name();
+                Self::outward(v, v)
+            }
+        )*
+    };
+}
+
+/// the irrational constants are enclosed by widening them by one ulp
+impl<T: num_traits::Float + num_traits::FloatConst> num_traits::FloatConst for Interval<T> {
+    float_const!(
+        E,
+        FRAC_1_PI,
+        FRAC_1_SQRT_2,
+        FRAC_2_PI,
+        FRAC_2_SQRT_PI,
+        FRAC_PI_2,
+        FRAC_PI_3,
+        FRAC_PI_4,
+        FRAC_PI_6,
+        FRAC_PI_8,
+        LN_10,
+        LN_2,
+        LOG10_E,
+        LOG2_E,
+        PI,
+        SQRT_2,
+        TAU,
+        LOG10_2,
+        LOG2_10
+    );
+}
+
+impl<T: num_traits::Float> num_traits::Float for Interval<T> {
+    fn nan() -> Self {
+        Self::point(T::nan())
+    }
+    fn infinity() -> Self {
+        Self::point(T::infinity())
+    }
+    fn neg_infinity() -> Self {
+        Self::point(T::neg_infinity())
+    }
+    fn neg_zero() -> Self {
+        Self::point(T::neg_zero())
+    }
+    fn min_value() -> Self {
+        Self::point(T::min_value())
+    }
+    fn min_positive_value() -> Self {
+        Self::point(T::min_positive_value())
+    }
+    fn epsilon() -> Self {
+        Self::point(T::epsilon())
+    }
+    fn max_value() -> Self {
+        Self::point(T::max_value())
+    }
+    fn is_nan(self) -> bool {
+        self.lo.is_nan() || self.hi.is_nan()
+    }
+    fn is_infinite(self) -> bool {
+        self.lo.is_infinite() || self.hi.is_infinite()
+    }
+    fn is_finite(self) -> bool {
+        self.lo.is_finite() && self.hi.is_finite()
+    }
+    fn is_normal(self) -> bool {
+        self.lo.is_normal() && self.hi.is_normal()
+    }
+    fn classify(self) -> core::num::FpCategory {
+        self.mid().classify()
+    }
+    fn floor(self) -> Self {
+        Interval::new(self.lo.floor(), self.hi.floor())
+    }
+    fn ceil(self) -> Self {
+        Interval::new(self.lo.ceil(), self.hi.ceil())
+    }
+    fn round(self) -> Self {
+        Interval::new(self.lo.round(), self.hi.round())
+    }
+    fn trunc(self) -> Self {
+        Interval::new(self.lo.trunc(), self.hi.trunc())
+    }
+    fn fract(self) -> Self {
+        if self.lo.trunc() == self.hi.trunc() {
+            return Self::outward(self.lo.fract(), self.hi.fract());
+        }
+        let one = T::one();
+        let lo = if self.lo >= T::zero() {
+            T::zero()
+        } else {
+            -one
+        };
+        let hi = if self.hi <= T::zero() { T::zero() } else { one };
+        Interval { lo, hi }
+    }
+    fn abs(self) -> Self {
+        if self.lo >= T::zero() {
+            self
+        } else if self.hi <= T::zero() {
+            -self
+        } else {
+            Interval::new(T::zero(), (-self.lo).max(self.hi))
+        }
+    }
+    fn signum(self) -> Self {
+        Interval::new(self.lo.signum(), self.hi.signum())
+    }
+    fn is_sign_positive(self) -> bool {
+        self.lo.is_sign_positive()
+    }
+    fn is_sign_negative(self) -> bool {
+        self.hi.is_sign_negative()
+    }
+    fn mul_add(self, a: Self, b: Self) -> Self {
+        self * a + b
+    }
+    fn recip(self) -> Self {
+        Self::point(T::one()) / self
+    }
+    fn powi(self, n: i32) -> Self {
+        if n == 0 {
+            return Self::point(T::one());
+        }
+        if n < 0 {
+            return self.powi(-n).recip();
+        }
+        if n % 2 == 0 {
+            self.even(|x| x.powi(n))
+        } else {
+            self.increasing(|x| x.powi(n))
+        }
+    }
+    fn powf(self, n: Self) -> Self {
+        (self.ln() * n).exp()
+    }
+    fn sqrt(self) -> Self {
+        let lo = self.lo.max(T::zero());
+        Interval::new(down(lo.sqrt()).max(T::zero()), up(self.hi.sqrt()))
+    }
+    fn exp(self) -> Self {
+        let r = self.increasing(T::exp);
+        Interval::new(r.lo.max(T::zero()), r.hi)
+    }
+    fn exp2(self) -> Self {
+        let r = self.increasing(T::exp2);
+        Interval::new(r.lo.max(T::zero()), r.hi)
+    }
+    fn ln(self) -> Self {
+        self.increasing(T::ln)
+    }
+    fn log(self, base: Self) -> Self {
+        self.ln() / base.ln()
+    }
+    fn log2(self) -> Self {
+        self.increasing(T::log2)
+    }
+    fn log10(self) -> Self {
+        self.increasing(T::log10)
+    }
+    fn max(self, other: Self) -> Self {
+        Interval::new(self.lo.max(other.lo), self.hi.max(other.hi))
+    }
+    fn min(self, other: Self) -> Self {
+        Interval::new(self.lo.min(other.lo), self.hi.min(other.hi))
+    }
+    fn clamp(self, min: Self, max: Self) -> Self {
+        self.max(min).min(max)
+    }
+    fn abs_sub(self, other: Self) -> Self {
+        (self - other).max(Self::point(T::zero()))
+    }
+    fn cbrt(self) -> Self {
+        self.increasing(T::cbrt)
+    }
+    fn hypot(self, other: Self) -> Self {
+        (self.powi(2) + other.powi(2)).sqrt()
+    }
+    fn sin(self) -> Self {
+        let pi = Self::pi();
+        let one = T::one();
+        let two = one + one;
+        if self.width() >= two * pi || self.is_nan() {
+            return Interval::new(-one, one);
+        }
+        let r = self.increasing(T::sin).hull(&self.decreasing(T::sin));
+        let lo = if self.contains_periodic(-pi / two, two * pi) {
+            -one
+        } else {
+            r.lo.max(-one)
+        };
+        let hi = if self.contains_periodic(pi / two, two * pi) {
+            one
+        } else {
+            r.hi.min(one)
+        };
+        Interval { lo, hi }
+    }
+    fn cos(self) -> Self {
+        let pi = Self::pi();
+        let one = T::one();
+        let two = one + one;
+        if self.width() >= two * pi || self.is_nan() {
+            return Interval::new(-one, one);
+        }
+        let r = self.increasing(T::cos).hull(&self.decreasing(T::cos));
+        let lo = if self.contains_periodic(pi, two * pi) {
+            -one
+        } else {
+            r.lo.max(-one)
+        };
+        let hi = if self.contains_periodic(T::zero(), two * pi) {
+            one
+        } else {
+            r.hi.min(one)
+        };
+        Interval { lo, hi }
+    }
+    fn tan(self) -> Self {
+        let pi = Self::pi();
+        if self.width() >= pi || self.contains_periodic(pi / (T::one() + T::one()), pi) {
+            return Self::entire();
+        }
+        self.increasing(T::tan)
+    }
+    fn asin(self) -> Self {
+        let one = T::one();
+        Interval::new(self.lo.max(-one), self.hi.min(one)).increasing(T::asin)
+    }
+    fn acos(self) -> Self {
+        let one = T::one();
+        Interval::new(self.lo.max(-one), self.hi.min(one)).decreasing(T::acos)
+    }
+    fn atan(self) -> Self {
+        self.increasing(T::atan)
+    }
+    /// `self` is `y` and `other` is `x`
+    fn atan2(self, other: Self) -> Self {
+        let pi = Self::pi();
+        if (other.lo <= T::zero() && self.contains_zero()) || self.is_nan() || other.is_nan() {
+            // the box contains the origin or crosses the branch cut on the negative x-axis
+            return Self::outward(-pi, pi);
+        }
+        // otherwise the extremes are at the corners of the box
+        let (a, b, c, d) = (
+            self.lo.atan2(other.lo),
+            self.lo.atan2(other.hi),
+            self.hi.atan2(other.lo),
+            self.hi.atan2(other.hi),
+        );
+        Self::outward(min4(a, b, c, d), max4(a, b, c, d))
+    }
+    fn sin_cos(self) -> (Self, Self) {
+        (self.sin(), self.cos())
+    }
+    fn exp_m1(self) -> Self {
+        self.increasing(T::exp_m1)
+    }
+    fn ln_1p(self) -> Self {
+        self.increasing(T::ln_1p)
+    }
+    fn sinh(self) -> Self {
+        self.increasing(T::sinh)
+    }
+    fn cosh(self) -> Self {
+        self.even(T::cosh)
+    }
+    fn tanh(self) -> Self {
+        self.increasing(T::tanh)
+    }
+    fn asinh(self) -> Self {
+        self.increasing(T::asinh)
+    }
+    fn acosh(self) -> Self {
+        Interval::new(self.lo.max(T::one()), self.hi).increasing(T::acosh)
+    }
+    fn atanh(self) -> Self {
+        self.increasing(T::atanh)
+    }
+    fn integer_decode(self) -> (u64, i16, i8) {
+        self.mid().integer_decode()
+    }
+}
+
+// ----------------------------------------
+
+#[cfg(test)]
+fn sample(rng: &mut rand_chacha::ChaChaRng, range: &Interval<f64>) -> f64 {
+    use rand::Rng;
+    range.lo + (range.hi - range.lo) * rng.gen::<f64>()
+}
+
+#[test]
+fn test_elementary_functions() {
+    use num_traits::Float;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    type F = fn(Interval<f64>) -> Interval<f64>;
+    type G = fn(f64) -> f64;
+    let funcs: [(F, G, Interval<f64>); 14] = [
+        (|x| x * x - x, |x| x * x - x, Interval::new(-0.5, 1.3)),
+        (
+            |x| x / (x + Interval::point(3.)),
+            |x| x / (x + 3.),
+            Interval::new(-0.5, 1.3),
+        ),
+        (|x| x.sqrt(), |x| x.sqrt(), Interval::new(0., 2.3)),
+        (|x| x.exp(), |x| x.exp(), Interval::new(-2.5, 1.3)),
+        (|x| x.ln(), |x| x.ln(), Interval::new(0.1, 3.3)),
+        (|x| x.powi(4), |x| x.powi(4), Interval::new(-0.5, 1.3)),
+        (|x| x.powi(-3), |x| x.powi(-3), Interval::new(0.2, 1.3)),
+        (|x| x.sin(), |x| x.sin(), Interval::new(-2.0, 4.0)),
+        (|x| x.cos(), |x| x.cos(), Interval::new(-4.0, 1.0)),
+        (|x| x.tan(), |x| x.tan(), Interval::new(-1.0, 1.4)),
+        (|x| x.abs(), |x| x.abs(), Interval::new(-1.0, 0.4)),
+        (|x| x.cosh(), |x| x.cosh(), Interval::new(-1.0, 0.4)),
+        (
+            |x| x.atan2(Interval::point(-0.5)),
+            |x| x.atan2(-0.5),
+            Interval::new(0.3, 1.0),
+        ),
+        (
+            |x| x % Interval::point(0.7),
+            |x| x % 0.7,
+            Interval::new(0.3, 2.2),
+        ),
+    ];
+    for (fi, ff, range) in funcs {
+        let yi = fi(range);
+        for _ in 0..1000 {
+            let x = sample(&mut rng, &range);
+            let y = ff(x);
+            assert!(yi.contains(y), "{} {} {}", x, y, yi);
+        }
+        // the enclosure of a degenerated interval is tight
+        let x = range.mid();
+        let yi = fi(Interval::point(x));
+        assert!(yi.contains(ff(x)));
+        assert!(yi.width() < 1.0e-12 * (1. + ff(x).abs()), "{}", yi);
+    }
+}
+
+#[test]
+fn test_rounding() {
+    // `0.1 + 0.2` is not exactly `0.3` in the floating point
+    let a = Interval::<f64>::point(0.1) + Interval::point(0.2);
+    assert!(a.lo < 0.1 + 0.2 && 0.1 + 0.2 < a.hi);
+    let a = Interval::<f64>::point(1.) / Interval::point(3.);
+    assert!(a.lo < 1. / 3. && 1. / 3. < a.hi);
+    assert!(a.width() < 1.0e-15);
+    let c = Interval::new(0.0, 1.0f64);
+    assert_eq!(c.partial_cmp(&Interval::new(0.5, 2.)), None);
+    assert!(c < Interval::new(1.5, 2.));
+    assert!(Interval::new(-1.0f64, 1.0) / Interval::new(-0.1, 0.1) == Interval::entire());
+}
+
+#[test]
+fn test_generic_functions() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let r = 0.05;
+    let box3 = |x: [f64; 3]| x.map(|v| Interval::new(v - r, v + r));
+    // nearest point on an edge
+    for (p0, p1, q) in [
+        ([0.1, 0.2, 0.3], [1.2, 0.5, -0.3], [0.6, 1.0, 0.1]),
+        ([0.1, 0.2, 0.3], [1.2, 0.5, -0.3], [2.0, 1.0, 0.1]),
+        ([0.1, 0.2, 0.3], [0.12, 0.21, 0.3], [2.0, 1.0, 0.1]),
+    ] {
+        let ni = crate::edge3::nearest_to_point3(&box3(p0), &box3(p1), &box3(q));
+        for _ in 0..1000 {
+            let sample3 = |rng: &mut rand_chacha::ChaChaRng, x: [f64; 3]| {
+                box3(x).map(|range| sample(rng, &range))
+            };
+            let (p0, p1, q) = (
+                sample3(&mut rng, p0),
+                sample3(&mut rng, p1),
+                sample3(&mut rng, q),
+            );
+            let n = crate::edge3::nearest_to_point3(&p0, &p1, &q);
+            for i in 0..3 {
+                assert!(ni[i].contains(n[i]), "{} {}", ni[i], n[i]);
+            }
+        }
+    }
+    // union of two boxes
+    let b0 = [0.1, 0.2, 0.3, 1.1, 1.2, 1.3f64];
+    let b1 = [0.15, 0.0, 0.3, 0.6, 1.5, 1.3f64];
+    let bi = crate::aabb3::from_two_aabbs(
+        &b0.map(|v| Interval::new(v - r, v + r)),
+        &b1.map(|v| Interval::new(v - r, v + r)),
+    );
+    for _ in 0..1000 {
+        let b0 = b0.map(|v| sample(&mut rng, &Interval::new(v - r, v + r)));
+        let b1 = b1.map(|v| sample(&mut rng, &Interval::new(v - r, v + r)));
+        let b = crate::aabb3::from_two_aabbs(&b0, &b1);
+        for i in 0..6 {
+            assert!(bi[i].contains(b[i]));
+        }
+    }
+    // range of a polynomial
+    let c = [0.3, -1.2, 0.5, 2.0f64];
+    let x = Interval::new(-0.7, 0.4);
+    let ci = c.map(Interval::point);
+    let yi = crate::polynomial::eval(&ci, x);
+    for _ in 0..1000 {
+        let x = sample(&mut rng, &x);
+        assert!(yi.contains(crate::polynomial::eval(&c, x)));
+    }
+}
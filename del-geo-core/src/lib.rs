@@ -1,3 +1,5 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
 /*
 vec < mat < aabb < obb <
 line < ray < edge <
@@ -11,6 +13,7 @@ pub mod aabb3;
 pub mod range;
 
 pub mod curve_linear_coords;
+pub mod dual;
 pub mod mat2x3_col_major;
 
 pub mod mat3_array_of_array;
@@ -20,23 +23,39 @@ pub mod obb3;
 pub mod vec2;
 pub mod vec3;
 //
+#[cfg(feature = "rayon")]
+pub mod bulk;
 pub mod edge;
 pub mod edge2;
 pub mod edge3;
+pub mod ellipse;
+pub mod ellipsoid;
+pub mod gaussian_splat;
+pub mod gaussian_surfel;
 pub mod hex;
+pub mod interval;
 pub mod mat2_sym;
 pub mod mat3_row_major;
 pub mod mat3_sym;
 pub mod matn;
 pub mod obb2;
+pub mod polygon2;
+pub mod polyline3;
+pub mod polynomial;
 pub mod polynomial_root;
 pub mod quaternion;
+pub mod rod3;
+pub mod simd;
 pub mod sphere;
 pub mod spherical_harmonics;
 pub mod tet;
 pub mod tri2;
 pub mod tri3;
+#[cfg(feature = "typed")]
+pub mod typed;
 pub mod uvec3;
 pub mod vecn;
 pub mod view_projection;
 pub mod view_rotation;
+pub mod voxel;
+mod wdwddw;
@@ -57,7 +57,7 @@ where
 
 pub fn safe_inverse_preserve_positive_definiteness<Real>(abc: &[Real; 3], eps: Real) -> [Real; 3]
 where
-    Real: num_traits::Float + std::fmt::Display,
+    Real: num_traits::Float + core::fmt::Display,
 {
     assert!(
         abc[0] + abc[2] > Real::zero(),
@@ -132,7 +132,7 @@ where
 
 pub fn aabb2<Real>(coeff: &[Real; 3]) -> [Real; 4]
 where
-    Real: num_traits::Float + std::fmt::Debug,
+    Real: num_traits::Float + core::fmt::Debug,
 {
     let a = coeff[0];
     let b = coeff[1];
@@ -184,7 +184,7 @@ fn test_prinsipal_directions() {
         let ndiv = 128;
         let mut sdf_max = f32::MIN;
         for i in 0..ndiv {
-            let theta = (i as f32) * 2.0 * std::f32::consts::PI / (ndiv as f32);
+            let theta = (i as f32) * 2.0 * core::f32::consts::PI / (ndiv as f32);
             let v = evec0 * theta.cos() + evec1 * theta.sin();
             let radrad = (v.transpose() * sig * v)[0];
             assert!((radrad - 1.).abs() < 1.0e-3, "{}", radrad);
@@ -206,7 +206,7 @@ pub fn wdw_projected_spd_mat3<Real>(
     d: &[Real; 3],
 ) -> ([Real; 3], [[Real; 6]; 3])
 where
-    Real: num_traits::Float + std::ops::AddAssign,
+    Real: num_traits::Float + core::ops::AddAssign,
 {
     let two = Real::one() + Real::one();
     let r = crate::quaternion::to_mat3_col_major(quat0);
@@ -325,7 +325,7 @@ fn test_wdw_projected_spd_mat3() {
 
 pub fn wdw_inverse<Real, const N: usize>(dabcdt: &[[Real; N]; 3], xyz: &[Real; 3]) -> [[Real; N]; 3]
 where
-    Real: num_traits::Float + std::ops::AddAssign + std::fmt::Debug,
+    Real: num_traits::Float + core::ops::AddAssign + core::fmt::Debug,
 {
     let one = Real::one();
     let two = one + one;
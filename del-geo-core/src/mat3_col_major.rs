@@ -16,7 +16,7 @@ where
 
 impl<Real> Mat3ColMajor<Real> for [Real; 9]
 where
-    Real: num_traits::Float + std::ops::AddAssign,
+    Real: num_traits::Float + core::ops::AddAssign,
 {
     fn from_diagonal(diagonal: &[Real; 3]) -> Self {
         from_diagonal(diagonal)
@@ -44,7 +44,7 @@ where
     }
 }
 
-use std::ops::AddAssign;
+use core::ops::AddAssign;
 
 // --------------------------------------------------
 // below from methods
@@ -109,6 +109,19 @@ pub fn from_transform_ndc2pix(img_shape: (usize, usize)) -> [f32; 9] {
     ]
 }
 
+/// [`from_transform_ndc2pix`] for any float type
+/// * `image_shape` - (width, height)
+pub fn from_transform_ndc2pix_generic<Real>(img_shape: (usize, usize)) -> [Real; 9]
+where
+    Real: num_traits::Float,
+{
+    let half = Real::one() / (Real::one() + Real::one());
+    let w = half * Real::from(img_shape.0).unwrap();
+    let h = half * Real::from(img_shape.1).unwrap();
+    let zero = Real::zero();
+    [w, zero, zero, zero, -h, zero, w, h, Real::one()]
+}
+
 /// transformation converting unit coodinate (NDC) `[0,+1]^2` to pixel coordinate
 /// * `image_shape` - (width, height)
 pub fn from_transform_unit2pix(img_shape: (usize, usize)) -> [f32; 9] {
@@ -131,7 +144,7 @@ pub fn from_transform_unit2pix(img_shape: (usize, usize)) -> [f32; 9] {
 
 pub fn to_quaternion<Real>(p: &[Real; 9]) -> [Real; 4]
 where
-    Real: num_traits::Float + ordered_float::FloatCore + std::fmt::Debug,
+    Real: num_traits::Float + ordered_float::FloatCore + core::fmt::Debug,
 {
     let one = Real::one();
     let one4th = one / (one + one + one + one);
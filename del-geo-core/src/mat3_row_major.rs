@@ -10,7 +10,7 @@ pub trait Mat3RowMajor<Real: num_traits::Float> {
 }
 impl<Real> Mat3RowMajor<Real> for [Real; 9]
 where
-    Real: num_traits::Float + std::ops::AddAssign + std::iter::Sum,
+    Real: num_traits::Float + core::ops::AddAssign + core::iter::Sum,
 {
     fn from_identity() -> Self {
         from_identity()
@@ -56,7 +56,7 @@ where
 
 pub fn squared_norm<Real>(u: &[Real; 9]) -> Real
 where
-    Real: num_traits::Float + std::iter::Sum,
+    Real: num_traits::Float + core::iter::Sum,
 {
     u.iter().map(|&v| v * v).sum()
 }
@@ -70,7 +70,7 @@ where
 
 pub fn mult_mat_row_major<Real>(a: &[Real; 9], b: &[Real; 9]) -> [Real; 9]
 where
-    Real: num_traits::Float + std::ops::AddAssign,
+    Real: num_traits::Float + core::ops::AddAssign,
 {
     let mut r = [Real::zero(); 9];
     for i in 0..3 {
@@ -87,10 +87,13 @@ pub fn sub<Real>(a: &[Real; 9], b: &[Real; 9]) -> [Real; 9]
 where
     Real: num_traits::Float,
 {
-    std::array::from_fn(|i| a[i] - b[i])
+    core::array::from_fn(|i| a[i] - b[i])
 }
 
-fn sort_eigen(g: &mut [f64; 3], v: &mut [f64; 9]) {
+fn sort_eigen<Real>(g: &mut [Real; 3], v: &mut [Real; 9])
+where
+    Real: num_traits::Float,
+{
     if g[1] > g[0] {
         g.swap(0, 1);
         v.swap(0, 1);
@@ -111,8 +114,18 @@ fn sort_eigen(g: &mut [f64; 3], v: &mut [f64; 9]) {
     }
 }
 
-// m = UGV^T
-pub fn svd(m: &[f64; 9], nitr: usize) {
+/// singular value decomposition `m = U G V^T` where `U` and `V` are rotation matrices.
+/// the singular values are sorted in descending order and the last one becomes negative
+/// if `det(m) < 0` (i.e., the matrix is inverted).
+/// # return
+/// `(U, G, V)`
+pub fn svd<Real>(m: &[Real; 9], nitr: usize) -> ([Real; 9], [Real; 3], [Real; 9])
+where
+    Real: num_traits::Float + core::ops::AddAssign + core::iter::Sum,
+{
+    let zero = Real::zero();
+    let one = Real::one();
+    let tiny = Real::epsilon() * Real::epsilon();
     // M^TM = VGGV^T
     let mtm = [
         m[0] * m[0] + m[3] * m[3] + m[6] * m[6],
@@ -123,70 +136,65 @@ pub fn svd(m: &[f64; 9], nitr: usize) {
         m[0] * m[1] + m[3] * m[4] + m[6] * m[7],
     ];
     let Some((mut v, mut lv)) = crate::mat3_sym::eigen_decomp(mtm, nitr) else {
-        todo!()
+        // zero matrix
+        let id = from_identity();
+        return (id, [zero; 3], id);
     };
     sort_eigen(&mut lv, &mut v);
-    lv = lv.map(|x| x.clamp(0.0, x));
-    let mut g = lv.map(|x| x.sqrt());
-
-    let mut u0 = [
-        m[0] * v[0] + m[1] * v[3] + m[2] * v[6],
-        m[3] * v[0] + m[4] * v[3] + m[5] * v[6],
-        m[6] * v[0] + m[7] * v[3] + m[8] * v[6],
-    ];
-    let mut u1 = [
-        m[0] * v[1] + m[1] * v[4] + m[2] * v[7],
-        m[3] * v[1] + m[4] * v[4] + m[5] * v[7],
-        m[6] * v[1] + m[7] * v[4] + m[8] * v[7],
-    ];
-    let mut u2 = [
-        m[0] * v[2] + m[1] * v[5] + m[2] * v[8],
-        m[3] * v[2] + m[4] * v[5] + m[5] * v[8],
-        m[6] * v[2] + m[7] * v[5] + m[8] * v[8],
-    ];
-
-    let mut u = [0f64; 9];
-    if v.determinant() < 0. {
+    let mut g = lv.map(|x| x.max(zero).sqrt());
+    if v.determinant() < zero {
         // making right hand coordinate
-        v[2] *= -1.; // v[0,2] = v[0*3+2]
-        v[5] *= -1.; // v[1,2] = v[1*3+2]
-        v[2 * 3 + 2] *= -1.;
-        g[2] *= -1.;
-        u[2] *= -1.; // u[0,2] = u[0*3+2]
-        u[5] *= -1.; // u[1,2] = u[1*3+2]
-        u[8] *= -1.; // u[2,2] = u[2*3+2]
+        v[2] = -v[2];
+        v[5] = -v[5];
+        v[8] = -v[8];
     }
-
-    let sql0 = u0.squared_norm();
-    if sql0 > 1.0e-20 {
+    let mv = |j: usize| {
+        [
+            m[0] * v[j] + m[1] * v[3 + j] + m[2] * v[6 + j],
+            m[3] * v[j] + m[4] * v[3 + j] + m[5] * v[6 + j],
+            m[6] * v[j] + m[7] * v[3 + j] + m[8] * v[6 + j],
+        ]
+    };
+    let (mut u0, mut u1, u2) = (mv(0), mv(1), mv(2));
+    if u0.squared_norm() > tiny {
         u0.normalize_in_place();
-        let sql1 = u1.squared_norm();
-        if sql1 < 1.0e-20 {
-            u1 = u0.map(|x| 1.0 - x.abs());
-        } else {
-            u1.normalize_in_place();
-        }
-        let d01 = u0[0] * u1[0] + u0[1] * u1[1] + u0[2] * u1[2];
-        u1 = std::array::from_fn(|i| u1[i] - d01 * u0[i]);
+    } else {
+        u0 = [one, zero, zero];
+    }
+    let d01 = u0.dot(&u1);
+    u1 = core::array::from_fn(|i| u1[i] - d01 * u0[i]);
+    if u1.squared_norm() > tiny {
         u1.normalize_in_place();
-        let s2 = u0.cross(&u1);
-        let d22 = u2[0] * s2[0] + u2[1] * s2[1] + u2[2] * s2[2];
-        u2 = s2;
-        if d22 < 0. {
-            g[2] *= -1.;
-        }
     } else {
-        u0 = [1., 0., 0.];
-        u1 = [0., 1., 0.];
-        u2 = [0., 0., 1.];
+        // any direction perpendicular to u0
+        u1 = crate::vec3::basis_xy_from_basis_z(&u0).0;
+    }
+    let s2 = u0.cross(&u1);
+    if s2.dot(&u2) < zero {
+        g[2] = -g[2];
+    }
+    let u = [
+        u0[0], u1[0], s2[0], //
+        u0[1], u1[1], s2[1], //
+        u0[2], u1[2], s2[2],
+    ];
+    (u, g, v)
+}
+
+#[test]
+fn test_svd() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _itr in 0..1000 {
+        let m: [f64; 9] = core::array::from_fn(|_| 2. * rng.gen::<f64>() - 1.);
+        let (u, g, v) = svd(&m, 30);
+        assert!((u.determinant() - 1.).abs() < 1.0e-10);
+        assert!((v.determinant() - 1.).abs() < 1.0e-10);
+        assert!(g[0] >= g[1] && g[1] >= g[2].abs());
+        assert_eq!(g[2] < 0., m.determinant() < 0.);
+        let ug: [f64; 9] = core::array::from_fn(|i| u[i] * g[i % 3]);
+        let m1 = ug.mult_mat_row_major(&v.transpose());
+        assert!(m1.sub(&m).squared_norm() < 1.0e-20);
     }
-    u[0] = u0[0];
-    u[1] = u1[0];
-    u[2] = u2[0];
-    u[3] = u0[1];
-    u[4] = u1[1];
-    u[5] = u2[1];
-    u[6] = u0[2];
-    u[7] = u1[2];
-    u[8] = u2[2];
 }
@@ -9,18 +9,24 @@ where
         + two * (sm[3] * sm[3] + sm[4] * sm[4] + sm[5] * sm[5])
 }
 
-pub fn eigen_decomp(sm: [f64; 6], nitr: usize) -> Option<([f64; 9], [f64; 3])> {
-    let mut u = [0f64; 9];
+pub fn eigen_decomp<Real>(sm: [Real; 6], nitr: usize) -> Option<([Real; 9], [Real; 3])>
+where
+    Real: num_traits::Float,
+{
+    let one = Real::one();
+    let two = one + one;
+    let half = one / two;
+    let mut u = [Real::zero(); 9];
     // initialize u as identity matrix
-    u[0] = 1.0;
-    u[4] = 1.0;
-    u[8] = 1.0;
+    u[0] = one;
+    u[4] = one;
+    u[8] = one;
     let dnrm = squared_norm(&sm);
-    if dnrm < 1.0e-30 {
+    if dnrm < Real::from(1.0e-30).unwrap() {
         return None;
     } // this matrix is too small
     let scale = dnrm.sqrt();
-    let invscl = 1.0 / scale;
+    let invscl = one / scale;
     let mut sms = sm.map(|x| x * invscl);
 
     for _itr in 0..nitr {
@@ -31,12 +37,12 @@ pub fn eigen_decomp(sm: [f64; 6], nitr: usize) -> Option<([f64; 9], [f64; 3])> {
         let a01 = sms[5].abs();
         if a12 >= a20 && a12 >= a01 {
             // when a12 sms[3] is the biggest
-            let t = 0.5 * (2. * m[3]).atan2(m[2] - m[1]);
+            let t = half * (two * m[3]).atan2(m[2] - m[1]);
             let ct = t.cos();
             let st = t.sin();
-            sms[1] = ct * ct * m[1] + st * st * m[2] - 2. * st * ct * m[3];
-            sms[2] = ct * ct * m[2] + st * st * m[1] + 2. * st * ct * m[3];
-            sms[3] = 0.; // (ct*ct-st*st)*m[3]+st*ct*(m[1]-m[2]);
+            sms[1] = ct * ct * m[1] + st * st * m[2] - two * st * ct * m[3];
+            sms[2] = ct * ct * m[2] + st * st * m[1] + two * st * ct * m[3];
+            sms[3] = Real::zero(); // (ct*ct-st*st)*m[3]+st*ct*(m[1]-m[2]);
             sms[4] = st * m[5] + ct * m[4];
             sms[5] = ct * m[5] - st * m[4];
             //
@@ -49,13 +55,13 @@ pub fn eigen_decomp(sm: [f64; 6], nitr: usize) -> Option<([f64; 9], [f64; 3])> {
         } else if a20 >= a01 && a20 >= a12 {
             // when a20 sms[4] is the biggest
             // the above condition statement shoud pass exactly once for each iteration.
-            let t = 0.5 * (2. * m[4]).atan2(m[2] - m[0]);
+            let t = half * (two * m[4]).atan2(m[2] - m[0]);
             let ct = t.cos();
             let st = t.sin();
-            sms[0] = ct * ct * m[0] + st * st * m[2] - 2. * st * ct * m[4];
-            sms[2] = ct * ct * m[2] + st * st * m[0] + 2. * st * ct * m[4];
+            sms[0] = ct * ct * m[0] + st * st * m[2] - two * st * ct * m[4];
+            sms[2] = ct * ct * m[2] + st * st * m[0] + two * st * ct * m[4];
             sms[3] = st * m[5] + ct * m[3];
-            sms[4] = 0.; // (ct*ct-st*st)*m[4]+st*ct*(m[0]-m[2]);
+            sms[4] = Real::zero(); // (ct*ct-st*st)*m[4]+st*ct*(m[0]-m[2]);
             sms[5] = ct * m[5] - st * m[3];
             //
             u[0] = ct * v[0] - st * v[2];
@@ -67,15 +73,15 @@ pub fn eigen_decomp(sm: [f64; 6], nitr: usize) -> Option<([f64; 9], [f64; 3])> {
         } else {
             // when a01 sms[5] is the biggest
             // the condition statement shoud pass exactly once for each iteration.
-            let t = 0.5 * (2. * m[5]).atan2(m[1] - m[0]);
+            let t = half * (two * m[5]).atan2(m[1] - m[0]);
             let ct = t.cos();
             let st = t.sin();
-            sms[0] = ct * ct * m[0] + st * st * m[1] - 2. * st * ct * m[5];
-            sms[1] = ct * ct * m[1] + st * st * m[0] + 2. * st * ct * m[5];
+            sms[0] = ct * ct * m[0] + st * st * m[1] - two * st * ct * m[5];
+            sms[1] = ct * ct * m[1] + st * st * m[0] + two * st * ct * m[5];
             sms[3] = st * m[4] + ct * m[3];
             sms[4] = ct * m[4] - st * m[3];
-            sms[5] = 0.; // (ct*ct-st*st)*m[5]+st*ct*(m[0]-m[1]);
-                         //
+            sms[5] = Real::zero(); // (ct*ct-st*st)*m[5]+st*ct*(m[0]-m[1]);
+                                   //
             u[0] = ct * v[0] - st * v[1];
             u[1] = st * v[0] + ct * v[1];
             u[3] = ct * v[3] - st * v[4];
@@ -84,7 +90,7 @@ pub fn eigen_decomp(sm: [f64; 6], nitr: usize) -> Option<([f64; 9], [f64; 3])> {
             u[7] = st * v[6] + ct * v[7];
         }
     }
-    let l = std::array::from_fn(|i| scale * sms[i]);
+    let l = core::array::from_fn(|i| scale * sms[i]);
     Some((u, l))
 }
 
@@ -96,7 +102,7 @@ fn hoge() {
     let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
     // std::uniform_real_distribution < double > dist(-50.0, 50.0);
     for _itr in 0..1000 {
-        let sm = std::array::from_fn(|_| rng.gen::<f64>() * 50.);
+        let sm = core::array::from_fn(|_| rng.gen::<f64>() * 50.);
         let Some((u, _l)) = eigen_decomp(sm, 20) else {
             todo!()
         };
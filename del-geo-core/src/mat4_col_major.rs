@@ -13,7 +13,7 @@ where
 
 impl<Real> Mat4ColMajor<Real> for [Real; 16]
 where
-    Real: num_traits::Float + AddAssign + std::ops::MulAssign + std::ops::SubAssign,
+    Real: num_traits::Float + AddAssign + core::ops::MulAssign + core::ops::SubAssign,
 {
     fn transform_homogeneous(&self, v: &[Real; 3]) -> Option<[Real; 3]> {
         transform_homogeneous(self, v)
@@ -30,8 +30,8 @@ where
 }
 
 use crate::aabb3::max_edge_size;
+use core::ops::AddAssign;
 use num_traits::AsPrimitive;
-use std::ops::AddAssign;
 
 pub fn from_identity<Real>() -> [Real; 16]
 where
@@ -248,9 +248,25 @@ where
     Some([y0 / y3, y1 / y3, y2 / y3])
 }
 
+/// map the homogeneous coordinate `x` by `transform_world2ndc` and then to the homogeneous pixel
+/// coordinate `[x, y, w]` (see `mat3_col_major::from_transform_ndc2pix`). The NDC z is dropped
+/// * `img_shape` - (width, height)
+pub fn transform_to_pix_homogeneous<Real>(
+    transform_world2ndc: &[Real; 16],
+    img_shape: (usize, usize),
+    x: &[Real; 4],
+) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let c = mult_vec(transform_world2ndc, x);
+    let ndc2pix = crate::mat3_col_major::from_transform_ndc2pix_generic(img_shape);
+    crate::mat3_col_major::mult_vec(&ndc2pix, &[c[0], c[1], c[3]])
+}
+
 pub fn jacobian_transform<Real>(t: &[Real; 16], p: &[Real; 3]) -> [Real; 9]
 where
-    Real: num_traits::Float + Copy + std::fmt::Debug,
+    Real: num_traits::Float + Copy + core::fmt::Debug,
 {
     let a = [t[0], t[1], t[2], t[4], t[5], t[6], t[8], t[9], t[10]];
     let b = [t[12], t[13], t[14]];
@@ -316,7 +332,7 @@ where
 
 pub fn try_inverse<Real>(b: &[Real; 16]) -> Option<[Real; 16]>
 where
-    Real: num_traits::Float + std::ops::MulAssign + std::ops::SubAssign,
+    Real: num_traits::Float + core::ops::MulAssign + core::ops::SubAssign,
 {
     crate::matn::try_inverse::<Real, 4, 16>(b)
 }
@@ -337,7 +353,7 @@ pub fn camera_perspective_blender<Real>(
     proj_direction: bool,
 ) -> [Real; 16]
 where
-    Real: num_traits::Float + 'static + std::ops::AddAssign,
+    Real: num_traits::Float + 'static + core::ops::AddAssign,
     f64: AsPrimitive<Real>,
 {
     if proj_direction {
@@ -408,14 +424,21 @@ where
 
 pub fn scale<Real>(m: &[Real; 16], s: Real) -> [Real; 16]
 where
-    Real: Copy + std::ops::Mul<Output = Real>,
+    Real: Copy + core::ops::Mul<Output = Real>,
 {
     m.map(|x| s * x)
 }
 
+pub fn mult_vec<Real>(a: &[Real; 16], b: &[Real; 4]) -> [Real; 4]
+where
+    Real: num_traits::Float,
+{
+    core::array::from_fn(|i| a[i] * b[0] + a[4 + i] * b[1] + a[8 + i] * b[2] + a[12 + i] * b[3])
+}
+
 pub fn mult_mat<Real>(a: &[Real; 16], b: &[Real; 16]) -> [Real; 16]
 where
-    Real: num_traits::Float + std::ops::AddAssign,
+    Real: num_traits::Float + core::ops::AddAssign,
 {
     let mut o = [Real::zero(); 16];
     for i in 0..4 {
@@ -428,6 +451,15 @@ where
     o
 }
 
+/// camera at `(0, 0, 2)` looking at the origin and the image shape `(64, 48)`.
+/// The depth range is `[-1, 1.5]` in the world z-coordinate
+#[cfg(test)]
+pub(crate) fn sample_camera() -> ([f64; 16], (usize, usize)) {
+    let proj = camera_perspective_blender(1.5f64, 24., 0.5, 3.0, true);
+    let view = from_translate(&[0., 0., -2.]);
+    (mult_mat(&proj, &view), (64, 48))
+}
+
 #[test]
 fn test_inverse_multmat() {
     let a: [f64; 16] = [
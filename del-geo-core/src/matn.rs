@@ -1,8 +1,10 @@
-//! methods for NxN matrix (column major)
+//! methods for NxN matrix (column major) and the const-generic matrix `MatNM`
+
+use crate::vecn::VecN;
 
 pub fn try_inverse<Real, const N: usize, const NN: usize>(b: &[Real; NN]) -> Option<[Real; NN]>
 where
-    Real: num_traits::Float + std::ops::MulAssign + std::ops::SubAssign,
+    Real: num_traits::Float + core::ops::MulAssign + core::ops::SubAssign,
 {
     let mut a = *b;
     for i in 0..N {
@@ -29,3 +31,452 @@ where
     }
     Some(a)
 }
+
+/// eigen decomposition of a symmetric matrix by the cyclic Jacobi method.
+/// since the matrix is symmetric, the storage order (row or column major) does not matter.
+/// # return
+/// `(eigen_values, eigen_vectors)` where `eigen_vectors[i][k]` is the `i`-th component of the `k`-th eigen vector
+pub fn eigen_decomposition_sym<Real, const N: usize>(
+    a: &[[Real; N]; N],
+    nitr: usize,
+) -> ([Real; N], [[Real; N]; N])
+where
+    Real: num_traits::Float,
+{
+    let mut a = *a;
+    let mut v = [[Real::zero(); N]; N];
+    for (i, vi) in v.iter_mut().enumerate() {
+        vi[i] = Real::one();
+    }
+    let sq_norm = a.iter().flatten().fold(Real::zero(), |s, &x| s + x * x);
+    for _itr in 0..nitr {
+        let sq_off = (0..N)
+            .flat_map(|i| (0..N).filter(move |&j| j != i).map(move |j| (i, j)))
+            .fold(Real::zero(), |s, (i, j)| s + a[i][j] * a[i][j]);
+        if sq_off <= sq_norm * Real::epsilon() * Real::epsilon() {
+            break;
+        }
+        for p in 0..N {
+            for q in p + 1..N {
+                if a[p][q] == Real::zero() {
+                    continue;
+                }
+                // rotation in the (p,q) plane that zeros a[p][q]
+                let theta = (a[q][q] - a[p][p]) / ((Real::one() + Real::one()) * a[p][q]);
+                let t = theta.signum() / (theta.abs() + (theta * theta + Real::one()).sqrt());
+                let c = Real::one() / (t * t + Real::one()).sqrt();
+                let s = t * c;
+                for row in a.iter_mut().chain(v.iter_mut()) {
+                    let (xp, xq) = (row[p], row[q]);
+                    row[p] = c * xp - s * xq;
+                    row[q] = s * xp + c * xq;
+                }
+                let (a_lo, a_hi) = a.split_at_mut(q);
+                for (xp, xq) in a_lo[p].iter_mut().zip(a_hi[0].iter_mut()) {
+                    (*xp, *xq) = (c * *xp - s * *xq, s * *xp + c * *xq);
+                }
+            }
+        }
+    }
+    (core::array::from_fn(|i| a[i][i]), v)
+}
+
+/// project a symmetric matrix to the nearest positive semi-definite matrix
+/// by clamping the negative eigen values to zero
+pub fn project_to_positive_semidefinite<Real, const N: usize>(a: &[[Real; N]; N]) -> [[Real; N]; N]
+where
+    Real: num_traits::Float,
+{
+    let (lambda, v) = eigen_decomposition_sym(a, 50);
+    if lambda.iter().all(|&l| l >= Real::zero()) {
+        return *a;
+    }
+    let mut b = [[Real::zero(); N]; N];
+    for (k, &l) in lambda.iter().enumerate() {
+        if l <= Real::zero() {
+            continue;
+        }
+        for i in 0..N {
+            for j in 0..N {
+                b[i][j] = b[i][j] + l * v[i][k] * v[j][k];
+            }
+        }
+    }
+    b
+}
+
+/// matrix with `N` rows and `M` columns with the const-generic dimensions.
+/// the storage is column major: `self.0[j][i]` is the entry at the `i`-th row and the `j`-th column
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MatNM<T, const N: usize, const M: usize>(pub [[T; N]; M]);
+
+impl<T, const N: usize, const M: usize> MatNM<T, N, M>
+where
+    T: num_traits::Float,
+{
+    pub fn zeros() -> Self {
+        MatNM([[T::zero(); N]; M])
+    }
+
+    /// `f(i, j)` is the entry at the `i`-th row and the `j`-th column
+    pub fn from_fn<F: FnMut(usize, usize) -> T>(mut f: F) -> Self {
+        MatNM(core::array::from_fn(|j| core::array::from_fn(|i| f(i, j))))
+    }
+
+    pub fn from_columns(columns: &[VecN<T, N>; M]) -> Self {
+        MatNM(columns.map(|c| c.0))
+    }
+
+    pub fn column(&self, j: usize) -> VecN<T, N> {
+        VecN(self.0[j])
+    }
+
+    pub fn transpose(&self) -> MatNM<T, M, N> {
+        MatNM::from_fn(|i, j| self.0[i][j])
+    }
+}
+
+impl<T, const N: usize> MatNM<T, N, N>
+where
+    T: num_traits::Float,
+{
+    pub fn identity() -> Self {
+        Self::from_fn(|i, j| if i == j { T::one() } else { T::zero() })
+    }
+
+    /// LU decomposition with the partial pivoting. `None` if the matrix is singular or a pivot is NaN
+    pub fn lu(&self) -> Option<Lu<T, N>> {
+        // row major during the elimination
+        let mut a: [[T; N]; N] = self.transpose().0;
+        let mut perm: [usize; N] = core::array::from_fn(|i| i);
+        let mut sign = T::one();
+        for k in 0..N {
+            if a[k..].iter().any(|ai| ai[k].is_nan()) {
+                return None;
+            }
+            let p =
+                (k..N).max_by(|&i0, &i1| a[i0][k].abs().partial_cmp(&a[i1][k].abs()).unwrap())?;
+            if a[p][k].is_zero() {
+                return None;
+            }
+            if p != k {
+                a.swap(p, k);
+                perm.swap(p, k);
+                sign = -sign;
+            }
+            let (a_lo, a_hi) = a.split_at_mut(k + 1);
+            let ak = &a_lo[k];
+            for ai in a_hi.iter_mut() {
+                let l = ai[k] / ak[k];
+                ai[k] = l;
+                for (aij, &akj) in ai.iter_mut().zip(ak.iter()).skip(k + 1) {
+                    *aij = *aij - l * akj;
+                }
+            }
+        }
+        Some(Lu { a, perm, sign })
+    }
+
+    /// NaN if the matrix has NaN
+    pub fn determinant(&self) -> T {
+        match self.lu() {
+            Some(lu) => lu.determinant(),
+            None if self.0.iter().flatten().any(|v| v.is_nan()) => T::nan(),
+            None => T::zero(),
+        }
+    }
+
+    pub fn try_inverse(&self) -> Option<Self> {
+        self.lu().map(|lu| lu.inverse())
+    }
+
+    /// solve `self * x = b` by the LU decomposition
+    pub fn solve_lu(&self, b: &VecN<T, N>) -> Option<VecN<T, N>> {
+        self.lu().map(|lu| lu.solve(b))
+    }
+
+    /// lower triangular matrix `L` such that `self = L * L^T`.
+    /// `None` if the matrix is not positive definite. Only the lower triangle of `self` is referred
+    pub fn cholesky(&self) -> Option<Self> {
+        let mut l = Self::zeros();
+        for j in 0..N {
+            let d = (0..j).fold(self.0[j][j], |s, k| s - l.0[k][j] * l.0[k][j]);
+            if d.is_nan() || d <= T::zero() {
+                return None;
+            }
+            let d = d.sqrt();
+            l.0[j][j] = d;
+            for i in j + 1..N {
+                let v = (0..j).fold(self.0[j][i], |s, k| s - l.0[k][i] * l.0[k][j]);
+                l.0[j][i] = v / d;
+            }
+        }
+        Some(l)
+    }
+
+    /// solve `self * x = b` by the Cholesky decomposition for a symmetric positive definite matrix
+    pub fn solve_cholesky(&self, b: &VecN<T, N>) -> Option<VecN<T, N>> {
+        let l = self.cholesky()?;
+        // forward substitution with L
+        let mut x = *b;
+        for i in 0..N {
+            let s = (0..i).fold(x[i], |s, k| s - l.0[k][i] * x[k]);
+            x[i] = s / l.0[i][i];
+        }
+        // backward substitution with L^T
+        for i in (0..N).rev() {
+            let s = (i + 1..N).fold(x[i], |s, k| s - l.0[i][k] * x[k]);
+            x[i] = s / l.0[i][i];
+        }
+        Some(x)
+    }
+}
+
+/// LU decomposition `P * A = L * U` of a square matrix `A` (see `MatNM::lu`)
+#[derive(Debug, Clone, Copy)]
+pub struct Lu<T, const N: usize> {
+    /// `L` (unit diagonal omitted) and `U` packed in a row major matrix
+    a: [[T; N]; N],
+    /// `perm[i]` is the row of `A` at the `i`-th row of `P * A`
+    perm: [usize; N],
+    /// sign of the permutation
+    sign: T,
+}
+
+impl<T, const N: usize> Lu<T, N>
+where
+    T: num_traits::Float,
+{
+    pub fn determinant(&self) -> T {
+        (0..N).fold(self.sign, |s, i| s * self.a[i][i])
+    }
+
+    pub fn solve(&self, b: &VecN<T, N>) -> VecN<T, N> {
+        let mut x = VecN::from_fn(|i| b[self.perm[i]]);
+        for i in 0..N {
+            x[i] = (0..i).fold(x[i], |s, k| s - self.a[i][k] * x[k]);
+        }
+        for i in (0..N).rev() {
+            x[i] = (i + 1..N).fold(x[i], |s, k| s - self.a[i][k] * x[k]) / self.a[i][i];
+        }
+        x
+    }
+
+    pub fn inverse(&self) -> MatNM<T, N, N> {
+        MatNM(core::array::from_fn(|j| {
+            let e = VecN::from_fn(|i| if i == j { T::one() } else { T::zero() });
+            self.solve(&e).0
+        }))
+    }
+}
+
+impl<T, const N: usize, const M: usize> From<[[T; N]; M]> for MatNM<T, N, M> {
+    fn from(v: [[T; N]; M]) -> Self {
+        MatNM(v)
+    }
+}
+
+impl<T, const N: usize, const M: usize> From<MatNM<T, N, M>> for [[T; N]; M] {
+    fn from(v: MatNM<T, N, M>) -> Self {
+        v.0
+    }
+}
+
+/// conversion to and from the flat column major arrays (e.g., `mat3_col_major`)
+macro_rules! impl_from_flat {
+    ($n:literal, $m:literal, $nm:literal) => {
+        impl<T: Copy> From<[T; $nm]> for MatNM<T, $n, $m> {
+            fn from(v: [T; $nm]) -> Self {
+                MatNM(core::array::from_fn(|j| {
+                    core::array::from_fn(|i| v[j * $n + i])
+                }))
+            }
+        }
+
+        impl<T: Copy> From<MatNM<T, $n, $m>> for [T; $nm] {
+            fn from(v: MatNM<T, $n, $m>) -> Self {
+                core::array::from_fn(|k| v.0[k / $n][k % $n])
+            }
+        }
+    };
+}
+
+impl_from_flat!(2, 2, 4);
+impl_from_flat!(2, 3, 6);
+impl_from_flat!(3, 3, 9);
+impl_from_flat!(4, 4, 16);
+
+/// `(row, column)`
+impl<T, const N: usize, const M: usize> core::ops::Index<(usize, usize)> for MatNM<T, N, M> {
+    type Output = T;
+    fn index(&self, (i, j): (usize, usize)) -> &T {
+        &self.0[j][i]
+    }
+}
+
+/// `(row, column)`
+impl<T, const N: usize, const M: usize> core::ops::IndexMut<(usize, usize)> for MatNM<T, N, M> {
+    fn index_mut(&mut self, (i, j): (usize, usize)) -> &mut T {
+        &mut self.0[j][i]
+    }
+}
+
+impl<T, const N: usize, const M: usize> core::ops::Add for MatNM<T, N, M>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Self::from_fn(|i, j| self.0[j][i] + rhs.0[j][i])
+    }
+}
+
+impl<T, const N: usize, const M: usize> core::ops::Sub for MatNM<T, N, M>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Self::from_fn(|i, j| self.0[j][i] - rhs.0[j][i])
+    }
+}
+
+/// scaling
+impl<T, const N: usize, const M: usize> core::ops::Mul<T> for MatNM<T, N, M>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        MatNM(self.0.map(|c| c.map(|v| v * rhs)))
+    }
+}
+
+/// matrix-vector product
+impl<T, const N: usize, const M: usize> core::ops::Mul<VecN<T, M>> for MatNM<T, N, M>
+where
+    T: num_traits::Float,
+{
+    type Output = VecN<T, N>;
+    fn mul(self, rhs: VecN<T, M>) -> VecN<T, N> {
+        VecN::from_fn(|i| (0..M).fold(T::zero(), |s, j| s + self.0[j][i] * rhs[j]))
+    }
+}
+
+/// matrix product
+impl<T, const N: usize, const M: usize, const K: usize> core::ops::Mul<MatNM<T, M, K>>
+    for MatNM<T, N, M>
+where
+    T: num_traits::Float,
+{
+    type Output = MatNM<T, N, K>;
+    fn mul(self, rhs: MatNM<T, M, K>) -> MatNM<T, N, K> {
+        MatNM::from_fn(|i, k| (0..M).fold(T::zero(), |s, j| s + self.0[j][i] * rhs.0[k][j]))
+    }
+}
+
+#[test]
+fn test_eigen_decomposition_sym() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let b: [[f64; 7]; 7] =
+            core::array::from_fn(|_| core::array::from_fn(|_| rng.gen::<f64>() - 0.5));
+        let a: [[f64; 7]; 7] =
+            core::array::from_fn(|i| core::array::from_fn(|j| b[i][j] + b[j][i]));
+        let (lambda, v) = eigen_decomposition_sym(&a, 50);
+        for k in 0..7 {
+            for i in 0..7 {
+                let av = (0..7).fold(0., |s, j| s + a[i][j] * v[j][k]);
+                assert!((av - lambda[k] * v[i][k]).abs() < 1.0e-10);
+            }
+        }
+        let b = project_to_positive_semidefinite(&a);
+        let (lambda, _) = eigen_decomposition_sym(&b, 50);
+        assert!(lambda.iter().all(|&l| l > -1.0e-10));
+        // the projection is idempotent
+        let c = project_to_positive_semidefinite(&b);
+        for i in 0..7 {
+            for j in 0..7 {
+                assert!((b[i][j] - c[i][j]).abs() < 1.0e-10);
+            }
+        }
+    }
+}
+
+#[test]
+fn test_matnm() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let a: [f64; 9] = core::array::from_fn(|_| rng.gen::<f64>() - 0.5);
+        let b: [f64; 9] = core::array::from_fn(|_| rng.gen::<f64>() - 0.5);
+        let v: [f64; 3] = core::array::from_fn(|_| rng.gen::<f64>() - 0.5);
+        let ma = MatNM::<f64, 3, 3>::from(a);
+        let mb = MatNM::<f64, 3, 3>::from(b);
+        assert_eq!(ma[(1, 2)], a[7]);
+        // consistency with `mat3_col_major`
+        let ab: [f64; 9] = (ma * mb).into();
+        let ab0 = crate::mat3_col_major::mult_mat_col_major(&a, &b);
+        assert!(ab.iter().zip(ab0).all(|(x, y)| (x - y).abs() < 1.0e-10));
+        let av = (ma * VecN(v)).0;
+        let av0 = crate::mat3_col_major::mult_vec(&a, &v);
+        assert!(av.iter().zip(av0).all(|(x, y)| (x - y).abs() < 1.0e-10));
+        let at: [f64; 9] = ma.transpose().into();
+        assert_eq!(at, crate::mat3_col_major::transpose(&a));
+        let det0 = crate::mat3_col_major::determinant(&a);
+        assert!((ma.determinant() - det0).abs() < 1.0e-10);
+        let ai: [f64; 9] = ma.try_inverse().unwrap().into();
+        let ai0 = crate::mat3_col_major::try_inverse(&a).unwrap();
+        assert!(ai.iter().zip(ai0).all(|(x, y)| (x - y).abs() < 1.0e-8));
+        let x = ma.solve_lu(&VecN(v)).unwrap();
+        assert!(((ma * x) - VecN(v)).norm() < 1.0e-8);
+    }
+    // 5x5 and rectangular matrices
+    for _ in 0..10 {
+        let b = MatNM::<f64, 5, 3>::from_fn(|_, _| rng.gen::<f64>() - 0.5);
+        // positive definite
+        let a = b * b.transpose() + MatNM::<f64, 5, 5>::identity();
+        let v = VecN::<f64, 5>::from_fn(|_| rng.gen::<f64>() - 0.5);
+        let x0 = a.solve_lu(&v).unwrap();
+        let x1 = a.solve_cholesky(&v).unwrap();
+        assert!((a * x0 - v).norm() < 1.0e-10);
+        assert!((x0 - x1).norm() < 1.0e-10);
+        let l = a.cholesky().unwrap();
+        assert!(((l * l.transpose() - a) * 1.0e+10)
+            .0
+            .iter()
+            .flatten()
+            .all(|v| v.abs() < 1.));
+        let ai = a.try_inverse().unwrap();
+        let e = a * ai - MatNM::identity();
+        assert!(e.0.iter().flatten().all(|v| v.abs() < 1.0e-10));
+        // det(A * A) = det(A)^2
+        assert!(((a * a).determinant() - a.determinant().powi(2)).abs() < 1.0e-8);
+        // not positive definite
+        assert!((a * -1.).cholesky().is_none());
+    }
+    // singular
+    let a = MatNM::<f64, 2, 2>::from([1., 2., 2., 4.]);
+    assert!(a.try_inverse().is_none());
+    assert_eq!(a.determinant(), 0.);
+    // permutation
+    let a = MatNM::<f64, 2, 2>::from([0., 1., 1., 0.]);
+    assert_eq!(a.determinant(), -1.);
+    assert_eq!(a.try_inverse().unwrap(), a);
+    // NaN does not panic
+    for i in 0..9 {
+        let mut a = MatNM::<f64, 3, 3>::identity();
+        let (col, row) = (i / 3, i % 3);
+        a.0[col][row] = f64::NAN;
+        assert!(a.lu().is_none());
+        assert!(a.try_inverse().is_none());
+        assert!(a.solve_lu(&VecN([1., 2., 3.])).is_none());
+        assert!(a.determinant().is_nan());
+        // only the lower triangle is referred
+        assert_eq!(a.cholesky().is_none(), row >= col);
+    }
+}
@@ -4,6 +4,7 @@
 //! next 2 Reals are for half of major axis direction
 //! next 2 Reals are for half of minar axis direction
 
+#[cfg(feature = "rand")]
 pub fn from_random<RAND>(reng: &mut RAND) -> [f32; 6]
 where
     RAND: rand::Rng,
@@ -1,10 +1,13 @@
 #![allow(unused_imports)]
 //! 3D Oriented Bounding Box (OBB)
 
-use std::ops::MulAssign;
+use core::ops::MulAssign;
 
+#[cfg(feature = "rand")]
+use crate::aabb3::AABB3Trait;
+use crate::edge3::Edge3Trait;
 use crate::vec3::Vec3;
-use crate::{aabb3::AABB3Trait, edge3::Edge3Trait};
+#[cfg(feature = "rand")]
 use rand::distributions::{Distribution, Standard};
 /// trait for 3D Oriented Bounding Box (OBB)
 pub trait OBB3Trait<T> {
@@ -27,10 +30,11 @@ where
     }
 }
 
+#[cfg(feature = "rand")]
 pub fn from_random<RAND, Real>(reng: &mut RAND) -> [Real; 12]
 where
     RAND: rand::Rng,
-    Real: num_traits::Float + std::ops::MulAssign,
+    Real: num_traits::Float + core::ops::MulAssign,
     Standard: Distribution<Real>,
 {
     let one = Real::one();
@@ -209,7 +213,7 @@ fn test_nearest_to_point3() {
 /// Use Separating Axis Theorem (SAT) to check if two OBBs are intersected
 pub fn is_intersect_to_obb3<Real>(obb_i: &[Real; 12], obb_j: &[Real; 12]) -> bool
 where
-    Real: num_traits::Float + std::fmt::Debug + MulAssign,
+    Real: num_traits::Float + core::fmt::Debug + MulAssign,
 {
     let axes = {
         let (axes_i, _) = obb_i.unit_axes_and_half_edge_lengths();
@@ -293,7 +297,7 @@ fn test_is_intersect_to_obb3() {
 
 #[test]
 fn test2_is_intersect_to_obb3() {
-    use std::f64::consts::PI;
+    use core::f64::consts::PI;
 
     for i in 0..2 {
         let obb_i = [0., 0., 0., 1., 0., 0., 0., 1., 0., 0., 0., 1.];
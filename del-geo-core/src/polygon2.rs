@@ -0,0 +1,633 @@
+//! methods for 2D closed polygon
+//! the coordinates of the vertices are stored in a flat array `vtx2xy = [x0, y0, x1, y1, ...]`.
+//! the last vertex is connected to the first one.
+
+#[cfg(feature = "std")]
+use num_traits::AsPrimitive;
+
+fn vtx<Real>(vtx2xy: &[Real], i_vtx: usize) -> &[Real; 2] {
+    arrayref::array_ref!(vtx2xy, i_vtx * 2, 2)
+}
+
+/// signed area of the polygon (positive if counter-clockwise)
+pub fn area<Real>(vtx2xy: &[Real]) -> Real
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xy.len() / 2;
+    let zero = [Real::zero(); 2];
+    (0..num_vtx).fold(Real::zero(), |a, i_vtx| {
+        let p0 = vtx(vtx2xy, i_vtx);
+        let p1 = vtx(vtx2xy, (i_vtx + 1) % num_vtx);
+        a + crate::tri2::area(&zero, p0, p1)
+    })
+}
+
+#[test]
+fn test_area() {
+    let vtx2xy = [0f64, 0., 2., 0., 2., 1., 0., 1.];
+    assert!((area(&vtx2xy) - 2.).abs() < 1.0e-10);
+    let vtx2xy = [0f64, 0., 0., 1., 2., 1., 2., 0.];
+    assert!((area(&vtx2xy) + 2.).abs() < 1.0e-10);
+}
+
+/// center of the mass of the region enclosed by the polygon
+pub fn centroid<Real>(vtx2xy: &[Real]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xy.len() / 2;
+    let zero = [Real::zero(); 2];
+    let three = Real::one() + Real::one() + Real::one();
+    let mut sum_area = Real::zero();
+    let mut sum_moment = [Real::zero(); 2];
+    for i_vtx in 0..num_vtx {
+        let p0 = vtx(vtx2xy, i_vtx);
+        let p1 = vtx(vtx2xy, (i_vtx + 1) % num_vtx);
+        let a = crate::tri2::area(&zero, p0, p1);
+        sum_area = sum_area + a;
+        sum_moment[0] = sum_moment[0] + a * (p0[0] + p1[0]) / three;
+        sum_moment[1] = sum_moment[1] + a * (p0[1] + p1[1]) / three;
+    }
+    [sum_moment[0] / sum_area, sum_moment[1] / sum_area]
+}
+
+#[test]
+fn test_centroid() {
+    let vtx2xy = [1f64, 1., 3., 1., 3., 2., 1., 2.];
+    let c = centroid(&vtx2xy);
+    assert!((c[0] - 2.).abs() < 1.0e-10 && (c[1] - 1.5).abs() < 1.0e-10);
+    // L-shape consists of two unit squares and one more
+    let vtx2xy = [0f64, 0., 2., 0., 2., 1., 1., 1., 1., 2., 0., 2.];
+    let c = centroid(&vtx2xy);
+    assert!((c[0] - 5. / 6.).abs() < 1.0e-10 && (c[1] - 5. / 6.).abs() < 1.0e-10);
+}
+
+pub fn is_counter_clockwise<Real>(vtx2xy: &[Real]) -> bool
+where
+    Real: num_traits::Float,
+{
+    area(vtx2xy) > Real::zero()
+}
+
+/// reverse the order of the vertices to flip the orientation
+#[cfg(feature = "std")]
+pub fn reverse_orientation<Real>(vtx2xy: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float,
+{
+    vtx2xy
+        .chunks(2)
+        .rev()
+        .flat_map(|xy| [xy[0], xy[1]])
+        .collect()
+}
+
+/// winding number of the polygon around the point `q`.
+/// the number is computed by counting signed crossings of the edges and the ray
+/// in +x direction from `q` (Sunday's algorithm) without trigonometric functions.
+/// * Return - positive if the polygon winds counter-clockwise around `q`
+pub fn winding_number<Real>(vtx2xy: &[Real], q: &[Real; 2]) -> i64
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xy.len() / 2;
+    let mut wn = 0;
+    for i_vtx in 0..num_vtx {
+        let p0 = vtx(vtx2xy, i_vtx);
+        let p1 = vtx(vtx2xy, (i_vtx + 1) % num_vtx);
+        if p0[1] <= q[1] {
+            if p1[1] > q[1] && crate::tri2::area(p0, p1, q) > Real::zero() {
+                wn += 1; // upward crossing with `q` on the left
+            }
+        } else if p1[1] <= q[1] && crate::tri2::area(p0, p1, q) < Real::zero() {
+            wn -= 1; // downward crossing with `q` on the right
+        }
+    }
+    wn
+}
+
+pub fn is_include_point<Real>(vtx2xy: &[Real], q: &[Real; 2]) -> bool
+where
+    Real: num_traits::Float,
+{
+    winding_number(vtx2xy, q) != 0
+}
+
+#[test]
+fn test_winding_number() {
+    use num_traits::FloatConst;
+    use rand::Rng;
+    let vtx2xy = [0f64, 0., 2., 0., 2., 1., 1., 1., 1., 2., 0., 2.];
+    assert_eq!(winding_number(&vtx2xy, &[0.5, 0.5]), 1);
+    assert_eq!(winding_number(&vtx2xy, &[1.5, 1.5]), 0);
+    assert_eq!(
+        winding_number(&reverse_orientation(&vtx2xy), &[0.5, 1.5]),
+        -1
+    );
+    // ray passing through vertices
+    assert_eq!(winding_number(&vtx2xy, &[0.5, 1.0]), 1);
+    assert_eq!(winding_number(&vtx2xy, &[-0.5, 1.0]), 0);
+    // compare with the sum of the continuous winding numbers of the edges
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let num_vtx = 7;
+    let vtx2xy: Vec<f64> = (0..num_vtx)
+        .flat_map(|i| {
+            let theta = 2. * f64::PI() * (i as f64) / (num_vtx as f64);
+            let r = 0.5 + rng.gen::<f64>();
+            [r * theta.cos(), r * theta.sin()]
+        })
+        .collect();
+    for _ in 0..100 {
+        let q = [3. * rng.gen::<f64>() - 1.5, 3. * rng.gen::<f64>() - 1.5];
+        let wn0: f64 = (0..num_vtx)
+            .map(|i| {
+                crate::edge2::winding_number(vtx(&vtx2xy, i), vtx(&vtx2xy, (i + 1) % num_vtx), &q)
+            })
+            .sum();
+        assert_eq!(winding_number(&vtx2xy, &q), wn0.round() as i64);
+    }
+}
+
+/// check if the polygon is convex.
+/// all the corners turn in the same direction and the polygon winds around only once.
+pub fn is_convex<Real>(vtx2xy: &[Real]) -> bool
+where
+    Real: num_traits::Float + num_traits::FloatConst,
+{
+    let num_vtx = vtx2xy.len() / 2;
+    if num_vtx < 3 {
+        return false;
+    }
+    let mut sign = Real::zero();
+    let mut sum_angle = Real::zero();
+    for i_vtx in 0..num_vtx {
+        let p0 = vtx(vtx2xy, i_vtx);
+        let p1 = vtx(vtx2xy, (i_vtx + 1) % num_vtx);
+        let p2 = vtx(vtx2xy, (i_vtx + 2) % num_vtx);
+        let a = crate::tri2::area(p0, p1, p2);
+        if a == Real::zero() {
+            continue;
+        }
+        if sign == Real::zero() {
+            sign = a.signum();
+        } else if a.signum() != sign {
+            return false;
+        }
+        let e0 = crate::vec2::sub(p1, p0);
+        let e1 = crate::vec2::sub(p2, p1);
+        sum_angle = sum_angle + crate::vec2::angle_between_two_vecs(&e0, &e1);
+    }
+    // the sum of exterior angles is 2*PI for a simple convex polygon
+    (sum_angle.abs() - Real::PI() - Real::PI()).abs() < Real::PI()
+}
+
+#[test]
+fn test_is_convex() {
+    assert!(is_convex(&[0., 0., 1., 0., 1., 1., 0., 1.]));
+    assert!(is_convex(&[0., 0., 0., 1., 1., 1., 1., 0.]));
+    assert!(!is_convex(&[
+        0., 0., 2., 0., 2., 1., 1., 1., 1., 2., 0., 2.
+    ]));
+    // pentagram turns in the same direction but winds twice
+    let vtx2xy: Vec<f64> = (0..5)
+        .flat_map(|i| {
+            let theta = 4. * core::f64::consts::PI * (i as f64) / 5.;
+            [theta.cos(), theta.sin()]
+        })
+        .collect();
+    assert!(!is_convex(&vtx2xy));
+}
+
+/// triangulation of a simple polygon by ear clipping
+/// * Return - `tri2vtx` indices of the vertices (counter-clockwise)
+#[cfg(feature = "std")]
+pub fn triangulate_ear_clipping<Real>(vtx2xy: &[Real]) -> Vec<usize>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xy.len() / 2;
+    if num_vtx < 3 {
+        return vec![];
+    }
+    let mut vtxs: Vec<usize> = if is_counter_clockwise(vtx2xy) {
+        (0..num_vtx).collect()
+    } else {
+        (0..num_vtx).rev().collect()
+    };
+    let mut tri2vtx = Vec::with_capacity((num_vtx - 2) * 3);
+    let is_ear = |vtxs: &[usize], i: usize| {
+        let n = vtxs.len();
+        let (i0, i1, i2) = (vtxs[(i + n - 1) % n], vtxs[i], vtxs[(i + 1) % n]);
+        let (p0, p1, p2) = (vtx(vtx2xy, i0), vtx(vtx2xy, i1), vtx(vtx2xy, i2));
+        if crate::tri2::area(p0, p1, p2) <= Real::zero() {
+            return false; // reflex corner
+        }
+        !vtxs.iter().any(|&j| {
+            j != i0
+                && j != i1
+                && j != i2
+                && crate::tri2::is_inside(p0, p1, p2, vtx(vtx2xy, j), Real::one()).is_some()
+        })
+    };
+    while vtxs.len() > 3 {
+        let n = vtxs.len();
+        // fall back to the most convex corner if no ear is found due to the degeneracy
+        let i = (0..n).find(|&i| is_ear(&vtxs, i)).unwrap_or_else(|| {
+            (0..n)
+                .max_by(|&a, &b| {
+                    let area = |i: usize| {
+                        crate::tri2::area(
+                            vtx(vtx2xy, vtxs[(i + n - 1) % n]),
+                            vtx(vtx2xy, vtxs[i]),
+                            vtx(vtx2xy, vtxs[(i + 1) % n]),
+                        )
+                    };
+                    area(a).partial_cmp(&area(b)).unwrap()
+                })
+                .unwrap()
+        });
+        tri2vtx.extend([vtxs[(i + n - 1) % n], vtxs[i], vtxs[(i + 1) % n]]);
+        vtxs.remove(i);
+    }
+    tri2vtx.extend([vtxs[0], vtxs[1], vtxs[2]]);
+    tri2vtx
+}
+
+#[test]
+fn test_triangulate_ear_clipping() {
+    let vtx2xy = [0f64, 0., 2., 0., 2., 1., 1., 1., 1., 2., 0., 2.];
+    for vtx2xy in [vtx2xy.to_vec(), reverse_orientation(&vtx2xy)] {
+        let tri2vtx = triangulate_ear_clipping(&vtx2xy);
+        assert_eq!(tri2vtx.len(), 4 * 3);
+        let mut sum_area = 0.;
+        for tri in tri2vtx.chunks(3) {
+            let a = crate::tri2::area(
+                vtx(&vtx2xy, tri[0]),
+                vtx(&vtx2xy, tri[1]),
+                vtx(&vtx2xy, tri[2]),
+            );
+            assert!(a > 0.);
+            sum_area += a;
+        }
+        assert!((sum_area - 3.).abs() < 1.0e-10);
+    }
+}
+
+/// clip the polygon by a convex polygon (Sutherland–Hodgman algorithm)
+/// * `vtx2xy_clip` - convex polygon in counter-clockwise order
+/// * Return - the clipped polygon. empty if there is no overlap
+#[cfg(feature = "std")]
+pub fn clip_by_convex_polygon<Real>(vtx2xy: &[Real], vtx2xy_clip: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float,
+{
+    let num_clip = vtx2xy_clip.len() / 2;
+    let mut output = vtx2xy.to_vec();
+    for i_clip in 0..num_clip {
+        let a = vtx(vtx2xy_clip, i_clip);
+        let b = vtx(vtx2xy_clip, (i_clip + 1) % num_clip);
+        let input = core::mem::take(&mut output);
+        let num_vtx = input.len() / 2;
+        for i_vtx in 0..num_vtx {
+            let p0 = vtx(&input, i_vtx);
+            let p1 = vtx(&input, (i_vtx + 1) % num_vtx);
+            let d0 = crate::tri2::area(a, b, p0);
+            let d1 = crate::tri2::area(a, b, p1);
+            if d0 >= Real::zero() {
+                output.extend(p0);
+            }
+            if (d0 >= Real::zero()) != (d1 >= Real::zero()) {
+                let r = d0 / (d0 - d1);
+                output.extend(crate::vec2::axpy(r, &crate::vec2::sub(p1, p0), p0));
+            }
+        }
+    }
+    output
+}
+
+#[test]
+fn test_clip_by_convex_polygon() {
+    let vtx2xy = [0f64, 0., 2., 0., 2., 1., 1., 1., 1., 2., 0., 2.];
+    let clip = [0.5f64, 0.5, 1.5, 0.5, 1.5, 1.5, 0.5, 1.5];
+    let res = clip_by_convex_polygon(&vtx2xy, &clip);
+    assert!((area(&res) - 0.75).abs() < 1.0e-10);
+    let clip = [3., 3., 4., 3., 4., 4.];
+    assert!(clip_by_convex_polygon(&vtx2xy, &clip).is_empty());
+}
+
+/// intersection of two simple polygons (Weiler–Atherton algorithm)
+/// * `vtx2xy_a`, `vtx2xy_b` - simple polygons in counter-clockwise order.
+///   degenerate configurations (a vertex exactly on the other polygon's edge,
+///   or overlapping edges) are not handled.
+/// * Return - list of the polygons of the overlapping region
+#[cfg(feature = "std")]
+pub fn intersection_weiler_atherton<Real>(vtx2xy_a: &[Real], vtx2xy_b: &[Real]) -> Vec<Vec<Real>>
+where
+    Real: num_traits::Float,
+{
+    struct Node<Real> {
+        xy: [Real; 2],
+        // index of the same intersection in the other polygon
+        neighbor: Option<usize>,
+        is_entry: bool,
+    }
+    let num_a = vtx2xy_a.len() / 2;
+    let num_b = vtx2xy_b.len() / 2;
+    // (edge index in a, ratio in a, edge index in b, ratio in b, point)
+    let mut isecs = vec![];
+    for i_a in 0..num_a {
+        let (sa, ea) = (vtx(vtx2xy_a, i_a), vtx(vtx2xy_a, (i_a + 1) % num_a));
+        for i_b in 0..num_b {
+            let (sb, eb) = (vtx(vtx2xy_b, i_b), vtx(vtx2xy_b, (i_b + 1) % num_b));
+            let Some((ra, rb)) = crate::edge2::intersection_edge2(sa, ea, sb, eb) else {
+                continue;
+            };
+            if !ra.is_finite() || !rb.is_finite() {
+                continue; // parallel
+            }
+            let xy = crate::vec2::axpy(ra, &crate::vec2::sub(ea, sa), sa);
+            isecs.push((i_a, ra, i_b, rb, xy));
+        }
+    }
+    if isecs.is_empty() {
+        // one contains the other or they are disjoint
+        if num_a > 0 && num_b > 0 {
+            if is_include_point(vtx2xy_b, vtx(vtx2xy_a, 0)) {
+                return vec![vtx2xy_a.to_vec()];
+            }
+            if is_include_point(vtx2xy_a, vtx(vtx2xy_b, 0)) {
+                return vec![vtx2xy_b.to_vec()];
+            }
+        }
+        return vec![];
+    }
+    // build the vertex lists with the intersections inserted
+    let build = |vtx2xy: &[Real], num_vtx: usize, is_a: bool| {
+        let mut nodes: Vec<Node<Real>> = vec![];
+        let mut isec2node = vec![0; isecs.len()];
+        for i_vtx in 0..num_vtx {
+            nodes.push(Node {
+                xy: *vtx(vtx2xy, i_vtx),
+                neighbor: None,
+                is_entry: false,
+            });
+            let mut on_edge: Vec<(Real, usize)> = isecs
+                .iter()
+                .enumerate()
+                .filter(|(_, s)| if is_a { s.0 == i_vtx } else { s.2 == i_vtx })
+                .map(|(i, s)| (if is_a { s.1 } else { s.3 }, i))
+                .collect();
+            on_edge.sort_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+            for (_, i_isec) in on_edge {
+                isec2node[i_isec] = nodes.len();
+                nodes.push(Node {
+                    xy: isecs[i_isec].4,
+                    neighbor: Some(i_isec),
+                    is_entry: false,
+                });
+            }
+        }
+        (nodes, isec2node)
+    };
+    let (mut nodes_a, isec2node_a) = build(vtx2xy_a, num_a, true);
+    let (mut nodes_b, isec2node_b) = build(vtx2xy_b, num_b, false);
+    for node in nodes_a.iter_mut() {
+        node.neighbor = node.neighbor.map(|i_isec| isec2node_b[i_isec]);
+    }
+    for node in nodes_b.iter_mut() {
+        node.neighbor = node.neighbor.map(|i_isec| isec2node_a[i_isec]);
+    }
+    // entry/exit flags alternate along the boundary
+    let mark_entry = |nodes: &mut [Node<Real>], vtx2xy_other: &[Real]| {
+        let mut is_entry = !is_include_point(vtx2xy_other, &nodes[0].xy);
+        for node in nodes.iter_mut().filter(|n| n.neighbor.is_some()) {
+            node.is_entry = is_entry;
+            is_entry = !is_entry;
+        }
+    };
+    mark_entry(&mut nodes_a, vtx2xy_b);
+    mark_entry(&mut nodes_b, vtx2xy_a);
+    // traverse
+    let mut visited_a = vec![false; nodes_a.len()];
+    let mut res = vec![];
+    for i_start in 0..nodes_a.len() {
+        if nodes_a[i_start].neighbor.is_none() || visited_a[i_start] {
+            continue;
+        }
+        let mut polygon = vec![];
+        let (mut on_a, mut i_node) = (true, i_start);
+        loop {
+            let nodes = if on_a { &nodes_a } else { &nodes_b };
+            let n = nodes.len();
+            let forward = nodes[i_node].is_entry;
+            // walk along the current polygon until the next intersection
+            loop {
+                if on_a {
+                    visited_a[i_node] = true;
+                }
+                polygon.extend(nodes[i_node].xy);
+                i_node = if forward {
+                    (i_node + 1) % n
+                } else {
+                    (i_node + n - 1) % n
+                };
+                if nodes[i_node].neighbor.is_some() {
+                    break;
+                }
+            }
+            if on_a {
+                visited_a[i_node] = true;
+            }
+            // jump to the other polygon
+            i_node = nodes[i_node].neighbor.unwrap();
+            on_a = !on_a;
+            if on_a && i_node == i_start {
+                break;
+            }
+            if polygon.len() > 2 * (nodes_a.len() + nodes_b.len()) {
+                break; // inconsistent flags due to the degeneracy
+            }
+        }
+        res.push(polygon);
+    }
+    res
+}
+
+#[test]
+fn test_intersection_weiler_atherton() {
+    // L-shape and a square
+    let vtx2xy_a = [0f64, 0., 2., 0., 2., 1., 1., 1., 1., 2., 0., 2.];
+    let vtx2xy_b = [0.5, 0.5, 1.5, 0.5, 1.5, 1.5, 0.5, 1.5];
+    let res = intersection_weiler_atherton(&vtx2xy_a, &vtx2xy_b);
+    assert_eq!(res.len(), 1);
+    assert!((area(&res[0]) - 0.75).abs() < 1.0e-10);
+    // U-shape and a horizontal bar gives two separate regions
+    let vtx2xy_a = [
+        0f64, 0., 3., 0., 3., 3., 2., 3., 2., 1., 1., 1., 1., 3., 0., 3.,
+    ];
+    let vtx2xy_b = [-1., 2., 4., 2., 4., 2.5, -1., 2.5];
+    let res = intersection_weiler_atherton(&vtx2xy_a, &vtx2xy_b);
+    assert_eq!(res.len(), 2);
+    for polygon in res.iter() {
+        assert!((area(polygon) - 0.5).abs() < 1.0e-10);
+    }
+    // containment
+    let vtx2xy_b = [0.1, 0.1, 0.9, 0.1, 0.9, 0.9];
+    let res = intersection_weiler_atherton(&vtx2xy_a, &vtx2xy_b);
+    assert_eq!(res, vec![vtx2xy_b.to_vec()]);
+    // disjoint
+    let vtx2xy_b = [5., 5., 6., 5., 6., 6.];
+    assert!(intersection_weiler_atherton(&vtx2xy_a, &vtx2xy_b).is_empty());
+}
+
+/// unit normal of the edge pointing outside for a counter-clockwise polygon
+#[cfg(feature = "std")]
+fn edge_normal<Real>(p0: &[Real; 2], p1: &[Real; 2]) -> [Real; 2]
+where
+    Real: num_traits::Float,
+{
+    let t = crate::edge2::unit_edge_vector(p0, p1);
+    [t[1], -t[0]]
+}
+
+/// indices of the vertices skipping the duplicated consecutive vertices (i.e., zero-length edges)
+#[cfg(feature = "std")]
+fn distinct_vertices<Real>(vtx2xy: &[Real]) -> Vec<usize>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xy.len() / 2;
+    (0..num_vtx)
+        .filter(|&i_vtx| vtx(vtx2xy, i_vtx) != vtx(vtx2xy, (i_vtx + 1) % num_vtx))
+        .collect()
+}
+
+/// offset the polygon by `distance` with mitered corners.
+/// corners sharper than `miter_limit` (ratio of the miter length to `distance`) are beveled.
+/// positive `distance` expands a counter-clockwise polygon.
+/// self-intersections of the result are not resolved.
+#[cfg(feature = "std")]
+pub fn offset_miter<Real>(vtx2xy: &[Real], distance: Real, miter_limit: Real) -> Vec<Real>
+where
+    Real: num_traits::Float + 'static,
+    f64: AsPrimitive<Real>,
+{
+    let idxs = distinct_vertices(vtx2xy);
+    let num_vtx = idxs.len();
+    let mut res = vec![];
+    for i in 0..num_vtx {
+        let p0 = vtx(vtx2xy, idxs[(i + num_vtx - 1) % num_vtx]);
+        let p1 = vtx(vtx2xy, idxs[i]);
+        let p2 = vtx(vtx2xy, idxs[(i + 1) % num_vtx]);
+        let n0 = edge_normal(p0, p1);
+        let n1 = edge_normal(p1, p2);
+        let cos = crate::vec2::dot(&n0, &n1);
+        // length of the miter is distance / cos(theta/2) where cos(theta/2)^2 = (1 + cos)/2
+        let half_cos_sq = (Real::one() + cos) * 0.5.as_();
+        if half_cos_sq * miter_limit * miter_limit >= Real::one() {
+            let m = crate::vec2::scale(&crate::vec2::add(&n0, &n1), distance / (Real::one() + cos));
+            res.extend(crate::vec2::add(p1, &m));
+        } else {
+            res.extend(crate::vec2::axpy(distance, &n0, p1));
+            res.extend(crate::vec2::axpy(distance, &n1, p1));
+        }
+    }
+    res
+}
+
+/// offset the polygon by `distance` with rounded corners.
+/// the arc at a corner is divided so that each segment spans at most `max_angle` radian.
+/// the inner corners where the boundary turns back are beveled.
+/// positive `distance` expands a counter-clockwise polygon.
+/// self-intersections of the result are not resolved.
+#[cfg(feature = "std")]
+pub fn offset_round<Real>(vtx2xy: &[Real], distance: Real, max_angle: Real) -> Vec<Real>
+where
+    Real: num_traits::Float + 'static + AsPrimitive<usize>,
+    f64: AsPrimitive<Real>,
+    usize: AsPrimitive<Real>,
+{
+    let idxs = distinct_vertices(vtx2xy);
+    let num_vtx = idxs.len();
+    let mut res = vec![];
+    for i in 0..num_vtx {
+        let p0 = vtx(vtx2xy, idxs[(i + num_vtx - 1) % num_vtx]);
+        let p1 = vtx(vtx2xy, idxs[i]);
+        let p2 = vtx(vtx2xy, idxs[(i + 1) % num_vtx]);
+        let n0 = edge_normal(p0, p1);
+        let n1 = edge_normal(p1, p2);
+        let cos = crate::vec2::dot(&n0, &n1);
+        let turn = crate::vec2::angle_between_two_vecs(&n0, &n1);
+        if turn * distance <= Real::zero() {
+            if (Real::one() + cos) * 0.5.as_() <= Real::epsilon().sqrt() {
+                // the boundary turns back: the offset edges are (nearly) parallel
+                res.extend(crate::vec2::axpy(distance, &n0, p1));
+                res.extend(crate::vec2::axpy(distance, &n1, p1));
+            } else {
+                // inner corner: intersection of the offset edges
+                let m =
+                    crate::vec2::scale(&crate::vec2::add(&n0, &n1), distance / (Real::one() + cos));
+                res.extend(crate::vec2::add(p1, &m));
+            }
+            continue;
+        }
+        let ndiv: usize = (turn.abs() / max_angle).ceil().as_();
+        let ndiv = ndiv.max(1);
+        let theta0 = n0[1].atan2(n0[0]);
+        for idiv in 0..ndiv + 1 {
+            let theta = theta0 + turn * idiv.as_() / ndiv.as_();
+            res.extend([
+                p1[0] + distance * theta.cos(),
+                p1[1] + distance * theta.sin(),
+            ]);
+        }
+    }
+    res
+}
+
+#[test]
+fn test_offset() {
+    let vtx2xy = [0f64, 0., 1., 0., 1., 1., 0., 1.];
+    let res = offset_miter(&vtx2xy, 0.1, 2.);
+    assert_eq!(res.len(), 8);
+    assert!((area(&res) - 1.44).abs() < 1.0e-10);
+    let res = offset_miter(&vtx2xy, -0.1, 2.);
+    assert!((area(&res) - 0.64).abs() < 1.0e-10);
+    // beveled corner
+    let res = offset_miter(&vtx2xy, 0.1, 1.2);
+    assert_eq!(res.len(), 16);
+    assert!((area(&res) - (1.44 - 4. * 0.005)).abs() < 1.0e-10);
+    // round corner approaches the Minkowski sum with a disk
+    let res = offset_round(&vtx2xy, 0.1, 0.01);
+    let area_trg = 1. + 4. * 0.1 + core::f64::consts::PI * 0.01;
+    assert!((area(&res) - area_trg).abs() < 1.0e-4);
+    let res = offset_round(&vtx2xy, -0.1, 0.01);
+    assert_eq!(res.len(), 8);
+    assert!((area(&res) - 0.64).abs() < 1.0e-10);
+    // L-shape has one concave corner
+    let vtx2xy = [0f64, 0., 2., 0., 2., 1., 1., 1., 1., 2., 0., 2.];
+    let res = offset_miter(&vtx2xy, 0.1, 2.);
+    assert!((area(&res) - (3. + 0.1 * 8. + 4. * 0.01)).abs() < 1.0e-10);
+    // duplicated vertices are ignored
+    let vtx2xy = [0f64, 0., 1., 0., 1., 0., 1., 1., 0., 1., 0., 0.];
+    let res = offset_round(&vtx2xy, -0.1, 0.01);
+    assert_eq!(res.len(), 8);
+    assert!((area(&res) - 0.64).abs() < 1.0e-10);
+    let res = offset_miter(&vtx2xy, 0.1, 2.);
+    assert!((area(&res) - 1.44).abs() < 1.0e-10);
+    // spikes going outside (tip at `(3, 1)`) and inside (tip at `(1, 1)`) of the square
+    let vtx2xy = [
+        0f64, 0., 2., 0., 2., 1., 3., 1., 2., 1., 2., 2., 1., 2., 1., 1., 1., 2., 0., 2.,
+    ];
+    for distance in [0.1, -0.1] {
+        for res in [
+            offset_round(&vtx2xy, distance, 0.1),
+            offset_miter(&vtx2xy, distance, 2.),
+        ] {
+            assert!(res.iter().all(|v| v.is_finite()));
+            assert!(res.iter().all(|v| v.abs() < 3.2));
+        }
+    }
+}
@@ -0,0 +1,477 @@
+//! methods for 3D open polyline
+//! the coordinates of the vertices are stored in a flat array `vtx2xyz = [x0, y0, z0, x1, y1, z1, ...]`.
+//! a frame at a vertex is stored as `[tangent, normal, binormal]`.
+
+use crate::vec3::Vec3;
+
+fn vtx<Real>(vtx2xyz: &[Real], i_vtx: usize) -> &[Real; 3] {
+    arrayref::array_ref!(vtx2xyz, i_vtx * 3, 3)
+}
+
+/// cumulative arc length at each vertex. the first entry is zero.
+#[cfg(feature = "std")]
+pub fn vtx2arclength<Real>(vtx2xyz: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    let mut res = Vec::with_capacity(num_vtx);
+    let mut s = Real::zero();
+    for i_vtx in 0..num_vtx {
+        if i_vtx > 0 {
+            s = s + crate::vec3::distance(vtx(vtx2xyz, i_vtx - 1), vtx(vtx2xyz, i_vtx));
+        }
+        res.push(s);
+    }
+    res
+}
+
+pub fn length<Real>(vtx2xyz: &[Real]) -> Real
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    (1..num_vtx).fold(Real::zero(), |s, i_vtx| {
+        s + crate::vec3::distance(vtx(vtx2xyz, i_vtx - 1), vtx(vtx2xyz, i_vtx))
+    })
+}
+
+/// position on the polyline at the arc length `s` from the first vertex.
+/// `s` is clamped to the range of the polyline.
+pub fn position_at_arclength<Real>(vtx2xyz: &[Real], vtx2arclength: &[Real], s: Real) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2arclength.len();
+    assert!(num_vtx > 0);
+    if num_vtx == 1 || s <= vtx2arclength[0] {
+        return *vtx(vtx2xyz, 0);
+    }
+    // index of the first vertex whose arc length is larger than `s`
+    let i1 = vtx2arclength.partition_point(|&si| si <= s);
+    if i1 >= num_vtx {
+        return *vtx(vtx2xyz, num_vtx - 1);
+    }
+    let i0 = i1 - 1;
+    let r = (s - vtx2arclength[i0]) / (vtx2arclength[i1] - vtx2arclength[i0]);
+    let p0 = vtx(vtx2xyz, i0);
+    let p1 = vtx(vtx2xyz, i1);
+    crate::vec3::axpy(r, &p1.sub(p0), p0)
+}
+
+/// resample the polyline with `num_vtx` vertices placed at the uniform arc length intervals.
+/// the first and the last vertices are kept.
+#[cfg(feature = "std")]
+pub fn resample_uniform<Real>(vtx2xyz: &[Real], num_vtx: usize) -> Vec<Real>
+where
+    Real: num_traits::Float,
+{
+    assert!(num_vtx >= 2);
+    let vtx2arclength = vtx2arclength(vtx2xyz);
+    let len = vtx2arclength.last().copied().unwrap();
+    let ndiv = Real::from(num_vtx - 1).unwrap();
+    (0..num_vtx)
+        .flat_map(|i_vtx| {
+            let s = len * Real::from(i_vtx).unwrap() / ndiv;
+            position_at_arclength(vtx2xyz, &vtx2arclength, s)
+        })
+        .collect()
+}
+
+#[test]
+fn test_resample_uniform() {
+    let vtx2xyz = [0f64, 0., 0., 1., 0., 0., 1., 2., 0.];
+    assert!((length(&vtx2xyz) - 3.).abs() < 1.0e-10);
+    let vtx2xyz_new = resample_uniform(&vtx2xyz, 7);
+    assert_eq!(vtx2xyz_new.len(), 21);
+    let vtx2arclength = vtx2arclength(&vtx2xyz_new);
+    for (i_vtx, s) in vtx2arclength.iter().enumerate() {
+        assert!((s - 0.5 * i_vtx as f64).abs() < 1.0e-10);
+    }
+    assert!(crate::vec3::distance(vtx(&vtx2xyz_new, 3), &[1., 0.5, 0.]) < 1.0e-10);
+}
+
+/// unit tangent vector at each vertex.
+/// the tangent at an interior vertex bisects the directions of the two adjacent edges.
+/// a zero-length edge (e.g., duplicated consecutive vertices) takes the direction of the previous edge
+/// (or the next edge at the beginning). The tangents are zero if all the vertices coincide
+#[cfg(feature = "std")]
+pub fn vtx2tangent<Real>(vtx2xyz: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    assert!(num_vtx >= 2);
+    let num_edge = num_vtx - 1;
+    let mut edge2dir: Vec<Option<[Real; 3]>> = (0..num_edge)
+        .map(|i_edge| {
+            let d = vtx(vtx2xyz, i_edge + 1).sub(vtx(vtx2xyz, i_edge));
+            let l = d.norm();
+            (l > Real::zero()).then(|| d.scale(Real::one() / l))
+        })
+        .collect();
+    let first = edge2dir.iter().find_map(|v| *v);
+    let mut prev = first;
+    for dir in edge2dir.iter_mut() {
+        match dir {
+            Some(_) => prev = *dir,
+            None => *dir = prev,
+        }
+    }
+    let edge2dir: Vec<[Real; 3]> = edge2dir
+        .into_iter()
+        .map(|v| v.unwrap_or([Real::zero(); 3]))
+        .collect();
+    (0..num_vtx)
+        .flat_map(|i_vtx| {
+            if i_vtx == 0 {
+                edge2dir[0]
+            } else if i_vtx == num_edge {
+                edge2dir[num_edge - 1]
+            } else {
+                let t = edge2dir[i_vtx - 1].add(&edge2dir[i_vtx]);
+                if t.squared_norm() < Real::epsilon() {
+                    edge2dir[i_vtx] // the polyline folds back
+                } else {
+                    t.normalize()
+                }
+            }
+        })
+        .collect()
+}
+
+/// discrete curvature at each vertex computed as the inverse of the radius of
+/// the circle passing through the vertex and its two neighbors.
+/// the curvature at the end points is zero.
+#[cfg(feature = "std")]
+pub fn vtx2curvature<Real>(vtx2xyz: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    let two = Real::one() + Real::one();
+    (0..num_vtx)
+        .map(|i_vtx| {
+            if i_vtx == 0 || i_vtx + 1 == num_vtx {
+                return Real::zero();
+            }
+            let p0 = vtx(vtx2xyz, i_vtx - 1);
+            let p1 = vtx(vtx2xyz, i_vtx);
+            let p2 = vtx(vtx2xyz, i_vtx + 1);
+            let e0 = p1.sub(p0);
+            let e1 = p2.sub(p1);
+            let denom = e0.norm() * e1.norm() * crate::vec3::distance(p0, p2);
+            if denom.is_zero() {
+                return Real::zero();
+            }
+            two * e0.cross(&e1).norm() / denom
+        })
+        .collect()
+}
+
+/// discrete torsion at each vertex computed from the rotation of the Frenet binormals
+/// of the neighboring vertices around the tangent.
+/// the torsion is zero where the binormals are not defined.
+#[cfg(feature = "std")]
+pub fn vtx2torsion<Real>(vtx2xyz: &[Real]) -> Vec<Real>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    let vtx2tangent = vtx2tangent(vtx2xyz);
+    let vtx2arclength = vtx2arclength(vtx2xyz);
+    let binormal = |i_vtx: usize| {
+        if i_vtx == 0 || i_vtx + 1 >= num_vtx {
+            return None;
+        }
+        let e0 = vtx(vtx2xyz, i_vtx).sub(vtx(vtx2xyz, i_vtx - 1));
+        let e1 = vtx(vtx2xyz, i_vtx + 1).sub(vtx(vtx2xyz, i_vtx));
+        let b = e0.cross(&e1);
+        let len = b.norm();
+        if len <= Real::epsilon() * e0.norm() * e1.norm() {
+            return None;
+        }
+        Some(b.scale(Real::one() / len))
+    };
+    (0..num_vtx)
+        .map(|i_vtx| {
+            if i_vtx == 0 || i_vtx + 1 >= num_vtx {
+                return Real::zero();
+            }
+            let (Some(b0), Some(b1)) = (binormal(i_vtx - 1), binormal(i_vtx + 1)) else {
+                return Real::zero();
+            };
+            let t = vtx(&vtx2tangent, i_vtx);
+            // db/ds = -torsion * n and b x n = -t, thus the rotation is positive around t
+            let angle = b0.cross(&b1).dot(t).atan2(b0.dot(&b1));
+            angle / (vtx2arclength[i_vtx + 1] - vtx2arclength[i_vtx - 1])
+        })
+        .collect()
+}
+
+/// rotate the vector `v` with the minimum rotation that maps the unit vector `t0` to `t1`
+pub fn parallel_transport<Real>(v: &[Real; 3], t0: &[Real; 3], t1: &[Real; 3]) -> [Real; 3]
+where
+    Real: num_traits::Float,
+{
+    let c = t0.dot(t1);
+    let a = t0.cross(t1); // rotation axis scaled by the sine
+    if Real::one() + c < Real::epsilon() {
+        // `t0` and `t1` are opposite. rotate 180 degrees around an axis perpendicular to `t0`
+        let k = crate::vec3::orthogonalize(t0, v);
+        let k = if k.squared_norm() < Real::epsilon() {
+            crate::vec3::basis_xy_from_basis_z(t0).0
+        } else {
+            k.normalize()
+        };
+        return k.scale((Real::one() + Real::one()) * k.dot(v)).sub(v);
+    }
+    // Rodrigues' rotation formula
+    let v1 = v.scale(c).add(&a.cross(v));
+    crate::vec3::axpy(a.dot(v) / (Real::one() + c), &a, &v1)
+}
+
+#[test]
+fn test_parallel_transport() {
+    let t0 = crate::vec3::normalize(&[0.3f64, -0.2, 0.9]);
+    let t1 = crate::vec3::normalize(&[-0.5f64, 0.4, 0.1]);
+    let (n0, b0) = crate::vec3::basis_xy_from_basis_z(&t0);
+    let n1 = parallel_transport(&n0, &t0, &t1);
+    let b1 = parallel_transport(&b0, &t0, &t1);
+    assert!((parallel_transport(&t0, &t0, &t1).sub(&t1)).norm() < 1.0e-10);
+    assert!(n1.dot(&t1).abs() < 1.0e-10);
+    assert!((n1.norm() - 1.).abs() < 1.0e-10);
+    assert!((crate::vec3::scalar_triple_product(&n1, &b1, &t1) - 1.).abs() < 1.0e-10);
+    // the component along the rotation axis is kept
+    let a = t0.cross(&t1);
+    assert!((n1.dot(&a) - n0.dot(&a)).abs() < 1.0e-10);
+    // opposite direction
+    let t1 = t0.scale(-1.);
+    let n1 = parallel_transport(&n0, &t0, &t1);
+    assert!(n1.dot(&t1).abs() < 1.0e-10);
+    assert!((n1.norm() - 1.).abs() < 1.0e-10);
+}
+
+/// make an orthonormal frame `[t, n, t x n]` from the tangent and the normal-ish vector `n`
+#[cfg(feature = "std")]
+fn frame_from_tangent_normal<Real>(t: &[Real; 3], n: &[Real; 3]) -> [[Real; 3]; 3]
+where
+    Real: num_traits::Float,
+{
+    let n = crate::vec3::orthogonalize(t, n);
+    let n = if n.squared_norm() < Real::epsilon() {
+        crate::vec3::basis_xy_from_basis_z(t).0
+    } else {
+        n.normalize()
+    };
+    [*t, n, t.cross(&n)]
+}
+
+/// Frenet frames at the vertices where the normal points to the center of the osculating circle.
+/// where the frame is not defined (e.g., straight part), the normal of the neighboring frame is used.
+#[cfg(feature = "std")]
+pub fn vtx2frame_frenet<Real>(vtx2xyz: &[Real]) -> Vec<[[Real; 3]; 3]>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    let vtx2tangent = vtx2tangent(vtx2xyz);
+    let vtx2normal: Vec<Option<[Real; 3]>> = (0..num_vtx)
+        .map(|i_vtx| {
+            if i_vtx == 0 || i_vtx + 1 == num_vtx {
+                return None;
+            }
+            let p0 = vtx(vtx2xyz, i_vtx - 1);
+            let p1 = vtx(vtx2xyz, i_vtx);
+            let p2 = vtx(vtx2xyz, i_vtx + 1);
+            let e0 = p1.sub(p0).normalize();
+            let e1 = p2.sub(p1).normalize();
+            let k = e1.sub(&e0); // points to the center of the osculating circle
+            if k.norm() < Real::epsilon().sqrt() {
+                return None;
+            }
+            Some(k)
+        })
+        .collect();
+    let Some(i_first) = vtx2normal.iter().position(|n| n.is_some()) else {
+        // straight polyline
+        return (0..num_vtx)
+            .map(|i_vtx| {
+                let t = vtx(&vtx2tangent, i_vtx);
+                let (n, b) = crate::vec3::basis_xy_from_basis_z(t);
+                [*t, n, b]
+            })
+            .collect();
+    };
+    let mut res = Vec::with_capacity(num_vtx);
+    let mut n_prev = vtx2normal[i_first].unwrap();
+    for (i_vtx, n) in vtx2normal.iter().enumerate() {
+        let t = vtx(&vtx2tangent, i_vtx);
+        let frame = frame_from_tangent_normal(t, &n.unwrap_or(n_prev));
+        n_prev = frame[1];
+        res.push(frame);
+    }
+    res
+}
+
+/// frames at the vertices computed by transporting the initial normal `n0`
+/// along the vertex tangents with the minimum rotation (Bishop frame)
+#[cfg(feature = "std")]
+pub fn vtx2frame_parallel_transport<Real>(vtx2xyz: &[Real], n0: &[Real; 3]) -> Vec<[[Real; 3]; 3]>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    let vtx2tangent = vtx2tangent(vtx2xyz);
+    let mut res = Vec::with_capacity(num_vtx);
+    res.push(frame_from_tangent_normal(vtx(&vtx2tangent, 0), n0));
+    for i_vtx in 1..num_vtx {
+        let [t0, n0, _] = res[i_vtx - 1];
+        let t1 = vtx(&vtx2tangent, i_vtx);
+        let n1 = parallel_transport(&n0, &t0, t1);
+        res.push(frame_from_tangent_normal(t1, &n1));
+    }
+    res
+}
+
+/// rotation minimizing frames at the vertices computed by the double reflection method
+/// * `n0` - normal at the first vertex
+///
+/// Wang et al. "Computation of rotation minimizing frames" ACM TOG 2008
+#[cfg(feature = "std")]
+pub fn vtx2frame_rotation_minimizing<Real>(vtx2xyz: &[Real], n0: &[Real; 3]) -> Vec<[[Real; 3]; 3]>
+where
+    Real: num_traits::Float,
+{
+    let num_vtx = vtx2xyz.len() / 3;
+    let two = Real::one() + Real::one();
+    let vtx2tangent = vtx2tangent(vtx2xyz);
+    let mut res = Vec::with_capacity(num_vtx);
+    res.push(frame_from_tangent_normal(vtx(&vtx2tangent, 0), n0));
+    for i_vtx in 1..num_vtx {
+        let [t0, r0, _] = res[i_vtx - 1];
+        let t1 = vtx(&vtx2tangent, i_vtx);
+        // reflection by the bisecting plane of the two vertices
+        let v1 = vtx(vtx2xyz, i_vtx).sub(vtx(vtx2xyz, i_vtx - 1));
+        let c1 = v1.dot(&v1);
+        let (r_l, t_l) = if c1.is_zero() {
+            (r0, t0)
+        } else {
+            (
+                crate::vec3::axpy(-two * v1.dot(&r0) / c1, &v1, &r0),
+                crate::vec3::axpy(-two * v1.dot(&t0) / c1, &v1, &t0),
+            )
+        };
+        // reflection to align the tangents
+        let v2 = t1.sub(&t_l);
+        let c2 = v2.dot(&v2);
+        let r1 = if c2 < Real::epsilon() {
+            r_l
+        } else {
+            crate::vec3::axpy(-two * v2.dot(&r_l) / c2, &v2, &r_l)
+        };
+        res.push(frame_from_tangent_normal(t1, &r1));
+    }
+    res
+}
+
+#[cfg(test)]
+fn helix(radius: f64, pitch: f64, num_vtx: usize, theta_max: f64) -> Vec<f64> {
+    (0..num_vtx)
+        .flat_map(|i_vtx| {
+            let theta = theta_max * i_vtx as f64 / (num_vtx - 1) as f64;
+            [radius * theta.cos(), radius * theta.sin(), pitch * theta]
+        })
+        .collect()
+}
+
+#[test]
+fn test_curvature_torsion_frenet() {
+    let (radius, pitch) = (1.3, 0.4);
+    let vtx2xyz = helix(radius, pitch, 201, 4.);
+    let kappas = vtx2curvature(&vtx2xyz);
+    let taus = vtx2torsion(&vtx2xyz);
+    let frames = vtx2frame_frenet(&vtx2xyz);
+    let denom = radius * radius + pitch * pitch;
+    for i_vtx in 2..199 {
+        assert!((kappas[i_vtx] - radius / denom).abs() < 1.0e-3);
+        assert!((taus[i_vtx] - pitch / denom).abs() < 1.0e-3);
+        let p = vtx(&vtx2xyz, i_vtx);
+        let n_ana = crate::vec3::normalize(&[-p[0], -p[1], 0.]);
+        assert!((frames[i_vtx][1].sub(&n_ana)).norm() < 1.0e-3);
+    }
+    // circle in a plane has zero torsion
+    let vtx2xyz = helix(1.0, 0.0, 50, 3.);
+    for (kappa, tau) in vtx2curvature(&vtx2xyz)
+        .iter()
+        .zip(vtx2torsion(&vtx2xyz).iter())
+        .take(49)
+        .skip(1)
+    {
+        assert!((kappa - 1.).abs() < 1.0e-10);
+        assert!(tau.abs() < 1.0e-10);
+    }
+    // straight line
+    let frames = vtx2frame_frenet(&[0f64, 0., 0., 1., 0., 0., 2., 0., 0.]);
+    for frame in frames.iter() {
+        assert!(
+            (crate::vec3::scalar_triple_product(&frame[0], &frame[1], &frame[2]) - 1.).abs()
+                < 1.0e-10
+        );
+    }
+}
+
+#[test]
+fn test_tangent_duplicated_vertex() {
+    let vtx2xyz0 = [0f64, 0., 0., 1., 0.2, 0., 2., -0.3, 0.1, 2.5, 0.5, 0.];
+    let t0 = vtx2tangent(&vtx2xyz0);
+    // the first, the second and the last vertices are repeated
+    let vtx2xyz1 = [
+        0f64, 0., 0., 0., 0., 0., 1., 0.2, 0., 1., 0.2, 0., 2., -0.3, 0.1, 2.5, 0.5, 0., 2.5, 0.5,
+        0.,
+    ];
+    let t1 = vtx2tangent(&vtx2xyz1);
+    for (i0, i1) in [(0, 0), (0, 1), (1, 3), (2, 4), (3, 5), (3, 6)] {
+        assert!(vtx(&t0, i0).sub(vtx(&t1, i1)).norm() < 1.0e-10);
+    }
+    // the repeated vertex takes the direction of the incoming edge
+    assert!(vtx(&t1, 2).sub(&[1., 0.2, 0.].normalize()).norm() < 1.0e-10);
+    for frame in vtx2frame_rotation_minimizing(&vtx2xyz1, &[0., 0., 1.]) {
+        assert!(frame.iter().flatten().all(|v| v.is_finite()));
+    }
+    for frame in vtx2frame_parallel_transport(&vtx2xyz1, &[0., 0., 1.]) {
+        assert!(frame.iter().flatten().all(|v| v.is_finite()));
+    }
+    assert_eq!(vtx2tangent(&[1f64, 2., 3., 1., 2., 3.]), [0.; 6]);
+}
+
+#[test]
+fn test_rotation_minimizing_frame() {
+    let vtx2xyz = helix(1.0, 0.3, 401, 6.);
+    let t0 = vtx2tangent(&vtx2xyz);
+    let n0 = crate::vec3::basis_xy_from_basis_z(vtx(&t0, 0)).0;
+    let frames0 = vtx2frame_parallel_transport(&vtx2xyz, &n0);
+    let frames1 = vtx2frame_rotation_minimizing(&vtx2xyz, &n0);
+    for (f0, f1) in frames0.iter().zip(frames1.iter()) {
+        for f in [f0, f1] {
+            assert!((crate::vec3::scalar_triple_product(&f[0], &f[1], &f[2]) - 1.).abs() < 1.0e-10);
+            assert!(f[0].dot(&f[1]).abs() < 1.0e-10);
+        }
+        // the two methods converge to the same frame
+        assert!(f0[1].sub(&f1[1]).norm() < 1.0e-3);
+    }
+    // no twist: the normal does not rotate around the tangent
+    for i_vtx in 0..400 {
+        let [_, _, b_a] = frames1[i_vtx];
+        let [_, n_b, _] = frames1[i_vtx + 1];
+        assert!(n_b.dot(&b_a).abs() < 1.0e-3);
+    }
+    // planar curve keeps the normal perpendicular to the plane
+    let vtx2xyz = [0f64, 0., 0., 1., 0.2, 0., 2., -0.3, 0., 2.5, 0.5, 0.];
+    for frame in vtx2frame_rotation_minimizing(&vtx2xyz, &[0., 0., 1.]) {
+        assert!((frame[1][2] - 1.).abs() < 1.0e-10);
+    }
+    for frame in vtx2frame_parallel_transport(&vtx2xyz, &[0., 0., 1.]) {
+        assert!((frame[1][2] - 1.).abs() < 1.0e-10);
+    }
+}
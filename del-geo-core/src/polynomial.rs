@@ -0,0 +1,351 @@
+//! methods for univariate polynomial
+//! the polynomial `c[0] + c[1]*x + c[2]*x^2 + ...` is stored as the coefficient slice `c`
+
+/// evaluate the polynomial at `x` using Horner's method
+pub fn eval<T>(c: &[T], x: T) -> T
+where
+    T: num_traits::Float,
+{
+    c.iter().rev().fold(T::zero(), |acc, &ci| acc * x + ci)
+}
+
+/// degree of the polynomial ignoring the leading coefficients whose magnitude is `<= eps`
+/// # return
+/// `None` if all the coefficients are (nearly) zero
+pub fn degree<T>(c: &[T], eps: T) -> Option<usize>
+where
+    T: num_traits::Float,
+{
+    c.iter().rposition(|&ci| ci.abs() > eps)
+}
+
+/// remove the leading coefficients whose magnitude is `<= eps`
+pub fn trim<T>(c: &[T], eps: T) -> &[T]
+where
+    T: num_traits::Float,
+{
+    match degree(c, eps) {
+        Some(n) => &c[..n + 1],
+        None => &c[..0],
+    }
+}
+
+#[cfg(feature = "std")]
+pub fn add<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    let n = a.len().max(b.len());
+    (0..n)
+        .map(|i| {
+            let ai = a.get(i).copied().unwrap_or(T::zero());
+            let bi = b.get(i).copied().unwrap_or(T::zero());
+            ai + bi
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+pub fn sub<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    let n = a.len().max(b.len());
+    (0..n)
+        .map(|i| {
+            let ai = a.get(i).copied().unwrap_or(T::zero());
+            let bi = b.get(i).copied().unwrap_or(T::zero());
+            ai - bi
+        })
+        .collect()
+}
+
+#[cfg(feature = "std")]
+pub fn scale<T>(a: &[T], s: T) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    a.iter().map(|&ai| ai * s).collect()
+}
+
+/// product of two polynomials
+#[cfg(feature = "std")]
+pub fn mult<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    if a.is_empty() || b.is_empty() {
+        return vec![];
+    }
+    let mut c = vec![T::zero(); a.len() + b.len() - 1];
+    for (i, &ai) in a.iter().enumerate() {
+        for (j, &bj) in b.iter().enumerate() {
+            c[i + j] = c[i + j] + ai * bj;
+        }
+    }
+    c
+}
+
+/// derivative of the polynomial
+#[cfg(feature = "std")]
+pub fn derivative<T>(c: &[T]) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    let mut k = T::zero();
+    c.iter()
+        .skip(1)
+        .map(|&ci| {
+            k = k + T::one();
+            ci * k
+        })
+        .collect()
+}
+
+/// composition of two polynomials `a(b(x))`
+#[cfg(feature = "std")]
+pub fn compose<T>(a: &[T], b: &[T]) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    let mut c: Vec<T> = vec![];
+    for &ai in a.iter().rev() {
+        c = add(&mult(&c, b), &[ai]);
+    }
+    c
+}
+
+/// polynomial division `a = q * b + r`
+/// # return
+/// `(q, r)`. `None` if `b` is (nearly) zero polynomial
+#[cfg(feature = "std")]
+pub fn div_rem<T>(a: &[T], b: &[T], eps: T) -> Option<(Vec<T>, Vec<T>)>
+where
+    T: num_traits::Float,
+{
+    let b = trim(b, eps);
+    let nb = b.len().checked_sub(1)?;
+    let mut r = trim(a, eps).to_vec();
+    if r.len() <= nb {
+        return Some((vec![], r));
+    }
+    let mut q = vec![T::zero(); r.len() - nb];
+    let inv_lead = T::one() / b[nb];
+    for k in (0..q.len()).rev() {
+        let coeff = r[k + nb] * inv_lead;
+        q[k] = coeff;
+        for (j, &bj) in b.iter().enumerate() {
+            r[k + j] = r[k + j] - coeff * bj;
+        }
+    }
+    r.truncate(nb);
+    Some((q, r))
+}
+
+#[test]
+fn test_arithmetic() {
+    let a = [1f64, -2., 3.];
+    let b = [0.5, 1.5];
+    let ab = mult(&a, &b);
+    for x in [-1.3, 0.0, 0.4, 2.1] {
+        assert!((eval(&ab, x) - eval(&a, x) * eval(&b, x)).abs() < 1.0e-12);
+        let ab = compose(&a, &b);
+        assert!((eval(&ab, x) - eval(&a, eval(&b, x))).abs() < 1.0e-12);
+        let eps = 1.0e-6;
+        let diff = (eval(&a, x + eps) - eval(&a, x - eps)) / (2. * eps);
+        assert!((eval(&derivative(&a), x) - diff).abs() < 1.0e-8);
+    }
+    let (q, r) = div_rem(&ab, &b, 0.).unwrap();
+    assert_eq!(q.len(), 3);
+    assert_eq!(r.len(), 1);
+    for i in 0..3 {
+        assert!((q[i] - a[i]).abs() < 1.0e-12);
+    }
+    assert!(r[0].abs() < 1.0e-12);
+    assert_eq!(degree(&[1., 0., 0.], 0.), Some(0));
+    assert_eq!(degree(&[0., 0.], 0.), None);
+}
+
+/// Sturm sequence `p0 = p, p1 = p', p_{k+1} = -rem(p_{k-1}, p_k)`
+///
+/// coefficients whose magnitude is smaller than the relative tolerance are treated as zero
+#[cfg(feature = "std")]
+pub fn sturm_sequence<T>(c: &[T]) -> Vec<Vec<T>>
+where
+    T: num_traits::Float,
+{
+    let max_abs = c.iter().fold(T::zero(), |m, &ci| m.max(ci.abs()));
+    let tol = |p: &[T]| {
+        let m = p.iter().fold(T::zero(), |m, &ci| m.max(ci.abs()));
+        m.max(max_abs) * T::epsilon() * T::from(64).unwrap()
+    };
+    let p0 = trim(c, tol(c)).to_vec();
+    if p0.is_empty() {
+        return vec![];
+    }
+    let p1 = derivative(&p0);
+    let p1 = trim(&p1, tol(&p1)).to_vec();
+    let mut seq = vec![p0];
+    if p1.is_empty() {
+        return seq;
+    }
+    seq.push(p1);
+    loop {
+        let n = seq.len();
+        let Some((_, r)) = div_rem(&seq[n - 2], &seq[n - 1], T::zero()) else {
+            break;
+        };
+        let r = scale(&r, -T::one());
+        let r = trim(&r, tol(&seq[n - 2])).to_vec();
+        if r.is_empty() {
+            break;
+        }
+        seq.push(r);
+    }
+    seq
+}
+
+/// number of sign changes of the Sturm sequence evaluated at `x`
+#[cfg(feature = "std")]
+pub fn num_sign_changes_sturm<T>(seq: &[Vec<T>], x: T) -> usize
+where
+    T: num_traits::Float,
+{
+    let mut cnt = 0;
+    let mut prev = T::zero();
+    for p in seq {
+        let v = eval(p, x);
+        if v == T::zero() {
+            continue;
+        }
+        if prev != T::zero() && (prev < T::zero()) != (v < T::zero()) {
+            cnt += 1;
+        }
+        prev = v;
+    }
+    cnt
+}
+
+/// number of distinct real roots in the half-open range `(a, b]`
+#[cfg(feature = "std")]
+pub fn num_roots_in_range_sturm<T>(seq: &[Vec<T>], a: T, b: T) -> usize
+where
+    T: num_traits::Float,
+{
+    let va = num_sign_changes_sturm(seq, a);
+    let vb = num_sign_changes_sturm(seq, b);
+    va.saturating_sub(vb)
+}
+
+#[test]
+fn test_sturm() {
+    // (x-0.2)(x-0.5)(x-1.5)(x+1)
+    let c = mult(
+        &mult(&[-0.2, 1.], &[-0.5, 1.]),
+        &mult(&[-1.5, 1.], &[1., 1.]),
+    );
+    let seq = sturm_sequence(&c);
+    assert_eq!(num_roots_in_range_sturm(&seq, -2., 2.), 4);
+    assert_eq!(num_roots_in_range_sturm(&seq, 0., 1.), 2);
+    assert_eq!(num_roots_in_range_sturm(&seq, 0.3, 1.), 1);
+    assert_eq!(num_roots_in_range_sturm(&seq, 0.6, 1.4), 0);
+    // x^2 + 1
+    let seq = sturm_sequence(&[1., 0., 1.]);
+    assert_eq!(num_roots_in_range_sturm(&seq, -10., 10.), 0);
+}
+
+/// coefficients in the Bernstein basis of degree `c.len()-1` over the range `[a, b]`
+#[cfg(feature = "std")]
+pub fn to_bernstein<T>(c: &[T], a: T, b: T) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    let p = compose(c, &[a, b - a]); // polynomial in s where x = a + (b-a)*s
+    let n = p.len();
+    if n == 0 {
+        return vec![];
+    }
+    let deg = n - 1;
+    // binom[i][j] = i choose j
+    let mut binom = vec![vec![T::one(); n]; n];
+    for i in 1..n {
+        for j in 1..i {
+            binom[i][j] = binom[i - 1][j - 1] + binom[i - 1][j];
+        }
+    }
+    (0..n)
+        .map(|i| (0..=i).fold(T::zero(), |acc, j| acc + binom[i][j] / binom[deg][j] * p[j]))
+        .collect()
+}
+
+/// split the Bernstein coefficients at the middle of the range by de Casteljau's algorithm
+/// # return
+/// `(left, right)` coefficients
+#[cfg(feature = "std")]
+pub fn split_bernstein<T>(b: &[T]) -> (Vec<T>, Vec<T>)
+where
+    T: num_traits::Float,
+{
+    let half = T::one() / (T::one() + T::one());
+    let mut w = b.to_vec();
+    let n = w.len();
+    let mut left = Vec::with_capacity(n);
+    let mut right = Vec::with_capacity(n);
+    for k in 0..n {
+        left.push(w[0]);
+        right.push(w[n - 1 - k]);
+        for i in 0..n - 1 - k {
+            w[i] = (w[i] + w[i + 1]) * half;
+        }
+    }
+    right.reverse();
+    (left, right)
+}
+
+/// number of sign changes in the Bernstein coefficients ignoring zeros.
+/// upper bound of the number of roots (Descartes' rule of signs)
+pub fn num_sign_changes<T>(b: &[T]) -> usize
+where
+    T: num_traits::Float,
+{
+    let mut cnt = 0;
+    let mut prev = T::zero();
+    for &v in b {
+        if v == T::zero() {
+            continue;
+        }
+        if prev != T::zero() && (prev < T::zero()) != (v < T::zero()) {
+            cnt += 1;
+        }
+        prev = v;
+    }
+    cnt
+}
+
+#[test]
+fn test_bernstein() {
+    let c = [0.3, -1.2, 0.7, 2.0];
+    let (a, b) = (-0.5, 1.5);
+    let bern = to_bernstein(&c, a, b);
+    let eval_bern = |bern: &[f64], s: f64| {
+        // de Casteljau
+        let mut w = bern.to_vec();
+        for k in (1..w.len()).rev() {
+            for i in 0..k {
+                w[i] = w[i] * (1. - s) + w[i + 1] * s;
+            }
+        }
+        w[0]
+    };
+    for s in [0., 0.25, 0.6, 1.] {
+        let x = a + (b - a) * s;
+        assert!((eval(&c, x) - eval_bern(&bern, s)).abs() < 1.0e-12);
+    }
+    let (l, r) = split_bernstein(&bern);
+    for s in [0., 0.3, 1.] {
+        let xl = a + (b - a) * 0.5 * s;
+        let xr = a + (b - a) * (0.5 + 0.5 * s);
+        assert!((eval(&c, xl) - eval_bern(&l, s)).abs() < 1.0e-12);
+        assert!((eval(&c, xr) - eval_bern(&r, s)).abs() < 1.0e-12);
+    }
+}
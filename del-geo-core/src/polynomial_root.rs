@@ -4,12 +4,21 @@ use num_traits::AsPrimitive;
 
 /// find root of quadratic function
 /// f(x) = c0 + c1*x + c2*x^2
+///
+/// if `c2 == 0`, the root of the linear function is returned twice
 pub fn quadratic_root<T>(c0: T, c1: T, c2: T) -> Option<[T; 2]>
 where
-    T: num_traits::Float + 'static + Copy + std::fmt::Debug,
+    T: num_traits::Float + 'static + Copy + core::fmt::Debug,
     i64: AsPrimitive<T>,
 {
-    assert_ne!(c2, T::zero());
+    if c2 == T::zero() {
+        // linear function
+        if c1 == T::zero() {
+            return None;
+        }
+        let x = -c0 / c1;
+        return Some([x, x]);
+    }
     let det = c1 * c1 - 4.as_() * c2 * c0;
     if det < T::zero() {
         return None;
@@ -25,11 +34,6 @@ where
         x2
     };
     let (x1, x2) = if x1 < x2 { (x1, x2) } else { (x2, x1) };
-    if x1 <= x2 {
-    } else {
-        dbg!(c0, c1, c2, det, tmp, x1, x2);
-    }
-    assert!(x1 <= x2);
     Some([x1, x2])
 }
 
@@ -55,16 +59,30 @@ fn test_quadratic_root() {
         let res = quadratic_root(c0, c1, c2);
         assert!(res.is_none());
     }
+    assert_eq!(quadratic_root(1., -2., 0.), Some([0.5, 0.5]));
+    assert_eq!(quadratic_root(1., 0., 0.), None);
 }
 
-/// f(x) = c0 + c1*x + c2*x^2 + c3*x^3
-pub fn cubic_roots_in_range_zero_to_t<T>(c0: T, c1: T, c2: T, c3: T, t: T, epsilon: T) -> Vec<T>
+/// roots of f(x) = c0 + c1*x + c2*x^2 + c3*x^3 in the range `[0, t]` without allocation.
+/// the first `n` entries of `roots` are the roots where `(roots, n)` is returned
+pub fn cubic_roots_in_range_zero_to_t_fixed<T>(
+    c0: T,
+    c1: T,
+    c2: T,
+    c3: T,
+    t: T,
+    epsilon: T,
+) -> ([T; 3], usize)
 where
-    T: num_traits::Float + 'static + Copy + std::fmt::Debug + std::fmt::Display,
+    T: num_traits::Float + 'static + Copy + core::fmt::Debug + core::fmt::Display,
     i64: AsPrimitive<T>,
 {
+    fn push<T>(result: &mut ([T; 3], usize), r: T) {
+        result.0[result.1] = r;
+        result.1 += 1;
+    }
     assert!(t > T::zero());
-    let mut result = vec![];
+    let mut result = ([T::zero(); 3], 0);
     let eval_f = |r| ((c3 * r + c2) * r + c1) * r + c0;
     let f0 = c0;
     let ft = eval_f(t);
@@ -76,14 +94,14 @@ where
                 // lesser than linear function
                 // constant function
                 if c0.abs() < T::epsilon() {
-                    result.push(T::zero());
+                    push(&mut result, T::zero());
                 }
                 result
             } else {
                 // linear function
                 if (f0 <= T::zero() && ft >= T::zero()) || (f0 >= T::zero() && ft <= T::zero()) {
                     assert_ne!(f0, ft);
-                    result.push(f0 / (f0 - ft));
+                    push(&mut result, f0 / (f0 - ft));
                 }
                 result
             }
@@ -92,10 +110,10 @@ where
             if let Some([e0, e1]) = quadratic_root(c0, c1, c2) {
                 let (e0, e1) = if e0 < e1 { (e0, e1) } else { (e1, e0) };
                 if e0 >= T::zero() && e0 <= t {
-                    result.push(e0);
+                    push(&mut result, e0);
                 }
                 if e1 >= T::zero() && e1 <= t {
-                    result.push(e1);
+                    push(&mut result, e1);
                 }
             }
             result
@@ -112,7 +130,10 @@ where
         if xs == xe {
             return if fs == T::zero() { Some(xs) } else { None };
         }
-        assert_ne!(fs, fe, "hoge {} {} {} {} {} {}", xs, xe, c0, c1, c2, c3);
+        if fs == fe {
+            // both ends are roots
+            return Some(xs);
+        }
         let mut r = (fs * xe - fe * xs) / (fs - fe);
         assert!(r >= T::zero() && r <= t);
         for _i in 0..20 {
@@ -137,7 +158,7 @@ where
             let a1 = t.min(e0);
             let fa1 = eval_f(a1);
             if let Some(r) = newton(T::zero(), a1, f0, fa1) {
-                result.push(r);
+                push(&mut result, r);
             }
         }
         if e0 <= t && T::zero() <= e1 {
@@ -148,7 +169,7 @@ where
             let fa1 = eval_f(a1);
             assert!(a0 <= a1);
             if let Some(r) = newton(a0, a1, fa0, fa1) {
-                result.push(r);
+                push(&mut result, r);
             }
         }
         if e1 <= t {
@@ -156,18 +177,29 @@ where
             let a0 = T::zero().max(e1);
             let fa0 = eval_f(a0);
             if let Some(r) = newton(a0, t, fa0, ft) {
-                result.push(r);
+                push(&mut result, r);
             }
         }
     } else {
         // monotonic
         if let Some(r) = newton(T::zero(), t, f0, ft) {
-            result.push(r);
+            push(&mut result, r);
         }
     }
     result
 }
 
+/// f(x) = c0 + c1*x + c2*x^2 + c3*x^3
+#[cfg(feature = "std")]
+pub fn cubic_roots_in_range_zero_to_t<T>(c0: T, c1: T, c2: T, c3: T, t: T, epsilon: T) -> Vec<T>
+where
+    T: num_traits::Float + 'static + Copy + core::fmt::Debug + core::fmt::Display,
+    i64: AsPrimitive<T>,
+{
+    let (roots, n) = cubic_roots_in_range_zero_to_t_fixed(c0, c1, c2, c3, t, epsilon);
+    roots[..n].to_vec()
+}
+
 #[test]
 fn test_cubic_root() {
     use rand::Rng;
@@ -179,9 +211,448 @@ fn test_cubic_root() {
         let c2: f64 = 4. * rng.gen::<f64>() - 2.;
         let c3: f64 = 4. * rng.gen::<f64>() - 2.;
         let list_time = cubic_roots_in_range_zero_to_t(c0, c1, c2, c3, 1.0, eps);
+        let (roots, n) = cubic_roots_in_range_zero_to_t_fixed(c0, c1, c2, c3, 1.0, eps);
+        assert_eq!(list_time, roots[..n]);
         for t in list_time {
             let fr: f64 = c0 + c1 * t + c2 * t * t + c3 * t * t * t;
             assert!(fr.abs() < eps);
         }
     }
 }
+
+/// all the real roots of the quadratic function in ascending order
+/// f(x) = c0 + c1*x + c2*x^2
+///
+/// the function is treated as linear if `c2 == 0`
+#[cfg(feature = "std")]
+pub fn quadratic_roots<T>(c0: T, c1: T, c2: T) -> Vec<T>
+where
+    T: num_traits::Float + 'static + Copy + core::fmt::Debug,
+    i64: AsPrimitive<T>,
+{
+    if c2 == T::zero() {
+        if c1 == T::zero() {
+            return vec![];
+        }
+        return vec![-c0 / c1];
+    }
+    match quadratic_root(c0, c1, c2) {
+        Some(xs) => xs.to_vec(),
+        None => vec![],
+    }
+}
+
+/// improve the root by Newton's method as long as the residual decreases
+#[cfg(feature = "std")]
+fn polish_root_newton<T>(c: &[T], x: T) -> T
+where
+    T: num_traits::Float,
+{
+    use crate::polynomial::{derivative, eval};
+    let dc = derivative(c);
+    let mut x = x;
+    let mut fx = eval(c, x).abs();
+    for _ in 0..8 {
+        if fx == T::zero() {
+            break;
+        }
+        let dfx = eval(&dc, x);
+        if dfx == T::zero() {
+            break;
+        }
+        let x1 = x - eval(c, x) / dfx;
+        let fx1 = eval(c, x1).abs();
+        if fx1 >= fx || !fx1.is_finite() {
+            break;
+        }
+        x = x1;
+        fx = fx1;
+    }
+    x
+}
+
+/// all the real roots of the cubic function in ascending order using closed-form solution
+/// f(x) = c0 + c1*x + c2*x^2 + c3*x^3
+///
+/// the function is treated as quadratic if `c3` is negligible compared to the other coefficients
+#[cfg(feature = "std")]
+pub fn cubic_roots<T>(c0: T, c1: T, c2: T, c3: T) -> Vec<T>
+where
+    T: num_traits::Float + 'static + Copy + core::fmt::Debug,
+    i64: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let max_abs = c0.abs().max(c1.abs()).max(c2.abs());
+    if c3.abs() <= T::epsilon() * max_abs || c3 == T::zero() {
+        return quadratic_roots(c0, c1, c2);
+    }
+    let two: T = 2.as_();
+    let three: T = 3.as_();
+    // x^3 + a*x^2 + b*x + c
+    let (a, b, c) = (c2 / c3, c1 / c3, c0 / c3);
+    // depressed cubic t^3 + p*t + q where x = t - a/3
+    let p = b - a * a / three;
+    let q = two * a * a * a / 27.as_() - a * b / three + c;
+    let disc = q * q / 4.as_() + p * p * p / 27.as_();
+    let ts = if disc > T::zero() {
+        // one real root
+        let sgnq = if q < T::zero() { -T::one() } else { T::one() };
+        let u = (-q / two - sgnq * disc.sqrt()).cbrt();
+        let v = if u == T::zero() {
+            T::zero()
+        } else {
+            -p / (three * u)
+        };
+        vec![u + v]
+    } else if p == T::zero() {
+        // triple root
+        vec![T::zero(); 3]
+    } else if disc == T::zero() {
+        // double root
+        vec![
+            three * q / p,
+            -three * q / (two * p),
+            -three * q / (two * p),
+        ]
+    } else {
+        // three real roots
+        let r = two * (-p / three).sqrt();
+        let cos_arg = (three * q / (two * p) * (-three / p).sqrt()).clamp(-T::one(), T::one());
+        let phi = cos_arg.acos() / three;
+        let two_pi_third: T = (2f64 * core::f64::consts::PI / 3f64).as_();
+        (0..3)
+            .map(|k| r * (phi - two_pi_third * (k as i64).as_()).cos())
+            .collect()
+    };
+    let coeff = [c0, c1, c2, c3];
+    let mut xs: Vec<T> = ts
+        .iter()
+        .map(|&t| polish_root_newton(&coeff, t - a / three))
+        .collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    xs
+}
+
+#[test]
+fn test_cubic_roots() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let mut r: [f64; 3] = core::array::from_fn(|_| 4. * rng.gen::<f64>() - 2.);
+        r.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let k: f64 = 4. * rng.gen::<f64>() - 2.;
+        let c3 = k;
+        let c2 = -k * (r[0] + r[1] + r[2]);
+        let c1 = k * (r[0] * r[1] + r[1] * r[2] + r[2] * r[0]);
+        let c0 = -k * r[0] * r[1] * r[2];
+        let xs = cubic_roots(c0, c1, c2, c3);
+        if r[1] - r[0] < 1.0e-3 || r[2] - r[1] < 1.0e-3 {
+            continue;
+        }
+        assert_eq!(xs.len(), 3);
+        for i in 0..3 {
+            assert!((xs[i] - r[i]).abs() < 1.0e-6, "{:?} {:?}", xs, r);
+        }
+        // one real root
+        let c0 = -k * r[0] * (1. + r[1] * r[1]);
+        let c1 = k * (1. + r[1] * r[1]);
+        let c2 = -k * r[0];
+        let xs = cubic_roots(c0, c1, c2, k);
+        assert_eq!(xs.len(), 1);
+        assert!((xs[0] - r[0]).abs() < 1.0e-8);
+    }
+    assert_eq!(cubic_roots(-1., 1., 0., 0.), vec![1.]);
+    let xs = cubic_roots(0., 0., 0., 2.);
+    assert_eq!(xs, vec![0., 0., 0.]);
+}
+
+/// all the real roots of the quartic function in ascending order using Ferrari's method
+/// f(x) = c0 + c1*x + c2*x^2 + c3*x^3 + c4*x^4
+///
+/// the function is treated as cubic if `c4` is negligible compared to the other coefficients
+#[cfg(feature = "std")]
+pub fn quartic_roots<T>(c0: T, c1: T, c2: T, c3: T, c4: T) -> Vec<T>
+where
+    T: num_traits::Float + 'static + Copy + core::fmt::Debug,
+    i64: AsPrimitive<T>,
+    f64: AsPrimitive<T>,
+{
+    let max_abs = c0.abs().max(c1.abs()).max(c2.abs()).max(c3.abs());
+    if c4.abs() <= T::epsilon() * max_abs || c4 == T::zero() {
+        return cubic_roots(c0, c1, c2, c3);
+    }
+    let two: T = 2.as_();
+    // x^4 + a*x^3 + b*x^2 + c*x + d
+    let (a, b, c, d) = (c3 / c4, c2 / c4, c1 / c4, c0 / c4);
+    // depressed quartic y^4 + p*y^2 + q*y + r where x = y - a/4
+    let aa = a * a;
+    let p = b - 3.as_() * aa / 8.as_();
+    let q = c - a * b / two + aa * a / 8.as_();
+    let r = d - a * c / 4.as_() + aa * b / 16.as_() - 3.as_() * aa * aa / 256.as_();
+    // resolvent cubic m^3 + p*m^2 + (p^2/4 - r)*m - q^2/8 = 0
+    let m = if q == T::zero() {
+        None
+    } else {
+        cubic_roots(-q * q / 8.as_(), p * p / 4.as_() - r, p, T::one())
+            .last()
+            .copied()
+            .filter(|&m| m > T::zero())
+    };
+    let mut ys = vec![];
+    if let Some(m) = m {
+        let s = (two * m).sqrt();
+        let h = p / two + m;
+        ys.extend(quadratic_roots(h + q / (two * s), -s, T::one()));
+        ys.extend(quadratic_roots(h - q / (two * s), s, T::one()));
+    } else {
+        // bi-quadratic y^4 + p*y^2 + r = 0
+        for z in quadratic_roots(r, p, T::one()) {
+            if z < T::zero() {
+                continue;
+            }
+            let y = z.sqrt();
+            ys.push(y);
+            ys.push(-y);
+        }
+    }
+    let coeff = [c0, c1, c2, c3, c4];
+    let mut xs: Vec<T> = ys
+        .iter()
+        .map(|&y| polish_root_newton(&coeff, y - a / 4.as_()))
+        .collect();
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    xs
+}
+
+#[test]
+fn test_quartic_roots() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..1000 {
+        let mut r: [f64; 4] = core::array::from_fn(|_| 4. * rng.gen::<f64>() - 2.);
+        r.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        if (0..3).any(|i| r[i + 1] - r[i] < 1.0e-2) {
+            continue;
+        }
+        let k: f64 = if rng.gen::<bool>() { 1. } else { -1. } * (0.5 + rng.gen::<f64>());
+        let c = crate::polynomial::mult(
+            &crate::polynomial::mult(&[-r[0], 1.], &[-r[1], 1.]),
+            &crate::polynomial::mult(&[-r[2], 1.], &[-r[3] * k, k]),
+        );
+        let xs = quartic_roots(c[0], c[1], c[2], c[3], c[4]);
+        assert_eq!(xs.len(), 4, "{:?} {:?}", xs, r);
+        for i in 0..4 {
+            assert!((xs[i] - r[i]).abs() < 1.0e-5, "{:?} {:?}", xs, r);
+        }
+        // two real roots (x-r0)(x-r1)(x^2+1)
+        let c = crate::polynomial::mult(
+            &crate::polynomial::mult(&[-r[0], 1.], &[-r[1], 1.]),
+            &[1., 0., 1.],
+        );
+        let xs = quartic_roots(c[0], c[1], c[2], c[3], c[4]);
+        assert_eq!(xs.len(), 2);
+        assert!((xs[0] - r[0]).abs() < 1.0e-6 && (xs[1] - r[1]).abs() < 1.0e-6);
+    }
+    // bi-quadratic (x^2-1)(x^2-4)
+    let xs = quartic_roots(4., 0., -5., 0., 1.);
+    assert_eq!(xs, vec![-2., -1., 1., 2.]);
+    // degenerate leading coefficient
+    assert_eq!(quartic_roots(-1., 1., 0., 0., 0.), vec![1.]);
+}
+
+/// find the root in `[lo, hi]` by bisection where the signs of `c(lo)` and `c(hi)` differ
+#[cfg(feature = "std")]
+fn root_bisection<T>(c: &[T], lo: T, hi: T, epsilon: T) -> T
+where
+    T: num_traits::Float,
+{
+    use crate::polynomial::eval;
+    let half = T::one() / (T::one() + T::one());
+    let (mut lo, mut hi) = (lo, hi);
+    let neg_lo = eval(c, lo) < T::zero();
+    while hi - lo > epsilon {
+        let mid = (lo + hi) * half;
+        if mid <= lo || mid >= hi {
+            break;
+        }
+        let fmid = eval(c, mid);
+        if fmid == T::zero() {
+            return mid;
+        }
+        if (fmid < T::zero()) == neg_lo {
+            lo = mid;
+        } else {
+            hi = mid;
+        }
+    }
+    let x = polish_root_newton(c, (lo + hi) * half);
+    x.max(lo).min(hi)
+}
+
+/// sort roots and merge the ones closer than `epsilon`
+#[cfg(feature = "std")]
+fn sort_and_merge_roots<T>(mut xs: Vec<T>, epsilon: T) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    xs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    xs.dedup_by(|b, a| (*b - *a).abs() < epsilon);
+    xs
+}
+
+/// distinct real roots of the polynomial in the range `[a, b]` in ascending order.
+/// roots are isolated using the Sturm sequence and refined by bisection until the
+/// range is narrower than `epsilon`.
+/// * `c` - coefficients of the polynomial `c[0] + c[1]*x + c[2]*x^2 + ...`
+#[cfg(feature = "std")]
+pub fn roots_in_range_sturm<T>(c: &[T], a: T, b: T, epsilon: T) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    use crate::polynomial::{div_rem, eval, num_roots_in_range_sturm, sturm_sequence};
+    assert!(epsilon > T::zero());
+    assert!(a <= b);
+    let seq = sturm_sequence(c);
+    if seq.len() < 2 {
+        return vec![]; // constant function
+    }
+    let seq = if seq[seq.len() - 1].len() > 1 {
+        // the last element is the GCD of `c` and `c'`. make the polynomial square-free
+        let (q, _) = div_rem(&seq[0], &seq[seq.len() - 1], T::zero()).unwrap();
+        sturm_sequence(&q)
+    } else {
+        seq
+    };
+    let p = &seq[0];
+    let half = T::one() / (T::one() + T::one());
+    let mut res = vec![];
+    if eval(p, a) == T::zero() {
+        res.push(a);
+    }
+    let mut stack = vec![(a, b, num_roots_in_range_sturm(&seq, a, b))];
+    while let Some((lo, hi, num_root)) = stack.pop() {
+        if num_root == 0 {
+            continue;
+        }
+        let flo = eval(p, lo);
+        let fhi = eval(p, hi);
+        if num_root == 1 && fhi == T::zero() {
+            res.push(hi);
+            continue;
+        }
+        if num_root == 1 && (flo < T::zero()) != (fhi < T::zero()) && flo != T::zero() {
+            res.push(root_bisection(p, lo, hi, epsilon));
+            continue;
+        }
+        let mid = (lo + hi) * half;
+        if hi - lo < epsilon || mid <= lo || mid >= hi {
+            // multiple root or cluster of roots
+            res.push(mid);
+            continue;
+        }
+        stack.push((lo, mid, num_roots_in_range_sturm(&seq, lo, mid)));
+        stack.push((mid, hi, num_roots_in_range_sturm(&seq, mid, hi)));
+    }
+    sort_and_merge_roots(res, epsilon)
+}
+
+/// distinct real roots of the polynomial in the range `[a, b]` in ascending order.
+/// roots are isolated by subdividing the polynomial in the Bernstein basis
+/// (Descartes' rule of signs) and refined by bisection until the range is narrower than `epsilon`.
+/// * `c` - coefficients of the polynomial `c[0] + c[1]*x + c[2]*x^2 + ...`
+///
+/// a root of even multiplicity may be missed because the sign does not change around it.
+/// use `roots_in_range_sturm` if such roots matter.
+#[cfg(feature = "std")]
+pub fn roots_in_range_bernstein<T>(c: &[T], a: T, b: T, epsilon: T) -> Vec<T>
+where
+    T: num_traits::Float,
+{
+    use crate::polynomial::{eval, num_sign_changes, split_bernstein, to_bernstein, trim};
+    assert!(epsilon > T::zero());
+    assert!(a <= b);
+    let max_abs = c.iter().fold(T::zero(), |m, &ci| m.max(ci.abs()));
+    let c = trim(c, max_abs * T::epsilon());
+    if c.len() < 2 {
+        return vec![]; // constant function
+    }
+    let half = T::one() / (T::one() + T::one());
+    let mut res = vec![];
+    if eval(c, a) == T::zero() {
+        res.push(a);
+    }
+    if eval(c, b) == T::zero() {
+        res.push(b);
+    }
+    let mut stack = vec![(a, b, to_bernstein(c, a, b))];
+    while let Some((lo, hi, bern)) = stack.pop() {
+        let (b_lo, b_hi) = (bern[0], bern[bern.len() - 1]);
+        if b_hi == T::zero() {
+            res.push(hi);
+        }
+        let num_sign_change = num_sign_changes(&bern);
+        if num_sign_change == 0 {
+            continue;
+        }
+        if num_sign_change == 1 && b_lo != T::zero() && b_hi != T::zero() {
+            // exactly one root
+            res.push(root_bisection(c, lo, hi, epsilon));
+            continue;
+        }
+        let mid = (lo + hi) * half;
+        if hi - lo < epsilon || mid <= lo || mid >= hi {
+            res.push(mid);
+            continue;
+        }
+        let (left, right) = split_bernstein(&bern);
+        stack.push((lo, mid, left));
+        stack.push((mid, hi, right));
+    }
+    sort_and_merge_roots(res, epsilon)
+}
+
+#[test]
+fn test_roots_in_range() {
+    use crate::polynomial::{eval, mult};
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let num_root = rng.gen_range(1..7);
+        let mut rs: Vec<f64> = (0..num_root).map(|_| 3. * rng.gen::<f64>() - 1.).collect();
+        rs.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        if rs.windows(2).any(|w| w[1] - w[0] < 1.0e-2) {
+            continue;
+        }
+        let mut c = vec![1.0 + rng.gen::<f64>()];
+        for &r in rs.iter() {
+            c = mult(&c, &[-r, 1.]);
+        }
+        c = mult(&c, &[1., 0., 1.]); // complex roots
+        let rs_in: Vec<f64> = rs
+            .iter()
+            .copied()
+            .filter(|&r| (0. ..=1.).contains(&r))
+            .collect();
+        for xs in [
+            roots_in_range_sturm(&c, 0., 1., 1.0e-10),
+            roots_in_range_bernstein(&c, 0., 1., 1.0e-10),
+        ] {
+            assert_eq!(xs.len(), rs_in.len(), "{:?} {:?}", xs, rs_in);
+            for (x, r) in xs.iter().zip(rs_in.iter()) {
+                assert!((x - r).abs() < 1.0e-8, "{} {}", x, r);
+                assert!(eval(&c, *x).abs() < 1.0e-8);
+            }
+        }
+    }
+    // double root (x-0.5)^2 and root at the end point
+    let c = mult(&mult(&[-0.5f64, 1.], &[-0.5, 1.]), &[-1., 1.]);
+    let xs = roots_in_range_sturm(&c, 0., 1., 1.0e-8);
+    assert_eq!(xs.len(), 2, "{:?}", xs);
+    assert!((xs[0] - 0.5).abs() < 1.0e-6);
+    assert!((xs[1] - 1.0).abs() < 1.0e-12);
+    let xs = roots_in_range_bernstein(&c, 0.6, 1., 1.0e-8);
+    assert_eq!(xs, vec![1.0]);
+}
@@ -15,7 +15,7 @@ where
 }
 impl<Real> Quaternion<Real> for [Real; 4]
 where
-    Real: num_traits::Float + std::ops::MulAssign,
+    Real: num_traits::Float + core::ops::MulAssign,
 {
     fn to_mat3_col_major(&self) -> [Real; 9] {
         to_mat3_col_major(self)
@@ -64,6 +64,47 @@ where
     ]
 }
 
+/// derivative of [`to_mat3_col_major`] w.r.t. the quaternion
+/// # return
+/// `dm[i][k]` is the derivative of the `i`-th entry of the matrix w.r.t. `q[k]`
+pub fn jacobian_to_mat3_col_major<Real>(q: &[Real; 4]) -> [[Real; 4]; 9]
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    let two = Real::one() + Real::one();
+    let four = two + two;
+    let (x, y, z, w) = (q[0], q[1], q[2], q[3]);
+    [
+        [zero, -four * y, -four * z, zero],
+        [two * y, two * x, two * w, two * z],
+        [two * z, -two * w, two * x, -two * y],
+        [two * y, two * x, -two * w, -two * z],
+        [-four * x, zero, -four * z, zero],
+        [two * w, two * z, two * y, two * x],
+        [two * z, two * w, two * x, two * y],
+        [-two * w, two * z, two * y, -two * x],
+        [-four * x, -four * y, zero, zero],
+    ]
+}
+
+#[test]
+fn test_jacobian_to_mat3_col_major() {
+    let q0 = [0.3, -0.2, 0.5, 0.8f64];
+    let dm = jacobian_to_mat3_col_major(&q0);
+    let m0 = to_mat3_col_major(&q0);
+    let eps = 1.0e-6;
+    for k in 0..4 {
+        let mut q1 = q0;
+        q1[k] += eps;
+        let m1 = to_mat3_col_major(&q1);
+        for i in 0..9 {
+            let v_num = (m1[i] - m0[i]) / eps;
+            assert!((v_num - dm[i][k]).abs() < 1.0e-5, "{} {} {}", i, k, v_num);
+        }
+    }
+}
+
 pub fn to_mat4_col_major<Real>(q: &[Real; 4]) -> [Real; 16]
 where
     Real: num_traits::Float,
@@ -178,7 +219,7 @@ where
 /// return rotation around axis with radian
 pub fn around_axis<Real>(a: &[Real; 3], rad: Real) -> [Real; 4]
 where
-    Real: num_traits::Float + std::ops::MulAssign,
+    Real: num_traits::Float + core::ops::MulAssign,
 {
     let v = a.normalize();
     let half = rad / Real::from(2).unwrap();
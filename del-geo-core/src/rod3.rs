@@ -0,0 +1,721 @@
+//! energy terms of the discrete elastic rod
+//!
+//! a stencil consists of three vertices `p0, p1, p2` and the two edges `e0 = p1 - p0`, `e1 = p2 - p1`.
+//! each edge has a reference director `r` and a twist angle `theta` that rotates the director around the edge.
+//! the degrees of freedom of a stencil are ordered as `[p0, p1, p2, theta0, theta1]` (11 in total).
+//!
+//! Bergou et al. "Discrete Elastic Rods" ACM TOG 2008
+
+use crate::vec3::Vec3;
+use crate::wdwddw::compose;
+
+// --------------------------------
+// below: private utility
+
+fn outer<T, const N: usize>(a: &[T; N], b: &[T; N]) -> [[T; N]; N]
+where
+    T: num_traits::Float,
+{
+    core::array::from_fn(|i| core::array::from_fn(|j| a[i] * b[j]))
+}
+
+/// unit vector `u = v / |v|` and its derivatives w.r.t. `v`
+#[allow(clippy::type_complexity)]
+fn wdwddw_unit_vector<T>(v: &[T; 3]) -> ([T; 3], [[T; 3]; 3], [[[T; 3]; 3]; 3])
+where
+    T: num_traits::Float,
+{
+    let three = T::one() + T::one() + T::one();
+    let l = v.norm();
+    let u = v.scale(T::one() / l);
+    let delta = |i: usize, j: usize| if i == j { T::one() } else { T::zero() };
+    let j = core::array::from_fn(|k| core::array::from_fn(|i| (delta(k, i) - u[k] * u[i]) / l));
+    let h = core::array::from_fn(|k| {
+        core::array::from_fn(|i| {
+            core::array::from_fn(|m| {
+                (three * u[i] * u[m] * u[k]
+                    - delta(i, m) * u[k]
+                    - delta(i, k) * u[m]
+                    - delta(m, k) * u[i])
+                    / (l * l)
+            })
+        })
+    });
+    (u, j, h)
+}
+
+/// tangent `t` of the edge and the director `q` that is the reference vector `r` projected
+/// perpendicular to `t`, with their derivatives w.r.t. the edge vector `e`
+#[allow(clippy::type_complexity)]
+fn wdwddw_edge_frame<T>(
+    e: &[T; 3],
+    r: &[T; 3],
+) -> (
+    [T; 3],
+    [[T; 3]; 3],
+    [[[T; 3]; 3]; 3],
+    [T; 3],
+    [[T; 3]; 3],
+    [[[T; 3]; 3]; 3],
+)
+where
+    T: num_traits::Float,
+{
+    let (t, jt, ht) = wdwddw_unit_vector(e);
+    // v = r - (r.t) t
+    let rt = r.dot(&t);
+    let v = crate::vec3::axpy(-rt, &t, r);
+    let delta = |i: usize, j: usize| if i == j { T::one() } else { T::zero() };
+    let jv: [[T; 3]; 3] =
+        core::array::from_fn(|k| core::array::from_fn(|j| -(r[j] * t[k] + rt * delta(k, j))));
+    let hv: [[[T; 3]; 3]; 3] = core::array::from_fn(|k| {
+        core::array::from_fn(|i| {
+            core::array::from_fn(|j| -(r[j] * delta(k, i) + r[i] * delta(k, j)))
+        })
+    });
+    let (q, jq, hq) = wdwddw_unit_vector(&v);
+    let (jqt, hqt) = compose(&jq, &hq, &jv, &hv);
+    let (jqe, hqe) = compose(&jqt, &hqt, &jt, &ht);
+    (t, jt, ht, q, jqe, hqe)
+}
+
+/// map the derivatives w.r.t. the edge vectors `[e0, e1]` to the vertex positions `[p0, p1, p2]`
+fn edges_to_vertices<T>(dw: &[T; 6], ddw: &[[T; 6]; 6]) -> ([T; 9], [[T; 9]; 9])
+where
+    T: num_traits::Float,
+{
+    // e0 = p1 - p0, e1 = p2 - p1
+    let mut jac = [[T::zero(); 9]; 6];
+    for i in 0..3 {
+        jac[i][i] = -T::one();
+        jac[i][3 + i] = T::one();
+        jac[3 + i][3 + i] = -T::one();
+        jac[3 + i][6 + i] = T::one();
+    }
+    let (j, h) = compose(&[*dw], &[*ddw], &jac, &[[[T::zero(); 9]; 9]; 6]);
+    (j[0], h[0])
+}
+
+// --------------------------------
+// below: public functions
+
+/// stretch energy of an edge `stiffness * (|p1 - p0| - length_rest)^2 / length_rest`
+/// * Return - `(energy, gradient, hessian)` w.r.t. `[p0, p1]`
+#[allow(clippy::type_complexity)]
+pub fn wdwddw_stretch<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    stiffness: T,
+    length_rest: T,
+) -> (T, [T; 6], [[T; 6]; 6])
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let e = p1.sub(p0);
+    let l = e.norm();
+    let u = e.scale(T::one() / l);
+    let c = stiffness / length_rest;
+    let w = c * (l - length_rest) * (l - length_rest);
+    let g = u.scale(two * c * (l - length_rest));
+    let mut dw = [T::zero(); 6];
+    for i in 0..3 {
+        dw[i] = -g[i];
+        dw[3 + i] = g[i];
+    }
+    let mut ddw = [[T::zero(); 6]; 6];
+    let r = (l - length_rest) / l;
+    for i in 0..3 {
+        for j in 0..3 {
+            let delta = if i == j { T::one() } else { T::zero() };
+            let h = two * c * (u[i] * u[j] + r * (delta - u[i] * u[j]));
+            ddw[i][j] = h;
+            ddw[3 + i][3 + j] = h;
+            ddw[i][3 + j] = -h;
+            ddw[3 + i][j] = -h;
+        }
+    }
+    (w, dw, ddw)
+}
+
+/// discrete curvature binormal `2 e0 x e1 / (|e0||e1| + e0.e1)` at the middle vertex.
+/// its magnitude is `2 tan(phi/2)` where `phi` is the turning angle.
+pub fn curvature_binormal<T>(p0: &[T; 3], p1: &[T; 3], p2: &[T; 3]) -> [T; 3]
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let e0 = p1.sub(p0);
+    let e1 = p2.sub(p1);
+    let d = e0.norm() * e1.norm() + e0.dot(&e1);
+    e0.cross(&e1).scale(two / d)
+}
+
+/// curvature binormal and its derivatives w.r.t. `[e0, e1]`
+#[allow(clippy::type_complexity)]
+fn wdwddw_curvature_binormal<T>(e0: &[T; 3], e1: &[T; 3]) -> ([T; 3], [[T; 6]; 3], [[[T; 6]; 6]; 3])
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let two = one + one;
+    let (l0, l1) = (e0.norm(), e1.norm());
+    let (u0, u1) = (e0.scale(one / l0), e1.scale(one / l1));
+    // Levi-Civita symbol
+    let levi = |k: usize, i: usize, j: usize| -> T {
+        let (k, i, j) = (k as i64, i as i64, j as i64);
+        T::from((i - j) * (j - k) * (k - i) / 2).unwrap()
+    };
+    // intermediate variables y = [e0 x e1, d] where d = |e0||e1| + e0.e1
+    let s = e0.cross(e1);
+    let d = l0 * l1 + e0.dot(e1);
+    let mut jy = [[T::zero(); 6]; 4];
+    let mut hy = [[[T::zero(); 6]; 6]; 4];
+    for k in 0..3 {
+        for i in 0..3 {
+            for j in 0..3 {
+                let v = levi(k, i, j);
+                jy[k][i] = jy[k][i] + v * e1[j];
+                jy[k][3 + j] = jy[k][3 + j] + v * e0[i];
+                hy[k][i][3 + j] = v;
+                hy[k][3 + j][i] = v;
+            }
+        }
+    }
+    for i in 0..3 {
+        jy[3][i] = l1 * u0[i] + e1[i];
+        jy[3][3 + i] = l0 * u1[i] + e0[i];
+        for j in 0..3 {
+            let delta = if i == j { one } else { T::zero() };
+            hy[3][i][j] = l1 / l0 * (delta - u0[i] * u0[j]);
+            hy[3][3 + i][3 + j] = l0 / l1 * (delta - u1[i] * u1[j]);
+            hy[3][i][3 + j] = u0[i] * u1[j] + delta;
+            hy[3][3 + j][i] = u0[i] * u1[j] + delta;
+        }
+    }
+    // kb = 2 s / d
+    let kb = s.scale(two / d);
+    let mut jz = [[T::zero(); 4]; 3];
+    let mut hz = [[[T::zero(); 4]; 4]; 3];
+    for k in 0..3 {
+        jz[k][k] = two / d;
+        jz[k][3] = -two * s[k] / (d * d);
+        hz[k][k][3] = -two / (d * d);
+        hz[k][3][k] = -two / (d * d);
+        hz[k][3][3] = two * two * s[k] / (d * d * d);
+    }
+    let (jkb, hkb) = compose(&jz, &hz, &jy, &hy);
+    (kb, jkb, hkb)
+}
+
+/// directors of the two edges computed by projecting the reference vectors `r0`, `r1`
+/// perpendicular to the edges and rotating them by `theta0`, `theta1` around the edges.
+/// `r0`, `r1` are typically updated by the parallel transport as the rod deforms.
+pub fn material_directors<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    r0: &[T; 3],
+    r1: &[T; 3],
+    theta0: T,
+    theta1: T,
+) -> ([T; 3], [T; 3])
+where
+    T: num_traits::Float,
+{
+    let director = |e: &[T; 3], r: &[T; 3], theta: T| {
+        let t = e.normalize();
+        let q = crate::vec3::orthogonalize(&t, r).normalize();
+        q.scale(theta.cos()).add(&t.cross(&q).scale(theta.sin()))
+    };
+    (
+        director(&p1.sub(p0), r0, theta0),
+        director(&p2.sub(p1), r1, theta1),
+    )
+}
+
+/// curvatures of the stencil in the material frames of the two edges, `[[kb.m2_0, -kb.m1_0], [kb.m2_1, -kb.m1_1]]`
+/// where `kb` is the curvature binormal (see [`curvature_binormal`]), `m1_j` is the director of the edge `j`
+/// (see [`material_directors`]) and `m2_j = t_j x m1_j`. Evaluate this at the rest shape to get `kappa_rest` of [`wdwddw_bend`]
+#[allow(clippy::too_many_arguments)]
+pub fn material_curvatures<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    r0: &[T; 3],
+    r1: &[T; 3],
+    theta0: T,
+    theta1: T,
+) -> [[T; 2]; 2]
+where
+    T: num_traits::Float,
+{
+    let kb = curvature_binormal(p0, p1, p2);
+    let (m0, m1) = material_directors(p0, p1, p2, r0, r1, theta0, theta1);
+    let t0 = p1.sub(p0).normalize();
+    let t1 = p2.sub(p1).normalize();
+    [(t0, m0), (t1, m1)].map(|(t, m)| [kb.dot(&t.cross(&m)), -kb.dot(&m)])
+}
+
+/// bending energy `stiffness * (|kappa_0 - kappa_rest_0|^2 + |kappa_1 - kappa_rest_1|^2) / (2 * length_voronoi)`
+/// where `kappa_j` is the curvature of the stencil in the material frame of the edge `j` (see [`material_curvatures`]).
+/// The energy is invariant under the rigid motion of the stencil and it is `stiffness * |kb|^2 / length_voronoi` if `kappa_rest` is zero
+/// * `r0`, `r1` - reference vectors of the edges. see [`material_directors`]
+/// * `kappa_rest` - material curvatures at rest, e.g., those of the rest shape of a pre-curved rod. Zero for a naturally straight rod
+/// * `length_voronoi` - rest length of the Voronoi region of the middle vertex, i.e., `(|e0| + |e1|)/2`
+/// * Return - `(energy, gradient, hessian)` w.r.t. `[p0, p1, p2, theta0, theta1]`
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn wdwddw_bend<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    r0: &[T; 3],
+    r1: &[T; 3],
+    theta0: T,
+    theta1: T,
+    kappa_rest: &[[T; 2]; 2],
+    stiffness: T,
+    length_voronoi: T,
+) -> (T, [T; 11], [[T; 11]; 11])
+where
+    T: num_traits::Float,
+{
+    let zero = T::zero();
+    let one = T::one();
+    let e = [p1.sub(p0), p2.sub(p1)];
+    let r = [r0, r1];
+    // y = [kb, t0, q0, t1, q1] w.r.t. [e0, e1]
+    let (kb, jkb, hkb) = wdwddw_curvature_binormal(&e[0], &e[1]);
+    let mut y = [zero; 15];
+    let mut jy = [[zero; 6]; 15];
+    let mut hy = [[[zero; 6]; 6]; 15];
+    y[..3].copy_from_slice(&kb);
+    jy[..3].copy_from_slice(&jkb);
+    hy[..3].copy_from_slice(&hkb);
+    for k in 0..2 {
+        let (t, jt, ht, q, jq, hq) = wdwddw_edge_frame(&e[k], r[k]);
+        for (offset, v, jv, hv) in [(3 + 6 * k, t, jt, ht), (6 + 6 * k, q, jq, hq)] {
+            for i in 0..3 {
+                y[offset + i] = v[i];
+                jy[offset + i][3 * k..3 * k + 3].copy_from_slice(&jv[i]);
+                for m in 0..3 {
+                    hy[offset + i][3 * k + m][3 * k..3 * k + 3].copy_from_slice(&hv[i][m]);
+                }
+            }
+        }
+    }
+    // v = [a0, b0, a1, b1, theta0, theta1] w.r.t. x = [e0, e1, theta0, theta1]
+    // where a_k = kb.q_k and b_k = kb.(t_k x q_k)
+    let mut v = [zero; 6];
+    let mut jv = [[zero; 8]; 6];
+    let mut hv = [[[zero; 8]; 8]; 6];
+    for k in 0..2 {
+        let (a, ja, ha) = crate::wdwddw::dot_slots(&y, 0, 6 + 6 * k);
+        let (b, jb, hb) = crate::wdwddw::det_slots(&y, 0, 3 + 6 * k, 6 + 6 * k);
+        let (jab, hab) = compose(&[ja, jb], &[ha, hb], &jy, &hy);
+        for (l, val) in [a, b].into_iter().enumerate() {
+            v[2 * k + l] = val;
+            jv[2 * k + l][..6].copy_from_slice(&jab[l]);
+            for i in 0..6 {
+                hv[2 * k + l][i][..6].copy_from_slice(&hab[l][i]);
+            }
+        }
+    }
+    v[4] = theta0;
+    v[5] = theta1;
+    jv[4][6] = one;
+    jv[5][7] = one;
+    // material curvatures w.r.t. v, using m1 = q cos + (t x q) sin and m2 = (t x q) cos - q sin
+    let mut kappa = [zero; 4];
+    let mut jk = [[zero; 6]; 4];
+    let mut hk = [[[zero; 6]; 6]; 4];
+    for k in 0..2 {
+        let (ia, ib, it) = (2 * k, 2 * k + 1, 4 + k);
+        let (s, c) = v[it].sin_cos();
+        let k1 = -s * v[ia] + c * v[ib];
+        let k2 = -(c * v[ia] + s * v[ib]);
+        kappa[2 * k] = k1;
+        kappa[2 * k + 1] = k2;
+        for (l, [da, db, dt], [dat, dbt, dtt]) in [
+            (2 * k, [-s, c, k2], [-c, -s, -k1]),
+            (2 * k + 1, [-c, -s, -k1], [s, -c, -k2]),
+        ] {
+            jk[l][ia] = da;
+            jk[l][ib] = db;
+            jk[l][it] = dt;
+            hk[l][ia][it] = dat;
+            hk[l][it][ia] = dat;
+            hk[l][ib][it] = dbt;
+            hk[l][it][ib] = dbt;
+            hk[l][it][it] = dtt;
+        }
+    }
+    let c = stiffness / length_voronoi;
+    let diff: [T; 4] = core::array::from_fn(|i| kappa[i] - kappa_rest[i / 2][i % 2]);
+    let w = c * diff.iter().fold(zero, |acc, &d| acc + d * d) / (one + one);
+    let mut hw = [[[zero; 4]; 4]];
+    for (i, row) in hw[0].iter_mut().enumerate() {
+        row[i] = c;
+    }
+    let (jw, hw) = compose(&[diff.map(|d| c * d)], &hw, &jk, &hk);
+    let (jw, hw) = compose(&jw, &hw, &jv, &hv);
+    // [e0, e1, theta0, theta1] to [p0, p1, p2, theta0, theta1]
+    let mut jx = [[zero; 11]; 8];
+    for i in 0..3 {
+        jx[i][i] = -one;
+        jx[i][3 + i] = one;
+        jx[3 + i][3 + i] = -one;
+        jx[3 + i][6 + i] = one;
+    }
+    jx[6][9] = one;
+    jx[7][10] = one;
+    let (dw, ddw) = compose(&jw, &hw, &jx, &[[[zero; 11]; 11]; 8]);
+    (w, dw[0], ddw[0])
+}
+
+/// twist between the material frames of the two edges measured relative to the parallel transport
+/// * Return - `(twist, reference_twist)` where `twist = theta1 - theta0 + reference_twist`
+#[allow(clippy::too_many_arguments)]
+pub fn twist<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    r0: &[T; 3],
+    r1: &[T; 3],
+    theta0: T,
+    theta1: T,
+) -> (T, T)
+where
+    T: num_traits::Float,
+{
+    let (w, _, _) = wdwddw_reference_twist(&p1.sub(p0), &p2.sub(p1), r0, r1);
+    (theta1 - theta0 + w, w)
+}
+
+/// reference twist and its derivatives w.r.t. `[e0, e1]`
+#[allow(clippy::type_complexity)]
+fn wdwddw_reference_twist<T>(
+    e0: &[T; 3],
+    e1: &[T; 3],
+    r0: &[T; 3],
+    r1: &[T; 3],
+) -> (T, [T; 6], [[T; 6]; 6])
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    // intermediate variables y = [t0, q0, t1, q1]
+    let (t0, jt0, ht0, q0, jq0, hq0) = wdwddw_edge_frame(e0, r0);
+    let (t1, jt1, ht1, q1, jq1, hq1) = wdwddw_edge_frame(e1, r1);
+    let mut y = [T::zero(); 12];
+    let mut jy = [[T::zero(); 6]; 12];
+    let mut hy = [[[T::zero(); 6]; 6]; 12];
+    for (iy, ix, v, jv, hv) in [
+        (0, 0, t0, jt0, ht0),
+        (3, 0, q0, jq0, hq0),
+        (6, 3, t1, jt1, ht1),
+        (9, 3, q1, jq1, hq1),
+    ] {
+        for k in 0..3 {
+            y[iy + k] = v[k];
+            for i in 0..3 {
+                jy[iy + k][ix + i] = jv[k][i];
+                for j in 0..3 {
+                    hy[iy + k][ix + i][ix + j] = hv[k][i][j];
+                }
+            }
+        }
+    }
+    let (it0, iq0, it1, iq1) = (0, 3, 6, 9);
+    // the rotation angle between the frames [t0, q0, t0 x q0] and [t1, q1, t1 x q1] around t1
+    // relative to the parallel transport is atan2(Y, X) where
+    // X = (1 + t0.t1)(q0.q1) - (t0.q1)(q0.t1), Y = det(t0, q0, q1) - det(q0, t1, q1)
+    let (a, da, dda) = crate::wdwddw::dot_slots(&y, it0, it1);
+    let a = a + one;
+    let (b, db, ddb) = crate::wdwddw::dot_slots(&y, iq0, iq1);
+    let (c, dc, ddc) = crate::wdwddw::dot_slots(&y, it0, iq1);
+    let (d, dd, ddd) = crate::wdwddw::dot_slots(&y, iq0, it1);
+    let x = a * b - c * d;
+    let mut dx = [T::zero(); 12];
+    let mut ddx = [[T::zero(); 12]; 12];
+    for i in 0..12 {
+        dx[i] = b * da[i] + a * db[i] - d * dc[i] - c * dd[i];
+        for j in 0..12 {
+            ddx[i][j] = da[i] * db[j] + db[i] * da[j] + b * dda[i][j] + a * ddb[i][j]
+                - dc[i] * dd[j]
+                - dd[i] * dc[j]
+                - d * ddc[i][j]
+                - c * ddd[i][j];
+        }
+    }
+    let (y0, dy0, ddy0) = crate::wdwddw::det_slots(&y, it0, iq0, iq1);
+    let (y1, dy1, ddy1) = crate::wdwddw::det_slots(&y, iq0, it1, iq1);
+    let yy = y0 - y1;
+    let dyy: [T; 12] = core::array::from_fn(|i| dy0[i] - dy1[i]);
+    let ddyy: [[T; 12]; 12] =
+        core::array::from_fn(|i| core::array::from_fn(|j| ddy0[i][j] - ddy1[i][j]));
+    let (w, dw_dy, ddw_dydy) = crate::wdwddw::atan2((yy, &dyy, &ddyy), (x, &dx, &ddx));
+    let (dw, ddw) = compose(&[dw_dy], &[ddw_dydy], &jy, &hy);
+    (w, dw[0], ddw[0])
+}
+
+/// twist energy `stiffness * (twist - twist_rest)^2 / length_voronoi`
+/// * `r0`, `r1` - reference vectors of the edges. see [`material_directors`]
+/// * `length_voronoi` - rest length of the Voronoi region of the middle vertex
+/// * Return - `(energy, gradient, hessian)` w.r.t. `[p0, p1, p2, theta0, theta1]`
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn wdwddw_twist<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    r0: &[T; 3],
+    r1: &[T; 3],
+    theta0: T,
+    theta1: T,
+    stiffness: T,
+    twist_rest: T,
+    length_voronoi: T,
+) -> (T, [T; 11], [[T; 11]; 11])
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let (m, dmde, ddmde) = wdwddw_reference_twist(&p1.sub(p0), &p2.sub(p1), r0, r1);
+    let (dmdp, ddmdp) = edges_to_vertices(&dmde, &ddmde);
+    let mut dtw = [T::zero(); 11];
+    dtw[..9].copy_from_slice(&dmdp);
+    dtw[9] = -T::one();
+    dtw[10] = T::one();
+    let c = stiffness / length_voronoi;
+    let diff = theta1 - theta0 + m - twist_rest;
+    let w = c * diff * diff;
+    let dw = dtw.map(|v| two * c * diff * v);
+    let mut ddw = outer(&dtw, &dtw).map(|row| row.map(|v| two * c * v));
+    for i in 0..9 {
+        for j in 0..9 {
+            ddw[i][j] = ddw[i][j] + two * c * diff * ddmdp[i][j];
+        }
+    }
+    (w, dw, ddw)
+}
+
+/// bending and twisting energy of a stencil
+/// * `r0`, `r1` - reference vectors of the edges. see [`material_directors`]
+/// * `stiffness` - `[bend, twist]`
+/// * `kappa_rest` - material curvatures at rest. see [`wdwddw_bend`]
+/// * `length_voronoi` - rest length of the Voronoi region of the middle vertex, i.e., `(|e0| + |e1|)/2`
+/// * Return - `(energy, gradient, hessian)` w.r.t. `[p0, p1, p2, theta0, theta1]`
+///
+/// the stretch is not included because an edge is shared by the neighboring stencils.
+/// Add [`wdwddw_stretch`] once per edge of the rod
+#[allow(clippy::too_many_arguments, clippy::type_complexity)]
+pub fn wdwddw_stencil<T>(
+    p0: &[T; 3],
+    p1: &[T; 3],
+    p2: &[T; 3],
+    r0: &[T; 3],
+    r1: &[T; 3],
+    theta0: T,
+    theta1: T,
+    stiffness: &[T; 2],
+    kappa_rest: &[[T; 2]; 2],
+    twist_rest: T,
+    length_voronoi: T,
+) -> (T, [T; 11], [[T; 11]; 11])
+where
+    T: num_traits::Float,
+{
+    let (mut w, mut dw, mut ddw) = wdwddw_bend(
+        p0,
+        p1,
+        p2,
+        r0,
+        r1,
+        theta0,
+        theta1,
+        kappa_rest,
+        stiffness[0],
+        length_voronoi,
+    );
+    let (w1, dw1, ddw1) = wdwddw_twist(
+        p0,
+        p1,
+        p2,
+        r0,
+        r1,
+        theta0,
+        theta1,
+        stiffness[1],
+        twist_rest,
+        length_voronoi,
+    );
+    w = w + w1;
+    for i in 0..11 {
+        dw[i] = dw[i] + dw1[i];
+        for j in 0..11 {
+            ddw[i][j] = ddw[i][j] + ddw1[i][j];
+        }
+    }
+    (w, dw, ddw)
+}
+
+/// random stencil bent moderately and random twist angles
+#[cfg(test)]
+fn sample_stencil<R: rand::Rng>(rng: &mut R) -> [f64; 11] {
+    let mut x: [f64; 11] = core::array::from_fn(|_| 0.6 * rng.gen::<f64>() - 0.3);
+    x[3] += 1.;
+    x[6] += 2.;
+    x[9] *= 10.;
+    x[10] *= 10.;
+    x
+}
+
+#[test]
+fn test_wdwddw_stretch() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let x0: [f64; 6] = core::array::from_fn(|_| rng.gen::<f64>());
+        crate::wdwddw::check_derivatives(&x0, |x| {
+            let p0 = arrayref::array_ref![x, 0, 3];
+            let p1 = arrayref::array_ref![x, 3, 3];
+            wdwddw_stretch(p0, p1, 1.3, 0.4)
+        });
+    }
+}
+
+#[test]
+fn test_wdwddw_bend() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let x0 = sample_stencil(&mut rng);
+        let r0 = [rng.gen::<f64>() - 0.5, 1., rng.gen::<f64>() - 0.5];
+        let r1 = [rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5, 1.];
+        let (p0, p1, p2) = (
+            arrayref::array_ref![x0, 0, 3],
+            arrayref::array_ref![x0, 3, 3],
+            arrayref::array_ref![x0, 6, 3],
+        );
+        // the magnitude of the curvature binormal is 2 tan(phi/2)
+        let kb = curvature_binormal(p0, p1, p2);
+        let phi = p1.sub(p0).normalize().dot(&p2.sub(p1).normalize()).acos();
+        assert!((kb.norm() - 2. * (phi * 0.5).tan()).abs() < 1.0e-8);
+        let bend = |x: &[f64; 11], r0: &[f64; 3], r1: &[f64; 3], kappa_rest: &[[f64; 2]; 2]| {
+            let p0 = arrayref::array_ref![x, 0, 3];
+            let p1 = arrayref::array_ref![x, 3, 3];
+            let p2 = arrayref::array_ref![x, 6, 3];
+            wdwddw_bend(p0, p1, p2, r0, r1, x[9], x[10], kappa_rest, 1.3, 0.7)
+        };
+        let (w, _, _) = bend(&x0, &r0, &r1, &[[0.; 2]; 2]);
+        assert!((w - 1.3 * kb.squared_norm() / 0.7).abs() < 1.0e-8);
+        // the energy vanishes at the rest shape
+        let kappa = material_curvatures(p0, p1, p2, &r0, &r1, x0[9], x0[10]);
+        let (w, dw, _) = bend(&x0, &r0, &r1, &kappa);
+        assert!(w.abs() < 1.0e-20);
+        assert!(dw.iter().all(|v| v.abs() < 1.0e-10));
+        // the energy does not change under the rigid motion
+        let kappa_rest = [
+            [rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5],
+            [rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5],
+        ];
+        {
+            let axisangle: [f64; 3] = core::array::from_fn(|_| 2. * rng.gen::<f64>() - 1.);
+            let rot = crate::quaternion::to_mat3_col_major(&crate::quaternion::from_axisangle(
+                &axisangle,
+            ));
+            let trans = [0.3, -0.2, 0.5];
+            let mut x1 = x0;
+            for i in 0..3 {
+                let p = crate::mat3_col_major::mult_vec(&rot, arrayref::array_ref![x0, i * 3, 3]);
+                x1[i * 3..i * 3 + 3].copy_from_slice(&p.add(&trans));
+            }
+            let r0r = crate::mat3_col_major::mult_vec(&rot, &r0);
+            let r1r = crate::mat3_col_major::mult_vec(&rot, &r1);
+            let (w0, _, _) = bend(&x0, &r0, &r1, &kappa_rest);
+            let (w1, _, _) = bend(&x1, &r0r, &r1r, &kappa_rest);
+            assert!(w0 > 1.0e-3);
+            assert!((w0 - w1).abs() < 1.0e-10 * w0);
+        }
+        for kappa_rest in [[[0.; 2]; 2], kappa_rest] {
+            crate::wdwddw::check_derivatives(&x0, |x| bend(x, &r0, &r1, &kappa_rest));
+        }
+    }
+}
+
+#[test]
+fn test_wdwddw_twist() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let x0 = sample_stencil(&mut rng);
+        let r0 = [rng.gen::<f64>() - 0.5, 1., rng.gen::<f64>() - 0.5];
+        let r1 = [rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5, 1.];
+        let (p0, p1, p2) = (
+            arrayref::array_ref![x0, 0, 3],
+            arrayref::array_ref![x0, 3, 3],
+            arrayref::array_ref![x0, 6, 3],
+        );
+        {
+            // compare with the angle between the transported director and the director of the second edge
+            let (d0, d1) = material_directors(p0, p1, p2, &r0, &r1, x0[9], x0[10]);
+            let t0 = p1.sub(p0).normalize();
+            let t1 = p2.sub(p1).normalize();
+            let d0 = crate::polyline3::parallel_transport(&d0, &t0, &t1);
+            let angle = d0.cross(&d1).dot(&t1).atan2(d0.dot(&d1));
+            let (tw, _) = twist(p0, p1, p2, &r0, &r1, x0[9], x0[10]);
+            let diff = (tw - angle) / (2. * core::f64::consts::PI);
+            assert!((diff - diff.round()).abs() < 1.0e-8);
+        }
+        crate::wdwddw::check_derivatives(&x0, |x| {
+            let p0 = arrayref::array_ref![x, 0, 3];
+            let p1 = arrayref::array_ref![x, 3, 3];
+            let p2 = arrayref::array_ref![x, 6, 3];
+            wdwddw_twist(p0, p1, p2, &r0, &r1, x[9], x[10], 1.3, 0.2, 0.7)
+        });
+    }
+}
+
+#[test]
+fn test_wdwddw_stencil() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..10 {
+        let x0 = sample_stencil(&mut rng);
+        let r0 = [rng.gen::<f64>() - 0.5, 1., rng.gen::<f64>() - 0.5];
+        let r1 = [rng.gen::<f64>() - 0.5, rng.gen::<f64>() - 0.5, 1.];
+        let kappa_rest = [[0.1, -0.2], [0.3, 0.05]];
+        let eval = |x: &[f64; 11]| {
+            let p0 = arrayref::array_ref![x, 0, 3];
+            let p1 = arrayref::array_ref![x, 3, 3];
+            let p2 = arrayref::array_ref![x, 6, 3];
+            wdwddw_stencil(
+                p0,
+                p1,
+                p2,
+                &r0,
+                &r1,
+                x[9],
+                x[10],
+                &[1.3, 0.9],
+                &kappa_rest,
+                0.2,
+                1.0,
+            )
+        };
+        crate::wdwddw::check_derivatives(&x0, eval);
+        // sum of the terms
+        let (p0, p1, p2) = (
+            arrayref::array_ref![x0, 0, 3],
+            arrayref::array_ref![x0, 3, 3],
+            arrayref::array_ref![x0, 6, 3],
+        );
+        let w0 = wdwddw_bend(p0, p1, p2, &r0, &r1, x0[9], x0[10], &kappa_rest, 1.3, 1.0).0
+            + wdwddw_twist(p0, p1, p2, &r0, &r1, x0[9], x0[10], 0.9, 0.2, 1.0).0;
+        assert!((eval(&x0).0 - w0).abs() < 1.0e-10);
+    }
+}
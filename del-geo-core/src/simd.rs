@@ -0,0 +1,390 @@
+//! batch versions of the hot kernels in the structure-of-arrays (SoA) layout.
+//! `L` elements (lanes) are processed at once with the same arithmetic as the scalar versions,
+//! written in the branch-less style so that the compiler vectorizes them (`std::simd` is not stable yet).
+//! `L` of 4, 8 or 16 matches the SSE, AVX and AVX-512 registers for `f32`.
+//! The functions with the suffix `_lanes` work on one packet of `L` lanes, and
+//! the other functions work on the slices of arbitrary length
+
+/// `L` values from `s[i0..i0 + L]` padded with zero at the end of the slice
+fn load<Real, const L: usize>(s: &[Real], i0: usize) -> [Real; L]
+where
+    Real: num_traits::Float,
+{
+    match s.get(i0..i0 + L) {
+        Some(c) => c.try_into().unwrap(),
+        None => core::array::from_fn(|i| s.get(i0 + i).copied().unwrap_or(Real::zero())),
+    }
+}
+
+/// write the first `min(L, s.len() - i0)` values of `v` to `s[i0..]`
+fn store<T: Copy, const L: usize>(s: &mut [T], i0: usize, v: &[T; L]) {
+    let n = L.min(s.len() - i0);
+    s[i0..i0 + n].copy_from_slice(&v[..n]);
+}
+
+/// structure-of-arrays `[xs, ys, ...]` from the array-of-structures `[x0, y0, ..., x1, y1, ...]`
+#[cfg(feature = "std")]
+pub fn to_soa<Real, const NDIM: usize>(aos: &[Real]) -> [Vec<Real>; NDIM]
+where
+    Real: Copy,
+{
+    core::array::from_fn(|i_dim| aos.iter().skip(i_dim).step_by(NDIM).copied().collect())
+}
+
+// ------------------------------
+// ray-triangle
+
+/// `a - b` for `L` 3D vectors `[i_dim][i_lane]`
+fn sub3<Real, const L: usize>(a: &[[Real; L]; 3], b: &[[Real; L]; 3]) -> [[Real; L]; 3]
+where
+    Real: num_traits::Float,
+{
+    core::array::from_fn(|i_dim| core::array::from_fn(|i| a[i_dim][i] - b[i_dim][i]))
+}
+
+/// cross product for `L` 3D vectors `[i_dim][i_lane]` in the same order as `vec3::cross`
+fn cross3<Real, const L: usize>(a: &[[Real; L]; 3], b: &[[Real; L]; 3]) -> [[Real; L]; 3]
+where
+    Real: num_traits::Float,
+{
+    [
+        core::array::from_fn(|i| a[1][i] * b[2][i] - b[1][i] * a[2][i]),
+        core::array::from_fn(|i| a[2][i] * b[0][i] - b[2][i] * a[0][i]),
+        core::array::from_fn(|i| a[0][i] * b[1][i] - b[0][i] * a[1][i]),
+    ]
+}
+
+/// dot product for `L` 3D vectors `[i_dim][i_lane]` in the same order as `vec3::dot`
+fn dot3<Real, const L: usize>(a: &[[Real; L]; 3], b: &[[Real; L]; 3]) -> [Real; L]
+where
+    Real: num_traits::Float,
+{
+    core::array::from_fn(|i| a[0][i] * b[0][i] + a[1][i] * b[1][i] + a[2][i] * b[2][i])
+}
+
+/// Möller–Trumbore intersection of `L` triangles against a line (see `tri3::intersection_against_line`).
+/// * `tri` - `tri[i_node][i_dim][i_lane]`
+///
+/// * Return
+///     * `(hit, t)` where `t[i_lane]` is zero if `hit[i_lane]` is false
+pub fn tri3_intersection_against_line_lanes<Real, const L: usize>(
+    tri: &[[[Real; L]; 3]; 3],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+) -> ([bool; L], [Real; L])
+where
+    Real: num_traits::Float,
+{
+    let eps = Real::epsilon();
+    let (zero, one) = (Real::zero(), Real::one());
+    let ray_org = ray_org.map(|v| [v; L]);
+    let ray_dir = ray_dir.map(|v| [v; L]);
+    let edge1 = sub3(&tri[1], &tri[0]);
+    let edge2 = sub3(&tri[2], &tri[0]);
+    let pvec = cross3(&ray_dir, &edge2);
+    let det = dot3(&edge1, &pvec);
+    let invdet = det.map(|d| one / d);
+    let tvec = sub3(&ray_org, &tri[0]);
+    let u = dot3(&tvec, &pvec);
+    let qvec = cross3(&tvec, &edge1);
+    let v = dot3(&ray_dir, &qvec);
+    let t = dot3(&edge2, &qvec);
+    let hit: [bool; L] = core::array::from_fn(|i| {
+        let (u, v) = (invdet[i] * u[i], invdet[i] * v[i]);
+        !(det[i] > -eps && det[i] < eps) & !(u < zero || u > one) & !(v < zero || u + v > one)
+    });
+    let t = core::array::from_fn(|i| if hit[i] { invdet[i] * t[i] } else { zero });
+    (hit, t)
+}
+
+/// intersections of a line against the triangles in the SoA layout
+/// * `tri_xyz` - `[p0_xs, p0_ys, p0_zs, p1_xs, ..., p2_zs]` where each slice has the length of the number of triangles
+/// * `hits` - (out) whether the line hits the triangle
+/// * `ts` - (out) ratio of `ray_dir` at the intersection (zero if not hit)
+pub fn tri3_intersection_against_line<Real, const L: usize>(
+    tri_xyz: &[&[Real]; 9],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+    hits: &mut [bool],
+    ts: &mut [Real],
+) where
+    Real: num_traits::Float,
+{
+    let num_tri = hits.len();
+    assert_eq!(ts.len(), num_tri);
+    assert!(tri_xyz.iter().all(|s| s.len() == num_tri));
+    for i0 in (0..num_tri).step_by(L) {
+        let tri = core::array::from_fn(|i_node| {
+            core::array::from_fn(|i_dim| load(tri_xyz[i_node * 3 + i_dim], i0))
+        });
+        let (hit, t) = tri3_intersection_against_line_lanes::<Real, L>(&tri, ray_org, ray_dir);
+        store(hits, i0, &hit);
+        store(ts, i0, &t);
+    }
+}
+
+// ------------------------------
+// ray-AABB
+
+/// intersections of `L` 3D AABBs against a ray (see `aabb::intersections_against_ray`)
+/// * `aabb` - `aabb[i_dim][i_lane]` in the order of `[min_x, min_y, min_z, max_x, max_y, max_z]`
+///
+/// * Return
+///     * `(hit, t_min, t_max)` where `t_min` and `t_max` are zero if not hit
+pub fn aabb3_intersections_against_ray_lanes<Real, const L: usize>(
+    aabb: &[[Real; L]; 6],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+) -> ([bool; L], [Real; L], [Real; L])
+where
+    Real: num_traits::Float,
+{
+    let mut tmin = [Real::min_value(); L];
+    let mut tmax = [Real::max_value(); L];
+    let mut hit = [true; L];
+    for i_dim in 0..3 {
+        let (lo, hi) = (&aabb[i_dim], &aabb[i_dim + 3]);
+        if !ray_dir[i_dim].abs().is_zero() {
+            // the same branch for all the lanes as the ray is shared
+            for (i, (tmin, tmax)) in tmin.iter_mut().zip(tmax.iter_mut()).enumerate() {
+                let t1 = (lo[i] - ray_org[i_dim]) / ray_dir[i_dim];
+                let t2 = (hi[i] - ray_org[i_dim]) / ray_dir[i_dim];
+                *tmin = tmin.max(t1.min(t2));
+                *tmax = tmax.min(t1.max(t2));
+            }
+        } else {
+            for (i, hit) in hit.iter_mut().enumerate() {
+                *hit &= !(ray_org[i_dim] < lo[i] || ray_org[i_dim] > hi[i]);
+            }
+        }
+    }
+    let hit: [bool; L] =
+        core::array::from_fn(|i| hit[i] & (tmax[i] >= tmin[i]) & (tmax[i] >= Real::zero()));
+    let tmin = core::array::from_fn(|i| if hit[i] { tmin[i] } else { Real::zero() });
+    let tmax = core::array::from_fn(|i| if hit[i] { tmax[i] } else { Real::zero() });
+    (hit, tmin, tmax)
+}
+
+/// intersections of a ray against the 3D AABBs in the SoA layout
+/// * `aabb` - `[min_xs, min_ys, min_zs, max_xs, max_ys, max_zs]` where each slice has the length of the number of AABBs
+/// * `hits` - (out) whether the ray hits the AABB
+/// * `tmins`, `tmaxs` - (out) min and max of the ratio of `ray_dir` at the intersections (zero if not hit)
+pub fn aabb3_intersections_against_ray<Real, const L: usize>(
+    aabb: &[&[Real]; 6],
+    ray_org: &[Real; 3],
+    ray_dir: &[Real; 3],
+    hits: &mut [bool],
+    tmins: &mut [Real],
+    tmaxs: &mut [Real],
+) where
+    Real: num_traits::Float,
+{
+    let num_aabb = hits.len();
+    assert_eq!(tmins.len(), num_aabb);
+    assert_eq!(tmaxs.len(), num_aabb);
+    assert!(aabb.iter().all(|s| s.len() == num_aabb));
+    for i0 in (0..num_aabb).step_by(L) {
+        let lanes = core::array::from_fn(|i_dim| load(aabb[i_dim], i0));
+        let (hit, tmin, tmax) =
+            aabb3_intersections_against_ray_lanes::<Real, L>(&lanes, ray_org, ray_dir);
+        store(hits, i0, &hit);
+        store(tmins, i0, &tmin);
+        store(tmaxs, i0, &tmax);
+    }
+}
+
+// ------------------------------
+// homogeneous transformation
+
+/// transform `L` points with a homogeneous transformation (see `mat4_col_major::transform_homogeneous`)
+/// * `xyz` - `xyz[i_dim][i_lane]`
+///
+/// * Return
+///     * `(valid, xyz)` where `valid` is false if the homogeneous coordinate is zero (the point is zero then)
+pub fn mat4_col_major_transform_homogeneous_lanes<Real, const L: usize>(
+    transform: &[Real; 16],
+    xyz: &[[Real; L]; 3],
+) -> ([bool; L], [[Real; L]; 3])
+where
+    Real: num_traits::Float,
+{
+    let t = transform;
+    let y: [[Real; L]; 4] = core::array::from_fn(|i_row| {
+        core::array::from_fn(|i| {
+            t[i_row] * xyz[0][i]
+                + t[4 + i_row] * xyz[1][i]
+                + t[8 + i_row] * xyz[2][i]
+                + t[12 + i_row]
+        })
+    });
+    let valid: [bool; L] = y[3].map(|w| !w.is_zero());
+    let out = core::array::from_fn(|i_dim| {
+        core::array::from_fn(|i| {
+            if valid[i] {
+                y[i_dim][i] / y[3][i]
+            } else {
+                Real::zero()
+            }
+        })
+    });
+    (valid, out)
+}
+
+/// transform the points in the SoA layout with a homogeneous transformation
+/// * `xyz` - `[xs, ys, zs]` where each slice has the length of the number of points
+/// * `xyz_out` - (out) transformed points in the SoA layout (zero if not valid)
+/// * `valids` - (out) false if the homogeneous coordinate is zero
+pub fn mat4_col_major_transform_homogeneous<Real, const L: usize>(
+    transform: &[Real; 16],
+    xyz: &[&[Real]; 3],
+    xyz_out: &mut [&mut [Real]; 3],
+    valids: &mut [bool],
+) where
+    Real: num_traits::Float,
+{
+    let num_point = valids.len();
+    assert!(xyz.iter().all(|s| s.len() == num_point));
+    assert!(xyz_out.iter().all(|s| s.len() == num_point));
+    for i0 in (0..num_point).step_by(L) {
+        let lanes = core::array::from_fn(|i_dim| load(xyz[i_dim], i0));
+        let (valid, out) = mat4_col_major_transform_homogeneous_lanes::<Real, L>(transform, &lanes);
+        store(valids, i0, &valid);
+        for (s, v) in xyz_out.iter_mut().zip(out.iter()) {
+            store(s, i0, v);
+        }
+    }
+}
+
+#[test]
+fn test_tri3_intersection_against_line() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let num_tri = 1001;
+    let vtx2xyz: Vec<f32> = (0..num_tri * 9).map(|_| rng.gen::<f32>()).collect();
+    let tri_soa = to_soa::<f32, 9>(&vtx2xyz);
+    let tri_soa: [&[f32]; 9] = core::array::from_fn(|i| tri_soa[i].as_slice());
+    for _itr in 0..10 {
+        let ray_org = [
+            rng.gen::<f32>() * 0.5 + 0.25,
+            rng.gen::<f32>() * 0.5 + 0.25,
+            -1.0,
+        ];
+        let ray_dir = [
+            (rng.gen::<f32>() - 0.5) * 0.2,
+            (rng.gen::<f32>() - 0.5) * 0.2,
+            1.0,
+        ];
+        let mut hits = vec![false; num_tri];
+        let mut ts = vec![0f32; num_tri];
+        fn check(
+            vtx2xyz: &[f32],
+            ray_org: &[f32; 3],
+            ray_dir: &[f32; 3],
+            hits: &[bool],
+            ts: &[f32],
+        ) {
+            let mut num_hit = 0;
+            for (i_tri, p) in vtx2xyz.chunks(9).enumerate() {
+                let t = crate::tri3::intersection_against_line(
+                    arrayref::array_ref![p, 0, 3],
+                    arrayref::array_ref![p, 3, 3],
+                    arrayref::array_ref![p, 6, 3],
+                    ray_org,
+                    ray_dir,
+                );
+                assert_eq!(t.is_some(), hits[i_tri]);
+                assert_eq!(t.unwrap_or(0.), ts[i_tri]);
+                num_hit += usize::from(hits[i_tri]);
+            }
+            assert!(num_hit > 0);
+        }
+        tri3_intersection_against_line::<f32, 4>(&tri_soa, &ray_org, &ray_dir, &mut hits, &mut ts);
+        check(&vtx2xyz, &ray_org, &ray_dir, &hits, &ts);
+        tri3_intersection_against_line::<f32, 8>(&tri_soa, &ray_org, &ray_dir, &mut hits, &mut ts);
+        check(&vtx2xyz, &ray_org, &ray_dir, &hits, &ts);
+        tri3_intersection_against_line::<f32, 16>(&tri_soa, &ray_org, &ray_dir, &mut hits, &mut ts);
+        check(&vtx2xyz, &ray_org, &ray_dir, &hits, &ts);
+    }
+}
+
+#[test]
+fn test_aabb3_intersections_against_ray() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let num_aabb = 203;
+    let aabbs: Vec<f32> = (0..num_aabb)
+        .flat_map(|_| {
+            let c: [f32; 3] = core::array::from_fn(|_| rng.gen::<f32>() * 2. - 1.);
+            let r = rng.gen::<f32>() * 0.3;
+            [c[0] - r, c[1] - r, c[2] - r, c[0] + r, c[1] + r, c[2] + r]
+        })
+        .collect();
+    let aabb_soa = to_soa::<f32, 6>(&aabbs);
+    let aabb_soa: [&[f32]; 6] = core::array::from_fn(|i| aabb_soa[i].as_slice());
+    for i_itr in 0..20 {
+        let ray_org: [f32; 3] = core::array::from_fn(|_| rng.gen::<f32>() * 2. - 1.);
+        let mut ray_dir: [f32; 3] = core::array::from_fn(|_| rng.gen::<f32>() - 0.5);
+        if i_itr % 4 == 0 {
+            ray_dir[i_itr % 3] = 0.; // axis-parallel ray
+        }
+        let mut hits = vec![false; num_aabb];
+        let mut tmins = vec![0f32; num_aabb];
+        let mut tmaxs = vec![0f32; num_aabb];
+        aabb3_intersections_against_ray::<f32, 8>(
+            &aabb_soa, &ray_org, &ray_dir, &mut hits, &mut tmins, &mut tmaxs,
+        );
+        for (i_aabb, aabb) in aabbs.chunks(6).enumerate() {
+            let res = crate::aabb::intersections_against_ray::<f32, 3, 6>(
+                arrayref::array_ref![aabb, 0, 6],
+                &ray_org,
+                &ray_dir,
+            );
+            assert_eq!(res.is_some(), hits[i_aabb]);
+            let (tmin, tmax) = res.unwrap_or((0., 0.));
+            assert_eq!(tmin, tmins[i_aabb]);
+            assert_eq!(tmax, tmaxs[i_aabb]);
+        }
+    }
+}
+
+#[test]
+fn test_mat4_col_major_transform_homogeneous() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let mut transform: [f32; 16] = core::array::from_fn(|_| rng.gen::<f32>() - 0.5);
+    transform[11] = 1.;
+    transform[15] = -0.5;
+    let num_point = 77;
+    let mut xyz: Vec<f32> = (0..num_point * 3).map(|_| rng.gen::<f32>()).collect();
+    // point mapped to the infinity
+    xyz[0..3].copy_from_slice(&[0., 0., 0.5]);
+    let xyz_soa = to_soa::<f32, 3>(&xyz);
+    let xyz_soa: [&[f32]; 3] = core::array::from_fn(|i| xyz_soa[i].as_slice());
+    let mut out = [
+        vec![0f32; num_point],
+        vec![0f32; num_point],
+        vec![0f32; num_point],
+    ];
+    let mut valids = vec![false; num_point];
+    {
+        let [o0, o1, o2] = &mut out;
+        let mut xyz_out = [o0.as_mut_slice(), o1.as_mut_slice(), o2.as_mut_slice()];
+        mat4_col_major_transform_homogeneous::<f32, 16>(
+            &transform,
+            &xyz_soa,
+            &mut xyz_out,
+            &mut valids,
+        );
+    }
+    for (i_point, p) in xyz.chunks(3).enumerate() {
+        let q =
+            crate::mat4_col_major::transform_homogeneous(&transform, arrayref::array_ref![p, 0, 3]);
+        assert_eq!(q.is_some(), valids[i_point]);
+        let q = q.unwrap_or([0.; 3]);
+        assert_eq!(q, core::array::from_fn(|i_dim| out[i_dim][i_point]));
+    }
+    assert!(!valids[0]);
+}
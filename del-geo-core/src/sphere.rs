@@ -1,6 +1,9 @@
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
 pub fn intersection_ray<T>(rad: T, center: &[T; 3], ray_src: &[T; 3], ray_dir: &[T; 3]) -> Option<T>
 where
-    T: num_traits::Float + Copy + std::ops::MulAssign,
+    T: num_traits::Float + Copy + core::ops::MulAssign,
 {
     use crate::vec3::Vec3;
     // Solve t^2*d.d + 2*t*(o-p).d + (o-p).(o-p)-R^2 = 0
@@ -22,14 +25,28 @@ where
     }
 }
 
+/// overlap test between a solid sphere and an axis-aligned box
+pub fn is_intersect_aabb3<T>(rad: T, center: &[T; 3], aabb: &[T; 6]) -> bool
+where
+    T: num_traits::Float,
+{
+    let sqdist = (0..3).fold(T::zero(), |s, i| {
+        let d = (aabb[i] - center[i])
+            .max(center[i] - aabb[i + 3])
+            .max(T::zero());
+        s + d * d
+    });
+    sqdist <= rad * rad
+}
+
 pub fn area(r: f32) -> f32 {
-    r * r * 4f32 * std::f32::consts::PI
+    r * r * 4f32 * core::f32::consts::PI
 }
 
 /// <https://corysimon.github.io/articles/uniformdistn-on-sphere/>
 pub fn sample(rnd: &[f32; 2]) -> [f32; 3] {
     let phi = (1. - 2. * rnd[0]).acos();
-    let theta = 2. * std::f32::consts::PI * rnd[1];
+    let theta = 2. * core::f32::consts::PI * rnd[1];
     [theta.cos() * phi.sin(), theta.sin() * phi.sin(), phi.cos()]
 }
 
@@ -53,12 +70,12 @@ pub fn sample_where_another_sphere_is_visible(
     );
     assert!(cos_theta <= 1f32);
     let sin_theta = (1f32 - cos_theta * cos_theta).max(0f32).sqrt();
-    let phi = 2f32 * std::f32::consts::PI * unirand[1];
+    let phi = 2f32 * core::f32::consts::PI * unirand[1];
     // sample unit sphere assuming that the other light is in the z-axis direction
     let dir_lcl = [sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta];
     let mat3 = crate::mat3_col_major::transform_lcl2world_given_local_z(pos_light_center);
     let dir_world = crate::mat3_col_major::mult_vec(&mat3, &dir_lcl);
-    let pdf = 1f32 / (2f32 * std::f32::consts::PI * (1f32 - cos_theta_max));
+    let pdf = 1f32 / (2f32 * core::f32::consts::PI * (1f32 - cos_theta_max));
     (dir_world, pdf)
 }
 pub fn pdf_light_sample(light_center: &[f32; 3], light_rad: f32) -> f32 {
@@ -66,5 +83,5 @@ pub fn pdf_light_sample(light_center: &[f32; 3], light_rad: f32) -> f32 {
     let sin_theta_max_squared = light_rad * light_rad / light_center.squared_norm();
     assert!(sin_theta_max_squared > 0f32 && sin_theta_max_squared < 1f32);
     let cos_theta_max = (1f32 - sin_theta_max_squared).max(0.0).sqrt();
-    1f32 / (2f32 * std::f32::consts::PI * (1f32 - cos_theta_max))
+    1f32 / (2f32 * core::f32::consts::PI * (1f32 - cos_theta_max))
 }
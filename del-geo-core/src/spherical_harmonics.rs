@@ -1,5 +1,7 @@
+#[cfg(feature = "std")]
+use core::cmp::Ordering;
+use core::f64::consts::PI;
 use num_complex::{Complex, ComplexFloat};
-use std::{cmp::Ordering, f64::consts::PI};
 
 /// Calculate the normalization of the vector.
 #[inline]
@@ -260,6 +262,7 @@ pub fn sph_coeff_buffer(n: i8, x: f64, y: f64, z: f64) -> [f64; 100] {
 
 /// Calculate the coefficients of Legendre Polynomials in all orders <= n.
 /// Try to access the coefficient of x^m in P_l(x) by result[l][m].
+#[cfg(feature = "std")]
 fn legendre_coeff_vec(n: u64) -> Vec<Vec<f64>> {
     let mut res = vec![vec![1.0], vec![0.0, 1.0]];
 
@@ -320,6 +323,7 @@ pub fn get_legendre_poly_term_coeff(func_order: u32, term_order: u32) -> f64 {
 }
 
 /// Calculate the associated Legendre Polynomial P_l^m(x) for l >= |m|.
+#[cfg(feature = "std")]
 fn calculate_assoc_legendre_poly(l: u64, m: i64, x: f64) -> f64 {
     let m_abs = m.abs();
     assert!(l >= m_abs as u64);
@@ -355,6 +359,7 @@ fn calculate_assoc_legendre_poly(l: u64, m: i64, x: f64) -> f64 {
 /// However, u128 is not enough for the factorial calculation, so the maximum l is 11.
 /// Now that it is related to multiply and divide in big factorials, it has large opmitization potential.
 /// But optimization based on combination is hard in both mathematically and programmatically.
+#[cfg(feature = "std")]
 pub fn get_spherical_harmonics_coeff(l: i64, m: i64, x: f64, y: f64, z: f64) -> f64 {
     let m_abs = m.abs();
     assert!(l >= 0 && l >= m_abs);
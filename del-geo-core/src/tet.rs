@@ -15,3 +15,348 @@ where
         (v2[2] - v1[2]) * ((v3[0] - v1[0]) * (v4[1] - v1[1]) - (v4[0] - v1[0]) * (v3[1] - v1[1]));
     (a0 + a1 + a2) * 0.166_666_666_666_666_67_f64.as_()
 }
+
+// ----------------------------------------
+// below: hyperelastic energy of tetrahedral element
+
+/// deformation gradient `F = Ds Dm^-1` where `Ds` and `Dm` are the edge matrices
+/// `[p1-p0, p2-p0, p3-p0]` of the deformed and rest tetrahedra
+pub fn deformation_gradient<T>(p: &[[T; 3]; 4], p_rest: &[[T; 3]; 4]) -> [[T; 3]; 3]
+where
+    T: num_traits::Float,
+{
+    let ds = edge_matrix(p);
+    let dm_inv = crate::mat3_array_of_array::inverse(&edge_matrix(p_rest));
+    crate::mat3_array_of_array::matmul(&ds, &dm_inv)
+}
+
+fn edge_matrix<T>(p: &[[T; 3]; 4]) -> [[T; 3]; 3]
+where
+    T: num_traits::Float,
+{
+    core::array::from_fn(|i| core::array::from_fn(|c| p[c + 1][i] - p[0][i]))
+}
+
+/// rotation `R` and signed singular values of the polar decomposition `F = R S`.
+/// `R` does not contain reflection even if the element is inverted.
+#[allow(clippy::type_complexity)]
+fn polar_decomposition<T>(f: &[[T; 3]; 3]) -> ([[T; 3]; 3], [T; 3], [[T; 3]; 3], [[T; 3]; 3])
+where
+    T: num_traits::Float + core::ops::AddAssign + core::iter::Sum,
+{
+    let m: [T; 9] = core::array::from_fn(|k| f[k / 3][k % 3]);
+    let (u, s, v) = crate::mat3_row_major::svd(&m, 30);
+    let u: [[T; 3]; 3] = core::array::from_fn(|i| core::array::from_fn(|j| u[i * 3 + j]));
+    let v: [[T; 3]; 3] = core::array::from_fn(|i| core::array::from_fn(|j| v[i * 3 + j]));
+    let r = core::array::from_fn(|i| {
+        core::array::from_fn(|j| (0..3).fold(T::zero(), |a, k| a + u[i][k] * v[j][k]))
+    });
+    (r, s, u, v)
+}
+
+/// derivative of the rotation of the polar decomposition `dvec(R)/dvec(F)`.
+/// it is spanned by the three twist modes `U T_i V^T`.
+fn diff_rotation<T>(s: &[T; 3], u: &[[T; 3]; 3], v: &[[T; 3]; 3]) -> [[T; 9]; 9]
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let mut dr = [[T::zero(); 9]; 9];
+    for (a, b) in [(0, 1), (1, 2), (0, 2)] {
+        let denom = s[a] + s[b];
+        if denom <= T::epsilon() {
+            continue;
+        }
+        // twist mode `(u_b v_a^T - u_a v_b^T) / sqrt(2)`
+        let q: [T; 9] = core::array::from_fn(|k| {
+            let (i, j) = (k % 3, k / 3);
+            (u[i][b] * v[j][a] - u[i][a] * v[j][b]) / two.sqrt()
+        });
+        let l = two / denom;
+        for k in 0..9 {
+            for m in 0..9 {
+                dr[k][m] = dr[k][m] + l * q[k] * q[m];
+            }
+        }
+    }
+    dr
+}
+
+/// energy density of the linear elasticity `myu ε:ε + lambda/2 tr(ε)^2` where `ε = (F + F^T)/2 - I`
+/// # return
+/// `(energy density, first Piola–Kirchhoff stress, derivative of the stress)`.
+/// the derivative is w.r.t. `vec(F)` where the entry `F[i][j]` is at `i + 3j`
+pub fn density_linear<T>(f: &[[T; 3]; 3], lambda: T, myu: T) -> (T, [[T; 3]; 3], [[T; 9]; 9])
+where
+    T: num_traits::Float,
+{
+    let (one, two) = (T::one(), T::one() + T::one());
+    let half = one / two;
+    let eps: [[T; 3]; 3] = core::array::from_fn(|i| {
+        core::array::from_fn(|j| half * (f[i][j] + f[j][i]) - if i == j { one } else { T::zero() })
+    });
+    let tr = eps[0][0] + eps[1][1] + eps[2][2];
+    let sq = eps.iter().flatten().fold(T::zero(), |a, &e| a + e * e);
+    let psi = myu * sq + half * lambda * tr * tr;
+    let pk1 = core::array::from_fn(|i| {
+        core::array::from_fn(|j| {
+            two * myu * eps[i][j] + if i == j { lambda * tr } else { T::zero() }
+        })
+    });
+    let mut dpk1 = [[T::zero(); 9]; 9];
+    for i in 0..3 {
+        for j in 0..3 {
+            dpk1[i + 3 * j][i + 3 * j] = dpk1[i + 3 * j][i + 3 * j] + myu;
+            dpk1[i + 3 * j][j + 3 * i] = dpk1[i + 3 * j][j + 3 * i] + myu;
+            dpk1[i * 4][j * 4] = dpk1[i * 4][j * 4] + lambda;
+        }
+    }
+    (psi, pk1, dpk1)
+}
+
+/// energy density of the co-rotated elasticity `myu |F - R|^2 + lambda/2 (tr(R^T F) - 3)^2`
+/// where `R` is the rotation of the polar decomposition of `F`.
+/// see [`density_linear`] for the return values
+pub fn density_corotated<T>(f: &[[T; 3]; 3], lambda: T, myu: T) -> (T, [[T; 3]; 3], [[T; 9]; 9])
+where
+    T: num_traits::Float + core::ops::AddAssign + core::iter::Sum,
+{
+    let (one, two) = (T::one(), T::one() + T::one());
+    let three = two + one;
+    let (r, s, u, v) = polar_decomposition(f);
+    let dr = diff_rotation(&s, &u, &v);
+    let tr = s[0] + s[1] + s[2] - three;
+    let sq = (0..3).fold(T::zero(), |a, i| a + (s[i] - one) * (s[i] - one));
+    let psi = myu * sq + lambda / two * tr * tr;
+    let pk1 = core::array::from_fn(|i| {
+        core::array::from_fn(|j| two * myu * (f[i][j] - r[i][j]) + lambda * tr * r[i][j])
+    });
+    let vr: [T; 9] = core::array::from_fn(|k| r[k % 3][k / 3]);
+    let dpk1 = core::array::from_fn(|k| {
+        core::array::from_fn(|m| {
+            let id = if k == m { one } else { T::zero() };
+            two * myu * (id - dr[k][m]) + lambda * (vr[k] * vr[m] + tr * dr[k][m])
+        })
+    });
+    (psi, pk1, dpk1)
+}
+
+/// energy density of the as-rigid-as-possible (ARAP) energy `myu |F - R|^2`
+/// where `R` is the rotation of the polar decomposition of `F`.
+/// see [`density_linear`] for the return values
+pub fn density_arap<T>(f: &[[T; 3]; 3], myu: T) -> (T, [[T; 3]; 3], [[T; 9]; 9])
+where
+    T: num_traits::Float + core::ops::AddAssign + core::iter::Sum,
+{
+    density_corotated(f, T::zero(), myu)
+}
+
+/// energy density of the stable Neo-Hookean material (Smith et al. 2018)
+/// `myu/2 (tr(F^T F) - 3) + lambda/2 (J - α)^2` where `J = det(F)` and `α = 1 + myu / lambda`.
+/// the constant is shifted such that the energy is zero at the rest shape.
+/// see [`density_linear`] for the return values
+pub fn density_stable_neo_hookean<T>(
+    f: &[[T; 3]; 3],
+    lambda: T,
+    myu: T,
+) -> (T, [[T; 3]; 3], [[T; 9]; 9])
+where
+    T: num_traits::Float,
+{
+    let (one, two) = (T::one(), T::one() + T::one());
+    let three = two + one;
+    let alpha = one + myu / lambda;
+    let vf: [T; 9] = core::array::from_fn(|k| f[k % 3][k / 3]);
+    let (j, dj, ddj) = crate::wdwddw::det_slots(&vf, 0, 3, 6);
+    let ic = vf.iter().fold(T::zero(), |a, &x| a + x * x);
+    let psi = myu / two * (ic - three)
+        + lambda / two * ((j - alpha) * (j - alpha) - (one - alpha) * (one - alpha));
+    let pk1 = core::array::from_fn(|i| {
+        core::array::from_fn(|jj| myu * f[i][jj] + lambda * (j - alpha) * dj[i + 3 * jj])
+    });
+    let dpk1 = core::array::from_fn(|k| {
+        core::array::from_fn(|m| {
+            let id = if k == m { myu } else { T::zero() };
+            id + lambda * (dj[k] * dj[m] + (j - alpha) * ddj[k][m])
+        })
+    });
+    (psi, pk1, dpk1)
+}
+
+/// integrate the energy density over the tetrahedron
+/// and compute the gradient and hessian w.r.t. the vertex positions
+fn wdwddw_element<T, F>(
+    p: &[[T; 3]; 4],
+    p_rest: &[[T; 3]; 4],
+    density: F,
+    is_project_psd: bool,
+) -> (T, [T; 12], [[T; 12]; 12])
+where
+    T: num_traits::Float,
+    F: Fn(&[[T; 3]; 3]) -> (T, [[T; 3]; 3], [[T; 9]; 9]),
+{
+    let six = T::from(6).unwrap();
+    let (det, dm_inv) = crate::mat3_array_of_array::det_inv(&edge_matrix(p_rest));
+    let vol = det.abs() / six;
+    // `F[i][j] = sum_k w[k][j] * p_k[i]`
+    let w: [[T; 3]; 4] = core::array::from_fn(|k| {
+        core::array::from_fn(|j| match k {
+            0 => -(dm_inv[0][j] + dm_inv[1][j] + dm_inv[2][j]),
+            _ => dm_inv[k - 1][j],
+        })
+    });
+    let f = crate::mat3_array_of_array::matmul(&edge_matrix(p), &dm_inv);
+    let (psi, pk1, dpk1) = density(&f);
+    let dpk1 = if is_project_psd {
+        crate::matn::project_to_positive_semidefinite(&dpk1)
+    } else {
+        dpk1
+    };
+    // chain rule for the linear map from the vertex positions to `vec(F)`
+    let mut jac = [[T::zero(); 12]; 9];
+    for j in 0..3 {
+        for (k, wk) in w.iter().enumerate() {
+            for i in 0..3 {
+                jac[i + 3 * j][k * 3 + i] = wk[j];
+            }
+        }
+    }
+    let dpsi: [T; 9] = core::array::from_fn(|k| pk1[k % 3][k / 3]);
+    let (dw, ddw) = crate::wdwddw::compose(&[dpsi], &[dpk1], &jac, &[[[T::zero(); 12]; 12]; 9]);
+    let dw = dw[0].map(|v| v * vol);
+    let ddw = ddw[0].map(|row| row.map(|v| v * vol));
+    (psi * vol, dw, ddw)
+}
+
+/// elastic energy of the linear elasticity material
+/// * `p` - deformed positions of the vertices
+/// * `p_rest` - rest positions of the vertices
+/// * `is_project_psd` - project the hessian of the energy density to positive semi-definite
+/// # return
+/// `(energy, gradient, hessian)` w.r.t. the `12` coordinates of the vertices
+pub fn wdwddw_linear<T>(
+    p: &[[T; 3]; 4],
+    p_rest: &[[T; 3]; 4],
+    lambda: T,
+    myu: T,
+    is_project_psd: bool,
+) -> (T, [T; 12], [[T; 12]; 12])
+where
+    T: num_traits::Float,
+{
+    wdwddw_element(
+        p,
+        p_rest,
+        |f| density_linear(f, lambda, myu),
+        is_project_psd,
+    )
+}
+
+/// elastic energy of the co-rotated elasticity material. see [`wdwddw_linear`] for the arguments
+pub fn wdwddw_corotated<T>(
+    p: &[[T; 3]; 4],
+    p_rest: &[[T; 3]; 4],
+    lambda: T,
+    myu: T,
+    is_project_psd: bool,
+) -> (T, [T; 12], [[T; 12]; 12])
+where
+    T: num_traits::Float + core::ops::AddAssign + core::iter::Sum,
+{
+    wdwddw_element(
+        p,
+        p_rest,
+        |f| density_corotated(f, lambda, myu),
+        is_project_psd,
+    )
+}
+
+/// as-rigid-as-possible energy. see [`wdwddw_linear`] for the arguments
+pub fn wdwddw_arap<T>(
+    p: &[[T; 3]; 4],
+    p_rest: &[[T; 3]; 4],
+    stiffness: T,
+    is_project_psd: bool,
+) -> (T, [T; 12], [[T; 12]; 12])
+where
+    T: num_traits::Float + core::ops::AddAssign + core::iter::Sum,
+{
+    wdwddw_element(p, p_rest, |f| density_arap(f, stiffness), is_project_psd)
+}
+
+/// elastic energy of the stable Neo-Hookean material. see [`wdwddw_linear`] for the arguments
+pub fn wdwddw_stable_neo_hookean<T>(
+    p: &[[T; 3]; 4],
+    p_rest: &[[T; 3]; 4],
+    lambda: T,
+    myu: T,
+    is_project_psd: bool,
+) -> (T, [T; 12], [[T; 12]; 12])
+where
+    T: num_traits::Float,
+{
+    wdwddw_element(
+        p,
+        p_rest,
+        |f| density_stable_neo_hookean(f, lambda, myu),
+        is_project_psd,
+    )
+}
+
+#[test]
+fn test_wdwddw_hyperelastic() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let p_rest = [
+        [0.1, 0.0, 0.2f64],
+        [1.2, 0.1, 0.0],
+        [0.2, 0.9, 0.1],
+        [0.0, 0.2, 1.1],
+    ];
+    type Kernel = fn(&[[f64; 3]; 4], &[[f64; 3]; 4], bool) -> (f64, [f64; 12], [[f64; 12]; 12]);
+    let kernels: [Kernel; 4] = [
+        |p, q, b| wdwddw_linear(p, q, 1.3, 0.7, b),
+        |p, q, b| wdwddw_corotated(p, q, 1.3, 0.7, b),
+        |p, q, b| wdwddw_arap(p, q, 0.7, b),
+        |p, q, b| wdwddw_stable_neo_hookean(p, q, 1.3, 0.7, b),
+    ];
+    for kernel in kernels {
+        // zero energy and force at the rest shape
+        let (w, dw, _) = kernel(&p_rest, &p_rest, false);
+        assert!(w.abs() < 1.0e-12);
+        assert!(dw.iter().all(|v| v.abs() < 1.0e-10));
+        for _itr in 0..10 {
+            let p: [[f64; 3]; 4] = core::array::from_fn(|i| {
+                core::array::from_fn(|j| p_rest[i][j] + 0.3 * (rng.gen::<f64>() - 0.5))
+            });
+            let x0: [f64; 12] = core::array::from_fn(|k| p[k / 3][k % 3]);
+            crate::wdwddw::check_derivatives(&x0, |x| {
+                let p = core::array::from_fn(|i| core::array::from_fn(|j| x[i * 3 + j]));
+                kernel(&p, &p_rest, false)
+            });
+            // projected hessian is positive semi-definite
+            let (_, _, ddw) = kernel(&p, &p_rest, true);
+            let (lambdas, _) = crate::matn::eigen_decomposition_sym(&ddw, 100);
+            assert!(lambdas.iter().all(|&l| l > -1.0e-10), "{:?}", lambdas);
+        }
+    }
+}
+
+#[test]
+fn test_deformation_gradient() {
+    let p_rest = [
+        [0.1, 0.0, 0.2f64],
+        [1.2, 0.1, 0.0],
+        [0.2, 0.9, 0.1],
+        [0.0, 0.2, 1.1],
+    ];
+    let a = [[1.1, 0.2, -0.3], [0.1, 0.8, 0.2], [-0.2, 0.3, 1.4f64]];
+    let p = p_rest.map(|x| core::array::from_fn(|i| (0..3).fold(0.5, |s, j| s + a[i][j] * x[j])));
+    let f = deformation_gradient(&p, &p_rest);
+    for i in 0..3 {
+        for j in 0..3 {
+            assert!((f[i][j] - a[i][j]).abs() < 1.0e-10);
+        }
+    }
+}
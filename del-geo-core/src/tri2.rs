@@ -133,6 +133,119 @@ where
     Some((a0 * sum_area_inv, a1 * sum_area_inv, a2 * sum_area_inv))
 }
 
+/// overlap test between a triangle and an axis-aligned box `[x_min, y_min, x_max, y_max]`
+/// using the separating axis theorem. touching is regarded as overlap
+pub fn is_intersect_aabb2<Real>(
+    p0: &[Real; 2],
+    p1: &[Real; 2],
+    p2: &[Real; 2],
+    aabb: &[Real; 4],
+) -> bool
+where
+    Real: num_traits::Float,
+{
+    let p = [p0, p1, p2];
+    for i in 0..2 {
+        if p0[i].min(p1[i]).min(p2[i]) > aabb[i + 2] || p0[i].max(p1[i]).max(p2[i]) < aabb[i] {
+            return false;
+        }
+    }
+    let corners = [
+        [aabb[0], aabb[1]],
+        [aabb[2], aabb[1]],
+        [aabb[0], aabb[3]],
+        [aabb[2], aabb[3]],
+    ];
+    let sign = area(p0, p1, p2).signum();
+    for i in 0..3 {
+        let (q0, q1) = (p[i], p[(i + 1) % 3]);
+        // all the corners are on the outer side of the edge
+        if corners
+            .iter()
+            .all(|c| area(q0, q1, c) * sign < Real::zero())
+        {
+            return false;
+        }
+    }
+    true
+}
+
+/// conservative rasterization of a triangle.
+/// list all the pixels overlapping with the triangle where the pixel `(ix, iy)` covers
+/// the region `[ix, ix+1] x [iy, iy+1]`
+/// # return
+/// pixel indices `iy * img_width + ix`
+#[cfg(feature = "std")]
+pub fn overlapping_pixels_conservative<Real>(
+    (img_width, img_height): (usize, usize),
+    p0: &[Real; 2],
+    p1: &[Real; 2],
+    p2: &[Real; 2],
+) -> Vec<usize>
+where
+    Real: num_traits::Float,
+{
+    let range = |i: usize, n: usize| {
+        let lo = p0[i].min(p1[i]).min(p2[i]).floor().max(Real::zero());
+        let hi = p0[i].max(p1[i]).max(p2[i]).floor();
+        let hi = hi.min(Real::from(n).unwrap() - Real::one());
+        (lo.to_usize().unwrap_or(0), hi.to_i64().unwrap_or(-1))
+    };
+    let (ix0, ix1) = range(0, img_width);
+    let (iy0, iy1) = range(1, img_height);
+    let mut res = vec![];
+    for iy in iy0..(iy1 + 1).max(0) as usize {
+        for ix in ix0..(ix1 + 1).max(0) as usize {
+            let x = Real::from(ix).unwrap();
+            let y = Real::from(iy).unwrap();
+            let aabb = [x, y, x + Real::one(), y + Real::one()];
+            if is_intersect_aabb2(p0, p1, p2, &aabb) {
+                res.push(iy * img_width + ix);
+            }
+        }
+    }
+    res
+}
+
+#[test]
+fn test_overlapping_pixels_conservative() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let (w, h) = (16usize, 12usize);
+    for _itr in 0..100 {
+        let p: [[f64; 2]; 3] =
+            core::array::from_fn(|_| [rng.gen::<f64>() * 20. - 2., rng.gen::<f64>() * 16. - 2.]);
+        let pixels = overlapping_pixels_conservative((w, h), &p[0], &p[1], &p[2]);
+        let pixels: std::collections::BTreeSet<usize> = pixels.into_iter().collect();
+        // every point of the triangle inside the image is in a listed pixel
+        for _ in 0..1000 {
+            let (r0, r1) = (rng.gen::<f64>(), rng.gen::<f64>());
+            let (r0, r1) = if r0 + r1 > 1. {
+                (1. - r0, 1. - r1)
+            } else {
+                (r0, r1)
+            };
+            let q: [f64; 2] =
+                core::array::from_fn(|i| p[0][i] * (1. - r0 - r1) + p[1][i] * r0 + p[2][i] * r1);
+            if q[0] < 0. || q[1] < 0. || q[0] >= w as f64 || q[1] >= h as f64 {
+                continue;
+            }
+            let i_pix = (q[1] as usize) * w + (q[0] as usize);
+            assert!(pixels.contains(&i_pix));
+        }
+    }
+    // a small triangle inside a pixel
+    let pixels = overlapping_pixels_conservative((4, 4), &[1.2, 1.2], &[1.8, 1.3], &[1.5, 1.7]);
+    assert_eq!(pixels, vec![5]);
+    // a triangle covering the image
+    let pixels = overlapping_pixels_conservative((4, 4), &[-1., -1.], &[10., -1.], &[-1., 10.]);
+    assert_eq!(pixels, (0..16).collect::<Vec<_>>());
+    // a triangle in the upper-left half touches the pixels with `ix + iy <= 2`
+    let pixels = overlapping_pixels_conservative((4, 4), &[0., 0.], &[2.9, 0.], &[0., 2.9]);
+    assert_eq!(pixels, vec![0, 1, 2, 4, 5, 8]);
+}
+
 // -------------------------------------------
 
 pub struct Tri2<'a, Real> {
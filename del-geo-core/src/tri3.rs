@@ -33,7 +33,7 @@ where
 /// normal vector of a 3D triangle (coordinates given by stack-allocated arrays)
 pub fn normal<T>(v1: &[T; 3], v2: &[T; 3], v3: &[T; 3]) -> [T; 3]
 where
-    T: std::ops::Sub<Output = T> + std::ops::Mul<Output = T> + std::ops::Sub + Copy,
+    T: core::ops::Sub<Output = T> + core::ops::Mul<Output = T> + core::ops::Sub + Copy,
 {
     [
         (v2[1] - v1[1]) * (v3[2] - v1[2]) - (v2[2] - v1[2]) * (v3[1] - v1[1]),
@@ -338,14 +338,445 @@ where
     ]
 }
 
+/// overlap test between a triangle and an axis-aligned box
+/// using the separating axis theorem (Akenine-Möller 2001).
+/// touching is regarded as overlap
+pub fn is_intersect_aabb3<T>(p0: &[T; 3], p1: &[T; 3], p2: &[T; 3], aabb: &[T; 6]) -> bool
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let c = crate::aabb3::center(aabb);
+    let h = crate::aabb3::size(aabb).map(|v| v / (T::one() + T::one()));
+    // move the box center to the origin
+    let v = [p0.sub(&c), p1.sub(&c), p2.sub(&c)];
+    let is_separated = |axis: &[T; 3]| {
+        let r = h[0] * axis[0].abs() + h[1] * axis[1].abs() + h[2] * axis[2].abs();
+        let d = [axis.dot(&v[0]), axis.dot(&v[1]), axis.dot(&v[2])];
+        d[0].min(d[1]).min(d[2]) > r || d[0].max(d[1]).max(d[2]) < -r
+    };
+    // the face normals of the box
+    let basis = [
+        [T::one(), T::zero(), T::zero()],
+        [T::zero(), T::one(), T::zero()],
+        [T::zero(), T::zero(), T::one()],
+    ];
+    if basis.iter().any(is_separated) {
+        return false;
+    }
+    // the normal of the triangle
+    let e = [v[1].sub(&v[0]), v[2].sub(&v[1]), v[0].sub(&v[2])];
+    if is_separated(&e[0].cross(&e[1])) {
+        return false;
+    }
+    // the cross products of the edges of the triangle and the box
+    for ei in e.iter() {
+        for bj in basis.iter() {
+            if is_separated(&ei.cross(bj)) {
+                return false;
+            }
+        }
+    }
+    true
+}
+
+#[test]
+fn test_is_intersect_aabb3() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb = [0.0, 0.0, 0.0, 1.0, 1.0, 1.0f64];
+    for _itr in 0..1000 {
+        let p: [[f64; 3]; 3] =
+            core::array::from_fn(|_| core::array::from_fn(|_| rng.gen::<f64>() * 3. - 1.));
+        let res = is_intersect_aabb3(&p[0], &p[1], &p[2], &aabb);
+        // a sample point of the triangle inside the box implies the overlap
+        for _ in 0..100 {
+            let (r0, r1) = (rng.gen::<f64>(), rng.gen::<f64>());
+            let (r0, r1) = if r0 + r1 > 1. {
+                (1. - r0, 1. - r1)
+            } else {
+                (r0, r1)
+            };
+            let q: [f64; 3] =
+                core::array::from_fn(|i| p[0][i] * (1. - r0 - r1) + p[1][i] * r0 + p[2][i] * r1);
+            if q.iter().all(|&x| (0. ..=1.).contains(&x)) {
+                assert!(res);
+            }
+        }
+    }
+    // a large triangle cutting through the box without the vertices inside
+    let p = [[-5., -5., 0.5], [5., -5., 0.5], [0., 5., 0.5]];
+    assert!(is_intersect_aabb3(&p[0], &p[1], &p[2], &aabb));
+    // a triangle near the corner that is separated by the cross-product axis
+    let p = [[1.5, 0.6, 0.5], [0.6, 1.5, 0.5], [1.5, 1.5, 0.5]];
+    assert!(!is_intersect_aabb3(&p[0], &p[1], &p[2], &aabb));
+}
+
+// ----------------------------
+// below: elastic energy of cloth and thin shell
+
+/// membrane energy of a triangle for the energy density given as the function of
+/// the right Cauchy-Green tensor `C = [[a, c], [c, b]]`
+/// * `density` - returns the energy density and its derivatives w.r.t. `[a, b, c]`
+#[allow(clippy::type_complexity)]
+fn wdwddw_membrane<T, F>(
+    p: &[[T; 3]; 3],
+    q: &[[T; 2]; 3],
+    density: F,
+    is_project_psd: bool,
+) -> (T, [T; 9], [[T; 9]; 9])
+where
+    T: num_traits::Float,
+    F: Fn(T, T, T) -> (T, [T; 3], [[T; 3]; 3]),
+{
+    let half = T::one() / (T::one() + T::one());
+    // inverse of the rest shape matrix `[q1-q0, q2-q0]`
+    let dm = [
+        [q[1][0] - q[0][0], q[2][0] - q[0][0]],
+        [q[1][1] - q[0][1], q[2][1] - q[0][1]],
+    ];
+    let det = dm[0][0] * dm[1][1] - dm[0][1] * dm[1][0];
+    let area = det.abs() * half;
+    let inv = [
+        [dm[1][1] / det, -dm[0][1] / det],
+        [-dm[1][0] / det, dm[0][0] / det],
+    ];
+    // the columns of the deformation gradient `f_a = sum_k w[k][a] * p_k`
+    let w = [
+        [-inv[0][0] - inv[1][0], -inv[0][1] - inv[1][1]],
+        [inv[0][0], inv[0][1]],
+        [inv[1][0], inv[1][1]],
+    ];
+    let f: [T; 6] = core::array::from_fn(|ai| {
+        let (a, i) = (ai / 3, ai % 3);
+        w[0][a] * p[0][i] + w[1][a] * p[1][i] + w[2][a] * p[2][i]
+    });
+    // `a = f0.f0`, `b = f1.f1`, `c = f0.f1`
+    let (a, da, dda) = crate::wdwddw::dot_slots(&f, 0, 0);
+    let (b, db, ddb) = crate::wdwddw::dot_slots(&f, 3, 3);
+    let (c, dc, ddc) = crate::wdwddw::dot_slots(&f, 0, 3);
+    let (psi, dpsi, ddpsi) = density(a, b, c);
+    let (dpsi_df, ddpsi_df) =
+        crate::wdwddw::compose(&[dpsi], &[ddpsi], &[da, db, dc], &[dda, ddb, ddc]);
+    // chain rule for the linear map from the vertex positions to `f`
+    let mut jac = [[T::zero(); 9]; 6];
+    for a in 0..2 {
+        for (k, wk) in w.iter().enumerate() {
+            for i in 0..3 {
+                jac[a * 3 + i][k * 3 + i] = wk[a];
+            }
+        }
+    }
+    let (dw, ddw) = crate::wdwddw::compose(&dpsi_df, &ddpsi_df, &jac, &[[[T::zero(); 9]; 9]; 6]);
+    let dw = dw[0].map(|v| v * area);
+    let ddw = ddw[0].map(|row| row.map(|v| v * area));
+    let ddw = if is_project_psd {
+        crate::matn::project_to_positive_semidefinite(&ddw)
+    } else {
+        ddw
+    };
+    (psi * area, dw, ddw)
+}
+
+/// membrane energy of the Saint Venant–Kirchhoff material
+/// * `p` - deformed positions of the vertices
+/// * `q` - rest positions of the vertices in 2D
+/// * `lambda`, `myu` - Lamé's parameters
+/// * `is_project_psd` - project the Hessian to positive semi-definite
+/// * Return - `(energy, gradient, hessian)` w.r.t. `[p0, p1, p2]`
+#[allow(clippy::type_complexity)]
+pub fn wdwddw_membrane_stvk<T>(
+    p: &[[T; 3]; 3],
+    q: &[[T; 2]; 3],
+    lambda: T,
+    myu: T,
+    is_project_psd: bool,
+) -> (T, [T; 9], [[T; 9]; 9])
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let two = one + one;
+    let half = one / two;
+    let quarter = half * half;
+    let density = |a: T, b: T, c: T| {
+        // Green-Lagrange strain `E = (C - I) / 2`
+        let (e00, e11, e01) = ((a - one) * half, (b - one) * half, c * half);
+        let tr = e00 + e11;
+        let psi = myu * (e00 * e00 + e11 * e11 + two * e01 * e01) + lambda * half * tr * tr;
+        let dpsi = [
+            (two * myu * e00 + lambda * tr) * half,
+            (two * myu * e11 + lambda * tr) * half,
+            two * myu * e01,
+        ];
+        let d = (two * myu + lambda) * quarter;
+        let o = lambda * quarter;
+        let ddpsi = [
+            [d, o, T::zero()],
+            [o, d, T::zero()],
+            [T::zero(), T::zero(), myu],
+        ];
+        (psi, dpsi, ddpsi)
+    };
+    wdwddw_membrane(p, q, density, is_project_psd)
+}
+
+/// membrane energy of the compressible Neo-Hookean material
+/// `myu/2 (tr(C) - 2) - myu ln(J) + lambda/2 ln(J)^2` where `J` is the area ratio
+/// * `p` - deformed positions of the vertices
+/// * `q` - rest positions of the vertices in 2D
+/// * `lambda`, `myu` - Lamé's parameters
+/// * `is_project_psd` - project the Hessian to positive semi-definite
+/// * Return - `(energy, gradient, hessian)` w.r.t. `[p0, p1, p2]`
+#[allow(clippy::type_complexity)]
+pub fn wdwddw_membrane_neo_hookean<T>(
+    p: &[[T; 3]; 3],
+    q: &[[T; 2]; 3],
+    lambda: T,
+    myu: T,
+    is_project_psd: bool,
+) -> (T, [T; 9], [[T; 9]; 9])
+where
+    T: num_traits::Float,
+{
+    let one = T::one();
+    let zero = T::zero();
+    let two = one + one;
+    let half = one / two;
+    let density = |a: T, b: T, c: T| {
+        // `ln(J) = ln(det(C)) / 2`
+        let d = a * b - c * c;
+        let l = d.ln() * half;
+        let dd = [b, a, -two * c];
+        let ddd = [[zero, one, zero], [one, zero, zero], [zero, zero, -two]];
+        let dl: [T; 3] = core::array::from_fn(|i| dd[i] / (two * d));
+        let ddl: [[T; 3]; 3] = core::array::from_fn(|i| {
+            core::array::from_fn(|j| ddd[i][j] / (two * d) - dd[i] * dd[j] / (two * d * d))
+        });
+        let psi = myu * half * (a + b - two) - myu * l + lambda * half * l * l;
+        let s = lambda * l - myu;
+        let dpsi = [myu * half + s * dl[0], myu * half + s * dl[1], s * dl[2]];
+        let ddpsi = core::array::from_fn(|i| {
+            core::array::from_fn(|j| lambda * dl[i] * dl[j] + s * ddl[i][j])
+        });
+        (psi, dpsi, ddpsi)
+    };
+    wdwddw_membrane(p, q, density, is_project_psd)
+}
+
+#[test]
+fn test_wdwddw_membrane() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    let q = [[0.1f64, 0.2], [1.2, 0.1], [0.4, 0.9]];
+    for _ in 0..10 {
+        // energy is zero at the rest shape
+        let p0 = [[0f64, 0., 0.], [1.1, -0.1, 0.], [0.3, 0.7, 0.]];
+        let (w, dw, _) = wdwddw_membrane_stvk(&p0, &q, 1.3, 0.8, false);
+        assert!(w.abs() < 1.0e-10 && dw.iter().all(|v| v.abs() < 1.0e-10));
+        let (w, dw, _) = wdwddw_membrane_neo_hookean(&p0, &q, 1.3, 0.8, false);
+        assert!(w.abs() < 1.0e-10 && dw.iter().all(|v| v.abs() < 1.0e-10));
+        // random deformation
+        let x0: [f64; 9] = core::array::from_fn(|i| p0[i / 3][i % 3] + 0.3 * rng.gen::<f64>());
+        let to_p = |x: &[f64; 9]| -> [[f64; 3]; 3] {
+            core::array::from_fn(|k| *arrayref::array_ref![x, k * 3, 3])
+        };
+        crate::wdwddw::check_derivatives(&x0, |x| {
+            wdwddw_membrane_stvk(&to_p(x), &q, 1.3, 0.8, false)
+        });
+        crate::wdwddw::check_derivatives(&x0, |x| {
+            wdwddw_membrane_neo_hookean(&to_p(x), &q, 1.3, 0.8, false)
+        });
+        let (_, _, ddw) = wdwddw_membrane_stvk(&to_p(&x0), &q, 1.3, 0.8, true);
+        let (lambda, _) = crate::matn::eigen_decomposition_sym(&ddw, 50);
+        assert!(lambda.iter().all(|&l| l > -1.0e-10));
+    }
+}
+
+/// dihedral angle of the two triangles `(p0, p1, p2)` and `(p1, p0, p3)` sharing the edge `(p0, p1)`.
+/// zero if the triangles are coplanar, and positive if the hinge is convex,
+/// i.e., the triangles fold away from their normals.
+/// * Return - `(angle, gradient, hessian)` w.r.t. `[p0, p1, p2, p3]`
+#[allow(clippy::type_complexity)]
+pub fn wdwddw_dihedral_angle<T>(p: &[[T; 3]; 4]) -> (T, [T; 12], [[T; 12]; 12])
+where
+    T: num_traits::Float,
+{
+    use crate::vec3::Vec3;
+    let zero = T::zero();
+    let one = T::one();
+    // edge vectors `[a, b, c] = [p1 - p0, p2 - p0, p3 - p1]`
+    let a = p[1].sub(&p[0]);
+    let b = p[2].sub(&p[0]);
+    let c = p[3].sub(&p[1]);
+    let mut jac_z = [[zero; 12]; 9];
+    for i in 0..3 {
+        jac_z[i][i] = -one;
+        jac_z[i][3 + i] = one;
+        jac_z[3 + i][i] = -one;
+        jac_z[3 + i][6 + i] = one;
+        jac_z[6 + i][3 + i] = -one;
+        jac_z[6 + i][9 + i] = one;
+    }
+    // normals `n0 = a x b`, `n1 = c x a` and the hinge edge `e = a`
+    let n0 = a.cross(&b);
+    let n1 = c.cross(&a);
+    let y = [n0[0], n0[1], n0[2], n1[0], n1[1], n1[2], a[0], a[1], a[2]];
+    let skew = |v: &[T; 3]| {
+        [
+            [zero, -v[2], v[1]],
+            [v[2], zero, -v[0]],
+            [-v[1], v[0], zero],
+        ]
+    };
+    // `epsilon_kij = (k - i)(i - j)(j - k) / 2` for the indices in `0..3`
+    let levi_civita = |k: usize, i: usize, j: usize| {
+        let (k, i, j) = (k as i64, i as i64, j as i64);
+        T::from((k - i) * (i - j) * (j - k) / 2).unwrap()
+    };
+    let mut jac_y = [[zero; 9]; 9];
+    let mut hess_y = [[[zero; 9]; 9]; 9];
+    let (sa, sb, sc) = (skew(&a), skew(&b), skew(&c));
+    for k in 0..3 {
+        for i in 0..3 {
+            // d(a x b)/da = -[b]x, d(a x b)/db = [a]x
+            jac_y[k][i] = -sb[k][i];
+            jac_y[k][3 + i] = sa[k][i];
+            // d(c x a)/da = [c]x, d(c x a)/dc = -[a]x
+            jac_y[3 + k][i] = sc[k][i];
+            jac_y[3 + k][6 + i] = -sa[k][i];
+            jac_y[6 + k][i] = if i == k { one } else { zero };
+            for j in 0..3 {
+                let e = levi_civita(k, i, j);
+                hess_y[k][i][3 + j] = e;
+                hess_y[k][3 + j][i] = e;
+                hess_y[3 + k][6 + i][j] = e;
+                hess_y[3 + k][j][6 + i] = e;
+            }
+        }
+    }
+    let (jac_y, hess_y) = crate::wdwddw::compose(&jac_y, &hess_y, &jac_z, &[[[zero; 12]; 12]; 9]);
+    // angle = atan2(det(n0, n1, e), |e| (n0.n1))
+    let (s, ds, dds) = crate::wdwddw::det_slots(&y, 0, 3, 6);
+    let (g, dg, ddg) = crate::wdwddw::dot_slots(&y, 0, 3);
+    let l = a.norm();
+    let mut dl = [zero; 9];
+    let mut ddl = [[zero; 9]; 9];
+    for i in 0..3 {
+        dl[6 + i] = a[i] / l;
+        for j in 0..3 {
+            let delta = if i == j { one } else { zero };
+            ddl[6 + i][6 + j] = (delta - a[i] * a[j] / (l * l)) / l;
+        }
+    }
+    let cc = l * g;
+    let dcc: [T; 9] = core::array::from_fn(|i| g * dl[i] + l * dg[i]);
+    let ddcc: [[T; 9]; 9] = core::array::from_fn(|i| {
+        core::array::from_fn(|j| dl[i] * dg[j] + dg[i] * dl[j] + g * ddl[i][j] + l * ddg[i][j])
+    });
+    let (theta, dtheta, ddtheta) = crate::wdwddw::atan2((s, &ds, &dds), (cc, &dcc, &ddcc));
+    let (dw, ddw) = crate::wdwddw::compose(&[dtheta], &[ddtheta], &jac_y, &hess_y);
+    (theta, dw[0], ddw[0])
+}
+
+/// bending energy of the discrete shell `stiffness * 3 |e|^2 / (A0 + A1) * (theta - theta_rest)^2`
+/// where `theta` is the dihedral angle (see [`wdwddw_dihedral_angle`]),
+/// `|e|` is the length of the hinge edge and `A0`, `A1` are the areas of the two triangles.
+/// `|e|`, `A0`, `A1` and `theta_rest` are computed from the rest positions `p_rest`.
+/// * `is_project_psd` - project the Hessian to positive semi-definite
+/// * Return - `(energy, gradient, hessian)` w.r.t. `[p0, p1, p2, p3]`
+///
+/// Grinspun et al. "Discrete Shells" SCA 2003
+#[allow(clippy::type_complexity)]
+pub fn wdwddw_dihedral_bending<T>(
+    p: &[[T; 3]; 4],
+    p_rest: &[[T; 3]; 4],
+    stiffness: T,
+    is_project_psd: bool,
+) -> (T, [T; 12], [[T; 12]; 12])
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let three = two + T::one();
+    let (theta_rest, _, _) = wdwddw_dihedral_angle(p_rest);
+    let l = crate::vec3::distance(&p_rest[0], &p_rest[1]);
+    let area0 = area(&p_rest[0], &p_rest[1], &p_rest[2]);
+    let area1 = area(&p_rest[1], &p_rest[0], &p_rest[3]);
+    let k = stiffness * three * l * l / (area0 + area1);
+    let (theta, dtheta, ddtheta) = wdwddw_dihedral_angle(p);
+    let diff = theta - theta_rest;
+    let w = k * diff * diff;
+    let dw = dtheta.map(|v| two * k * diff * v);
+    let ddw: [[T; 12]; 12] = core::array::from_fn(|i| {
+        core::array::from_fn(|j| two * k * (dtheta[i] * dtheta[j] + diff * ddtheta[i][j]))
+    });
+    let ddw = if is_project_psd {
+        crate::matn::project_to_positive_semidefinite(&ddw)
+    } else {
+        ddw
+    };
+    (w, dw, ddw)
+}
+
+#[test]
+fn test_wdwddw_dihedral_bending() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    // hinge folded by 90 degrees
+    let p = [[0f64, 0., 0.], [1., 0., 0.], [0.5, 1., 0.], [0.5, 0., 1.]];
+    let (theta, _, _) = wdwddw_dihedral_angle(&p);
+    assert!((theta + core::f64::consts::FRAC_PI_2).abs() < 1.0e-10);
+    let p_rest = [[0f64, 0., 0.], [1., 0., 0.], [0.5, 1., 0.], [0.5, -1., 0.]];
+    let (theta, _, _) = wdwddw_dihedral_angle(&p_rest);
+    assert!(theta.abs() < 1.0e-10);
+    for _ in 0..10 {
+        let x0: [f64; 12] =
+            core::array::from_fn(|i| p_rest[i / 3][i % 3] + 0.4 * rng.gen::<f64>() - 0.2);
+        let to_p = |x: &[f64; 12]| -> [[f64; 3]; 4] {
+            core::array::from_fn(|k| *arrayref::array_ref![x, k * 3, 3])
+        };
+        crate::wdwddw::check_derivatives(&x0, |x| wdwddw_dihedral_angle(&to_p(x)));
+        crate::wdwddw::check_derivatives(&x0, |x| {
+            wdwddw_dihedral_bending(&to_p(x), &p_rest, 1.3, false)
+        });
+        let (_, _, ddw) = wdwddw_dihedral_bending(&to_p(&x0), &p_rest, 1.3, true);
+        let (lambda, _) = crate::matn::eigen_decomposition_sym(&ddw, 50);
+        assert!(lambda.iter().all(|&l| l > -1.0e-10));
+    }
+}
+
 // -------------------------
 
+/// borrowed view of a triangle.
+/// It serializes the same as `[[Real; 3]; 3]`, so deserialize into the array and take a view of it
 pub struct Tri3<'a, Real> {
     pub p0: &'a [Real; 3],
     pub p1: &'a [Real; 3],
     pub p2: &'a [Real; 3],
 }
 
+#[cfg(feature = "serde")]
+impl<Real> serde::Serialize for Tri3<'_, Real>
+where
+    Real: serde::Serialize,
+{
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        use serde::ser::SerializeTuple;
+        let mut t = serializer.serialize_tuple(3)?;
+        t.serialize_element(self.p0)?;
+        t.serialize_element(self.p1)?;
+        t.serialize_element(self.p2)?;
+        t.end()
+    }
+}
+
 #[allow(clippy::needless_lifetimes)]
 impl<'a, Real> Tri3<'a, Real>
 where
@@ -401,3 +832,19 @@ where
         ]
     }
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let (p0, p1, p2) = ([0., 0., 0.], [1., 0., 0.], [0., 1., 0.]);
+    let tri = Tri3 {
+        p0: &p0,
+        p1: &p1,
+        p2: &p2,
+    };
+    let s = serde_json::to_string(&tri).unwrap();
+    assert_eq!(
+        serde_json::from_str::<[[f64; 3]; 3]>(&s).unwrap(),
+        [p0, p1, p2]
+    );
+}
@@ -0,0 +1,464 @@
+//! zero-cost newtypes over the raw arrays to tell apart the conventions at compile time
+//! (e.g., `Aabb3` and `Mat3Sym` are both `[T; 6]`).
+//! The methods and the operators delegate to the free functions in the corresponding modules.
+//! Enabled by the `typed` feature. `bytemuck` casts are enabled by the `bytemuck` feature
+//! and the serialization as the raw array is enabled by the `serde` feature
+
+macro_rules! newtype {
+    ($(#[$attr:meta])* $name:ident, $n:literal) => {
+        $(#[$attr])*
+        #[repr(transparent)]
+        #[derive(Debug, Clone, Copy, PartialEq)]
+        #[cfg_attr(
+            feature = "serde",
+            derive(serde::Serialize, serde::Deserialize),
+            serde(transparent)
+        )]
+        pub struct $name<T>(pub [T; $n]);
+
+        impl<T> From<[T; $n]> for $name<T> {
+            fn from(v: [T; $n]) -> Self {
+                $name(v)
+            }
+        }
+
+        impl<T> From<$name<T>> for [T; $n] {
+            fn from(v: $name<T>) -> Self {
+                v.0
+            }
+        }
+
+        impl<T> AsRef<[T; $n]> for $name<T> {
+            fn as_ref(&self) -> &[T; $n] {
+                &self.0
+            }
+        }
+
+        impl<T> AsMut<[T; $n]> for $name<T> {
+            fn as_mut(&mut self) -> &mut [T; $n] {
+                &mut self.0
+            }
+        }
+
+        impl<T> core::ops::Index<usize> for $name<T> {
+            type Output = T;
+            fn index(&self, i: usize) -> &T {
+                &self.0[i]
+            }
+        }
+
+        impl<T> core::ops::IndexMut<usize> for $name<T> {
+            fn index_mut(&mut self, i: usize) -> &mut T {
+                &mut self.0[i]
+            }
+        }
+
+        impl<T> $name<T> {
+            /// view of the array as the newtype without copy
+            pub fn from_ref(v: &[T; $n]) -> &Self {
+                // SAFETY: `#[repr(transparent)]` over `[T; N]`
+                unsafe { &*(v as *const [T; $n] as *const Self) }
+            }
+
+            /// view of the array as the newtype without copy
+            pub fn from_mut(v: &mut [T; $n]) -> &mut Self {
+                // SAFETY: `#[repr(transparent)]` over `[T; N]`
+                unsafe { &mut *(v as *mut [T; $n] as *mut Self) }
+            }
+        }
+
+        #[cfg(feature = "bytemuck")]
+        // SAFETY: `#[repr(transparent)]` over `[T; N]`, which is `Zeroable` if `T` is
+        unsafe impl<T: bytemuck::Zeroable> bytemuck::Zeroable for $name<T> {}
+
+        #[cfg(feature = "bytemuck")]
+        // SAFETY: `#[repr(transparent)]` over `[T; N]`, which is `Pod` if `T` is
+        unsafe impl<T: bytemuck::Pod> bytemuck::Pod for $name<T> {}
+
+        #[cfg(feature = "bytemuck")]
+        // SAFETY: `#[repr(transparent)]` over `[T; N]`
+        unsafe impl<T> bytemuck::TransparentWrapper<[T; $n]> for $name<T> {}
+    };
+}
+
+newtype!(
+    /// axis-aligned bounding box `[min_x, min_y, min_z, max_x, max_y, max_z]` (see `aabb3`)
+    Aabb3,
+    6
+);
+
+newtype!(
+    /// oriented bounding box `[center, half axis 0, half axis 1, half axis 2]` (see `obb3`)
+    Obb3,
+    12
+);
+
+newtype!(
+    /// 3x3 matrix in the column-major order (see `mat3_col_major`)
+    Mat3ColMajor,
+    9
+);
+
+newtype!(
+    /// 3x3 matrix in the row-major order (see `mat3_row_major`)
+    Mat3RowMajor,
+    9
+);
+
+newtype!(
+    /// 3x3 symmetric matrix `[m00, m11, m22, m12, m20, m01]` (see `mat3_sym`)
+    Mat3Sym,
+    6
+);
+
+newtype!(
+    /// quaternion `[i, j, k, w]` (see `quaternion`)
+    Quat,
+    4
+);
+
+// ----------------------------
+// Aabb3
+
+impl<Real> Aabb3<Real>
+where
+    Real: num_traits::Float,
+{
+    pub fn from_point(xyz: &[Real; 3], eps: Real) -> Self {
+        let mut aabb = [Real::zero(); 6];
+        crate::aabb3::set_as_cube(&mut aabb, xyz, eps);
+        Aabb3(aabb)
+    }
+
+    pub fn add_point(&mut self, xyz: &[Real; 3], eps: Real) {
+        crate::aabb3::add_point(&mut self.0, xyz, eps);
+    }
+
+    pub fn center(&self) -> [Real; 3] {
+        crate::aabb3::center(&self.0)
+    }
+
+    pub fn size(&self) -> [Real; 3] {
+        crate::aabb3::size(&self.0)
+    }
+
+    pub fn is_intersect(&self, other: &Self) -> bool {
+        crate::aabb3::is_intersect(&self.0, &other.0)
+    }
+}
+
+/// union of two boxes
+impl<Real> core::ops::BitOr for Aabb3<Real>
+where
+    Real: num_traits::Float,
+{
+    type Output = Self;
+    fn bitor(self, rhs: Self) -> Self {
+        Aabb3(crate::aabb3::from_two_aabbs(&self.0, &rhs.0))
+    }
+}
+
+// ----------------------------
+// Obb3
+
+impl<Real> Obb3<Real>
+where
+    Real: num_traits::Float + core::ops::MulAssign,
+{
+    pub fn is_include_point(&self, p: &[Real; 3], eps: Real) -> bool {
+        crate::obb3::is_include_point(&self.0, p, eps)
+    }
+
+    pub fn unit_axes_and_half_edge_lengths(&self) -> ([[Real; 3]; 3], [Real; 3]) {
+        crate::obb3::unit_axes_and_half_edge_lengths(&self.0)
+    }
+
+    pub fn nearest_to_point3(&self, p: &[Real; 3]) -> [Real; 3] {
+        crate::obb3::nearest_to_point3(&self.0, p)
+    }
+}
+
+// ----------------------------
+// Mat3ColMajor
+
+impl<Real> Mat3ColMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    pub fn identity() -> Self {
+        Mat3ColMajor(crate::mat3_col_major::from_identity())
+    }
+
+    pub fn from_diagonal(diagonal: &[Real; 3]) -> Self {
+        Mat3ColMajor(crate::mat3_col_major::from_diagonal(diagonal))
+    }
+
+    pub fn determinant(&self) -> Real {
+        crate::mat3_col_major::determinant(&self.0)
+    }
+
+    pub fn try_inverse(&self) -> Option<Self> {
+        crate::mat3_col_major::try_inverse(&self.0).map(Mat3ColMajor)
+    }
+
+    pub fn transpose(&self) -> Self {
+        Mat3ColMajor(crate::mat3_col_major::transpose(&self.0))
+    }
+
+    pub fn to_quaternion(&self) -> Quat<Real>
+    where
+        Real: num_traits::float::FloatCore + core::fmt::Debug,
+    {
+        Quat(crate::mat3_col_major::to_quaternion(&self.0))
+    }
+}
+
+impl<Real> core::ops::Add for Mat3ColMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        Mat3ColMajor(core::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<Real> core::ops::Sub for Mat3ColMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Mat3ColMajor(core::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<Real> core::ops::Mul for Mat3ColMajor<Real>
+where
+    Real: num_traits::Float + core::ops::AddAssign,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Mat3ColMajor(crate::mat3_col_major::mult_mat_col_major(&self.0, &rhs.0))
+    }
+}
+
+impl<Real> core::ops::Mul<[Real; 3]> for Mat3ColMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    type Output = [Real; 3];
+    fn mul(self, rhs: [Real; 3]) -> [Real; 3] {
+        crate::mat3_col_major::mult_vec(&self.0, &rhs)
+    }
+}
+
+impl<Real> core::ops::Mul<Real> for Mat3ColMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    type Output = Self;
+    fn mul(self, rhs: Real) -> Self {
+        Mat3ColMajor(self.0.map(|v| v * rhs))
+    }
+}
+
+/// same matrix stored in the column-major order
+impl<Real> From<Mat3RowMajor<Real>> for Mat3ColMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    fn from(m: Mat3RowMajor<Real>) -> Self {
+        Mat3ColMajor(crate::mat3_row_major::transpose(&m.0))
+    }
+}
+
+impl<Real> From<Mat3Sym<Real>> for Mat3ColMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    fn from(m: Mat3Sym<Real>) -> Self {
+        let s = m.0;
+        Mat3ColMajor([s[0], s[5], s[4], s[5], s[1], s[3], s[4], s[3], s[2]])
+    }
+}
+
+// ----------------------------
+// Mat3RowMajor
+
+impl<Real> Mat3RowMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    pub fn identity() -> Self {
+        Mat3RowMajor(crate::mat3_row_major::from_identity())
+    }
+
+    pub fn determinant(&self) -> Real {
+        crate::mat3_row_major::determinant(&self.0)
+    }
+
+    pub fn transpose(&self) -> Self {
+        Mat3RowMajor(crate::mat3_row_major::transpose(&self.0))
+    }
+
+    /// singular value decomposition `self = U G V^T`. see `mat3_row_major::svd`
+    pub fn svd(&self, nitr: usize) -> (Self, [Real; 3], Self)
+    where
+        Real: core::ops::AddAssign + core::iter::Sum,
+    {
+        let (u, g, v) = crate::mat3_row_major::svd(&self.0, nitr);
+        (Mat3RowMajor(u), g, Mat3RowMajor(v))
+    }
+}
+
+impl<Real> core::ops::Sub for Mat3RowMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        Mat3RowMajor(crate::mat3_row_major::sub(&self.0, &rhs.0))
+    }
+}
+
+impl<Real> core::ops::Mul for Mat3RowMajor<Real>
+where
+    Real: num_traits::Float + core::ops::AddAssign,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Mat3RowMajor(crate::mat3_row_major::mult_mat_row_major(&self.0, &rhs.0))
+    }
+}
+
+/// same matrix stored in the row-major order
+impl<Real> From<Mat3ColMajor<Real>> for Mat3RowMajor<Real>
+where
+    Real: num_traits::Float,
+{
+    fn from(m: Mat3ColMajor<Real>) -> Self {
+        Mat3RowMajor(crate::mat3_col_major::transpose(&m.0))
+    }
+}
+
+// ----------------------------
+// Mat3Sym
+
+impl<Real> Mat3Sym<Real>
+where
+    Real: num_traits::Float,
+{
+    /// `(U, lambda)` where the columns of `U` are the eigenvectors. see `mat3_sym::eigen_decomp`
+    pub fn eigen_decomp(&self, nitr: usize) -> Option<(Mat3RowMajor<Real>, [Real; 3])> {
+        crate::mat3_sym::eigen_decomp(self.0, nitr).map(|(u, l)| (Mat3RowMajor(u), l))
+    }
+}
+
+// ----------------------------
+// Quat
+
+impl<Real> Quat<Real>
+where
+    Real: num_traits::Float,
+{
+    pub fn identity() -> Self {
+        Quat(crate::quaternion::identity())
+    }
+
+    pub fn from_axisangle(a: &[Real; 3]) -> Self {
+        Quat(crate::quaternion::from_axisangle(a))
+    }
+
+    pub fn normalized(&self) -> Self {
+        Quat(crate::quaternion::normalized(&self.0))
+    }
+
+    pub fn inverse(&self) -> Self {
+        Quat(crate::quaternion::inverse(self.0))
+    }
+
+    pub fn to_mat3_col_major(&self) -> Mat3ColMajor<Real> {
+        Mat3ColMajor(crate::quaternion::to_mat3_col_major(&self.0))
+    }
+}
+
+impl<Real> core::ops::Mul for Quat<Real>
+where
+    Real: num_traits::Float,
+{
+    type Output = Self;
+    fn mul(self, rhs: Self) -> Self {
+        Quat(crate::quaternion::mult_quaternion(&self.0, &rhs.0))
+    }
+}
+
+#[test]
+fn test_typed() {
+    let q0 = Quat::from_axisangle(&[0.3f64, -0.2, 0.5]);
+    let q1 = Quat::from_axisangle(&[-0.1, 0.4, 0.2]);
+    // the rotation of the product is the product of the rotations
+    let m0 = (q0 * q1).to_mat3_col_major();
+    let m1 = q0.to_mat3_col_major() * q1.to_mat3_col_major();
+    assert!((m0 - m1).0.iter().all(|v| v.abs() < 1.0e-12));
+    let p = [0.3, 0.5, -0.2];
+    let p0 = m0 * p;
+    let p1 = crate::mat3_col_major::mult_vec(&m0.0, &p);
+    assert_eq!(p0, p1);
+    // conversion between the orders keeps the matrix
+    let r = Mat3RowMajor::from(m0);
+    assert_eq!(Mat3ColMajor::from(r), m0);
+    assert_eq!(r.0, crate::mat3_col_major::transpose(&m0.0));
+    assert!((m0.try_inverse().unwrap() - m0.transpose())
+        .0
+        .iter()
+        .all(|v| v.abs() < 1.0e-12));
+    // symmetric matrix
+    let s = Mat3Sym([2.0f64, 3.0, 4.0, 0.1, 0.2, 0.3]);
+    let (u, l) = s.eigen_decomp(20).unwrap();
+    let a = Mat3RowMajor::from(Mat3ColMajor::from(s));
+    let d = (u.transpose() * a * u).0;
+    for i in 0..3 {
+        for j in 0..3 {
+            let v = if i == j { l[i] } else { 0. };
+            assert!((d[i * 3 + j] - v).abs() < 1.0e-10);
+        }
+    }
+    // box
+    let b0 = Aabb3::from_point(&[0., 0., 0.], 1.0f32);
+    let b1 = Aabb3::from_point(&[3., 0., 0.], 1.0);
+    assert!(!b0.is_intersect(&b1));
+    let b2 = b0 | b1;
+    assert_eq!(b2.0, [-1., -1., -1., 4., 1., 1.]);
+    assert_eq!(b2.center(), [1.5, 0., 0.]);
+    let mut raw = [0f32; 6];
+    Aabb3::from_mut(&mut raw).add_point(&[1., 2., 3.], 0.);
+    assert_eq!(raw, [0., 0., 0., 1., 2., 3.]);
+    // obb
+    let obb = Obb3([0., 0., 0., 2., 0., 0., 0., 1., 0., 0., 0., 0.5]);
+    assert!(obb.is_include_point(&[1.5, 0.5, 0.], 0.));
+    assert_eq!(obb.nearest_to_point3(&[3., 0., 1.]), [2., 0., 0.5]);
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_bytemuck() {
+    let boxes = [
+        Aabb3([0f32, 1., 2., 3., 4., 5.]),
+        Aabb3([6., 7., 8., 9., 10., 11.]),
+    ];
+    let flat: &[f32] = bytemuck::cast_slice(&boxes);
+    assert_eq!(flat.len(), 12);
+    assert_eq!(flat[7], 7.);
+    let raw: &[[f32; 6]] = bytemuck::TransparentWrapper::peel_slice(&boxes);
+    assert_eq!(raw[1][0], 6.);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let obb = Obb3([0f32, 0., 0., 2., 0., 0., 0., 1., 0., 0., 0., 0.5]);
+    let s = serde_json::to_string(&obb).unwrap();
+    assert_eq!(serde_json::from_str::<[f32; 12]>(&s).unwrap(), obb.0);
+    assert_eq!(serde_json::from_str::<Obb3<f32>>(&s).unwrap(), obb);
+}
@@ -1,5 +1,8 @@
 //! methods for unit 3D vector
 
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
 pub fn map_to_unit2_octahedron(dir: &[f32; 3]) -> [f32; 2] {
     let n = dir[0].abs() + dir[1].abs() + dir[2].abs();
     let oct = [dir[0] / n, dir[1] / n];
@@ -21,7 +24,7 @@ pub fn map_to_unit2_equal_area(d: &[f32; 3]) -> [f32; 2] {
     let z = d[2].abs();
     let r = (1. - z).sqrt();
     let phi = y.atan2(x);
-    let phi = phi * std::f32::consts::FRAC_2_PI;
+    let phi = phi * core::f32::consts::FRAC_2_PI;
     let v = phi * r;
     let u = r - v;
     let (u, v) = if d[2] < 0. { (1. - v, 1. - u) } else { (u, v) };
@@ -1,4 +1,8 @@
 //! methods for 2D vector
+
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
+
 /// trait for 2D vector
 pub trait Vec2<Real>
 where
@@ -40,14 +44,14 @@ where
 
 pub fn sub<T>(a: &[T; 2], b: &[T; 2]) -> [T; 2]
 where
-    T: std::ops::Sub<Output = T> + Copy,
+    T: core::ops::Sub<Output = T> + Copy,
 {
     [a[0] - b[0], a[1] - b[1]]
 }
 
 pub fn add<T>(a: &[T; 2], b: &[T; 2]) -> [T; 2]
 where
-    T: std::ops::Add<Output = T> + Copy,
+    T: core::ops::Add<Output = T> + Copy,
 {
     [a[0] + b[0], a[1] + b[1]]
 }
@@ -87,7 +91,7 @@ fn test_angle_between_two_vecs() {
     let a = [3f64.sqrt(), 1.0];
     let b = [-1.0, 1.0];
     let theta0 = angle_between_two_vecs(&a, &b);
-    let theta1 = 7f64 / 12f64 * std::f64::consts::PI;
+    let theta1 = 7f64 / 12f64 * core::f64::consts::PI;
     assert!((theta0 - theta1).abs() < 1.0e-10);
 }
 
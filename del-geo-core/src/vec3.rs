@@ -193,7 +193,7 @@ where
 
 pub fn squared_norm<T>(p: &[T; 3]) -> T
 where
-    T: std::ops::Mul<Output = T> + std::ops::Add<Output = T> + Copy,
+    T: core::ops::Mul<Output = T> + core::ops::Add<Output = T> + Copy,
 {
     assert_eq!(p.len(), 3);
     p[0] * p[0] + p[1] * p[1] + p[2] * p[2]
@@ -226,12 +226,12 @@ where
 {
     let l = v.norm();
     let linv = T::one() / l;
-    std::array::from_fn(|i| v[i] * linv)
+    core::array::from_fn(|i| v[i] * linv)
 }
 
 pub fn cross_mut<T>(vo: &mut [T; 3], v1: &[T; 3], v2: &[T; 3])
 where
-    T: std::ops::Mul<Output = T> + std::ops::Sub<Output = T> + Copy,
+    T: core::ops::Mul<Output = T> + core::ops::Sub<Output = T> + Copy,
 {
     vo[0] = v1[1] * v2[2] - v2[1] * v1[2];
     vo[1] = v1[2] * v2[0] - v2[2] * v1[0];
@@ -240,7 +240,7 @@ where
 
 pub fn cross<T>(v1: &[T; 3], v2: &[T; 3]) -> [T; 3]
 where
-    T: std::ops::Mul<Output = T> + std::ops::Sub<Output = T> + Copy,
+    T: core::ops::Mul<Output = T> + core::ops::Sub<Output = T> + Copy,
 {
     [
         v1[1] * v2[2] - v2[1] * v1[2],
@@ -251,7 +251,7 @@ where
 
 pub fn dot<T>(a: &[T; 3], b: &[T; 3]) -> T
 where
-    T: std::ops::Mul<Output = T> + std::ops::Add<Output = T> + Copy,
+    T: core::ops::Mul<Output = T> + core::ops::Add<Output = T> + Copy,
 {
     a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
 }
@@ -262,14 +262,14 @@ where
 ///   return a-b
 pub fn sub<T>(a: &[T; 3], b: &[T; 3]) -> [T; 3]
 where
-    T: std::ops::Sub<Output = T> + Copy,
+    T: core::ops::Sub<Output = T> + Copy,
 {
     [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
 }
 
 pub fn scale<T>(a: &[T; 3], s: T) -> [T; 3]
 where
-    T: Copy + std::ops::Mul<Output = T>,
+    T: Copy + core::ops::Mul<Output = T>,
 {
     [s * a[0], s * a[1], s * a[2]]
 }
@@ -293,7 +293,7 @@ where
 
 pub fn scalar_triple_product<T>(a: &[T; 3], b: &[T; 3], c: &[T; 3]) -> T
 where
-    T: std::ops::Mul<Output = T> + std::ops::Sub<Output = T> + std::ops::Add<Output = T> + Copy,
+    T: core::ops::Mul<Output = T> + core::ops::Sub<Output = T> + core::ops::Add<Output = T> + Copy,
 {
     let v0: T = a[0] * (b[1] * c[2] - b[2] * c[1]);
     let v1: T = a[1] * (b[2] * c[0] - b[0] * c[2]);
@@ -305,7 +305,7 @@ pub fn axpy<Real>(alpha: Real, x: &[Real; 3], y: &[Real; 3]) -> [Real; 3]
 where
     Real: num_traits::Float,
 {
-    std::array::from_fn(|i| alpha * x[i] + y[i])
+    core::array::from_fn(|i| alpha * x[i] + y[i])
 }
 
 pub fn to_quaternion_from_axis_angle_vector<Real>(a: &[Real; 3]) -> [Real; 4]
@@ -334,14 +334,14 @@ where
     Real: num_traits::Float,
 {
     let a = nrm.dot(v);
-    std::array::from_fn(|i| v[i] - nrm[i] * Real::from(2).unwrap() * a)
+    core::array::from_fn(|i| v[i] - nrm[i] * Real::from(2).unwrap() * a)
 }
 
 pub fn element_wise_mult<Real>(a: &[Real; 3], b: &[Real; 3]) -> [Real; 3]
 where
     Real: num_traits::Float,
 {
-    std::array::from_fn(|i| a[i] * b[i])
+    core::array::from_fn(|i| a[i] * b[i])
 }
 
 // ------------------------------------------
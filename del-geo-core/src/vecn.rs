@@ -6,19 +6,158 @@ pub trait Arr<T, const N: usize> {
 
 impl<T, const N: usize> Arr<T, N> for [T; N]
 where
-    T: num_traits::Float + Copy + std::iter::Sum,
+    T: num_traits::Float + Copy + core::iter::Sum,
 {
     fn add(self, other: &[T; N]) -> Self {
-        std::array::from_fn(|i| self[i] + other[i])
+        core::array::from_fn(|i| self[i] + other[i])
     }
     fn sub(self, other: &[T; N]) -> Self {
-        std::array::from_fn(|i| self[i] - other[i])
+        core::array::from_fn(|i| self[i] - other[i])
     }
     fn norm(self) -> T {
         self.iter().map(|&v| v * v).sum::<T>().sqrt()
     }
 }
 
+/// vector with the const-generic dimension `N`.
+/// `#[repr(transparent)]` over `[T; N]` so that the conversion to and from the arrays in `vec2` and `vec3` is free
+#[repr(transparent)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct VecN<T, const N: usize>(pub [T; N]);
+
+impl<T, const N: usize> VecN<T, N>
+where
+    T: num_traits::Float,
+{
+    pub fn zeros() -> Self {
+        VecN([T::zero(); N])
+    }
+
+    pub fn from_fn<F: FnMut(usize) -> T>(f: F) -> Self {
+        VecN(core::array::from_fn(f))
+    }
+
+    pub fn dot(&self, other: &Self) -> T {
+        self.0
+            .iter()
+            .zip(other.0.iter())
+            .fold(T::zero(), |s, (&a, &b)| s + a * b)
+    }
+
+    pub fn squared_norm(&self) -> T {
+        self.dot(self)
+    }
+
+    pub fn norm(&self) -> T {
+        self.squared_norm().sqrt()
+    }
+
+    pub fn normalized(&self) -> Self {
+        *self * (T::one() / self.norm())
+    }
+}
+
+impl<T> VecN<T, 3>
+where
+    T: num_traits::Float,
+{
+    pub fn cross(&self, other: &Self) -> Self {
+        VecN(crate::vec3::cross(&self.0, &other.0))
+    }
+}
+
+impl<T, const N: usize> VecN<T, N> {
+    /// view of the array as the vector without copy
+    pub fn from_ref(v: &[T; N]) -> &Self {
+        // SAFETY: `#[repr(transparent)]` over `[T; N]`
+        unsafe { &*(v as *const [T; N] as *const Self) }
+    }
+
+    /// view of the array as the vector without copy
+    pub fn from_mut(v: &mut [T; N]) -> &mut Self {
+        // SAFETY: `#[repr(transparent)]` over `[T; N]`
+        unsafe { &mut *(v as *mut [T; N] as *mut Self) }
+    }
+}
+
+impl<T, const N: usize> From<[T; N]> for VecN<T, N> {
+    fn from(v: [T; N]) -> Self {
+        VecN(v)
+    }
+}
+
+impl<T, const N: usize> From<VecN<T, N>> for [T; N] {
+    fn from(v: VecN<T, N>) -> Self {
+        v.0
+    }
+}
+
+impl<T, const N: usize> AsRef<[T; N]> for VecN<T, N> {
+    fn as_ref(&self) -> &[T; N] {
+        &self.0
+    }
+}
+
+impl<T, const N: usize> AsMut<[T; N]> for VecN<T, N> {
+    fn as_mut(&mut self) -> &mut [T; N] {
+        &mut self.0
+    }
+}
+
+impl<T, const N: usize> core::ops::Index<usize> for VecN<T, N> {
+    type Output = T;
+    fn index(&self, i: usize) -> &T {
+        &self.0[i]
+    }
+}
+
+impl<T, const N: usize> core::ops::IndexMut<usize> for VecN<T, N> {
+    fn index_mut(&mut self, i: usize) -> &mut T {
+        &mut self.0[i]
+    }
+}
+
+impl<T, const N: usize> core::ops::Add for VecN<T, N>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn add(self, rhs: Self) -> Self {
+        VecN(core::array::from_fn(|i| self.0[i] + rhs.0[i]))
+    }
+}
+
+impl<T, const N: usize> core::ops::Sub for VecN<T, N>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn sub(self, rhs: Self) -> Self {
+        VecN(core::array::from_fn(|i| self.0[i] - rhs.0[i]))
+    }
+}
+
+impl<T, const N: usize> core::ops::Neg for VecN<T, N>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn neg(self) -> Self {
+        VecN(self.0.map(|v| -v))
+    }
+}
+
+/// scaling
+impl<T, const N: usize> core::ops::Mul<T> for VecN<T, N>
+where
+    T: num_traits::Float,
+{
+    type Output = Self;
+    fn mul(self, rhs: T) -> Self {
+        VecN(self.0.map(|v| v * rhs))
+    }
+}
+
 #[test]
 fn test_add() {
     // Test with different array sizes
@@ -28,3 +167,21 @@ fn test_add() {
     // assert_eq!([1, 2, 3].add(&[2, 3, 4]), [3, 5, 7]);
     // assert_eq!([1, 2, 3, 4].add(&[2, 3, 4, 5]), [3, 5, 7, 9]);
 }
+
+#[test]
+fn test_vecn() {
+    let a = VecN([1., 2., 3.]);
+    let b: VecN<f64, 3> = [4., 5., 6.].into();
+    assert_eq!((a + b).0, [5., 7., 9.]);
+    assert_eq!((b - a).0, [3., 3., 3.]);
+    assert_eq!((-a * 2.).0, [-2., -4., -6.]);
+    assert_eq!(a.dot(&b), crate::vec3::dot(&a.0, &b.0));
+    assert_eq!(a.cross(&b).0, crate::vec3::cross(&a.0, &b.0));
+    assert!((a.normalized().norm() - 1.).abs() < 1.0e-10);
+    let mut c = [3f32, 4.];
+    assert_eq!(VecN::from_ref(&c).norm(), 5.);
+    VecN::from_mut(&mut c)[1] = 0.;
+    assert_eq!(c, [3., 0.]);
+    let d: [f32; 2] = VecN(c).into();
+    assert_eq!(d, c);
+}
@@ -1,5 +1,9 @@
 use crate::mat4_col_major::Mat4ColMajor;
 
+/// perspective camera.
+/// There is no `Pod` view because of the `bool` field. Upload `mat4_col_major` to the GPU instead
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Perspective {
     pub lens: f32,
     pub near: f32,
@@ -36,3 +40,18 @@ impl Perspective {
         self.cam_pos[1] -= sy * cursor_dy;
     }
 }
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let cam = Perspective {
+        lens: 24.,
+        near: 0.5,
+        far: 3.0,
+        proj_direction: true,
+        cam_pos: [0., 0., 2.],
+        scale: 1.,
+    };
+    let s = serde_json::to_string(&cam).unwrap();
+    assert_eq!(serde_json::from_str::<Perspective>(&s).unwrap(), cam);
+}
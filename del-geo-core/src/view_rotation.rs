@@ -1,5 +1,12 @@
 use crate::quaternion::Quaternion;
+#[cfg(not(feature = "std"))]
+use num_traits::Float;
 
+/// rotation of the view with a unit quaternion `[i, j, k, w]`.
+/// `#[repr(C)]` so that it can be copied to a GPU buffer as it is (see the `bytemuck` feature)
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Trackball {
     pub quaternion: [f32; 4],
 }
@@ -32,3 +39,35 @@ impl Default for Trackball {
         Self::new()
     }
 }
+
+#[cfg(feature = "bytemuck")]
+// SAFETY: `#[repr(C)]` with a single `[f32; 4]` field
+unsafe impl bytemuck::Zeroable for Trackball {}
+
+#[cfg(feature = "bytemuck")]
+// SAFETY: `#[repr(C)]` with a single `[f32; 4]` field, no padding
+unsafe impl bytemuck::Pod for Trackball {}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn test_bytemuck() {
+    let trackballs = [
+        Trackball::new(),
+        Trackball {
+            quaternion: [1., 0., 0., 0.],
+        },
+    ];
+    let flat: &[f32] = bytemuck::cast_slice(&trackballs);
+    assert_eq!(flat, [0., 0., 0., 1., 1., 0., 0., 0.]);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde() {
+    let trackball = Trackball {
+        quaternion: [0.5, 0.5, 0.5, 0.5],
+    };
+    let s = serde_json::to_string(&trackball).unwrap();
+    assert_eq!(s, r#"{"quaternion":[0.5,0.5,0.5,0.5]}"#);
+    assert_eq!(serde_json::from_str::<Trackball>(&s).unwrap(), trackball);
+}
@@ -0,0 +1,376 @@
+//! voxelization on a regular grid.
+//! the grid is defined by the bounding box `aabb: [x_min, y_min, z_min, x_max, y_max, z_max]`
+//! and the resolution `(nx, ny, nz)`. The index of the voxel `(ix, iy, iz)` is `(iz * ny + iy) * nx + ix`
+
+/// bounding box of the voxel `(ix, iy, iz)`
+pub fn aabb_of_voxel<Real>(
+    aabb: &[Real; 6],
+    (nx, ny, nz): (usize, usize, usize),
+    (ix, iy, iz): (usize, usize, usize),
+) -> [Real; 6]
+where
+    Real: num_traits::Float,
+{
+    let h = [
+        (aabb[3] - aabb[0]) / Real::from(nx).unwrap(),
+        (aabb[4] - aabb[1]) / Real::from(ny).unwrap(),
+        (aabb[5] - aabb[2]) / Real::from(nz).unwrap(),
+    ];
+    let i = [ix, iy, iz].map(|i| Real::from(i).unwrap());
+    let i1 = i.map(|i| i + Real::one());
+    [
+        aabb[0] + h[0] * i[0],
+        aabb[1] + h[1] * i[1],
+        aabb[2] + h[2] * i[2],
+        aabb[0] + h[0] * i1[0],
+        aabb[1] + h[1] * i1[1],
+        aabb[2] + h[2] * i1[2],
+    ]
+}
+
+/// index of the voxel containing the point. `None` if the point is outside the grid
+pub fn voxel_containing_point<Real>(
+    aabb: &[Real; 6],
+    (nx, ny, nz): (usize, usize, usize),
+    p: &[Real; 3],
+) -> Option<usize>
+where
+    Real: num_traits::Float,
+{
+    let n = [nx, ny, nz];
+    let mut idx = [0usize; 3];
+    for i in 0..3 {
+        let r = (p[i] - aabb[i]) / (aabb[i + 3] - aabb[i]) * Real::from(n[i]).unwrap();
+        if r.is_nan() || r < Real::zero() {
+            return None;
+        }
+        let ir = r.floor().to_usize()?;
+        if ir >= n[i] {
+            return None;
+        }
+        idx[i] = ir;
+    }
+    Some((idx[2] * ny + idx[1]) * nx + idx[0])
+}
+
+/// list the voxels whose box overlaps with the object bounded by `obj_aabb`
+/// and passes the overlap test `is_intersect`
+#[cfg(feature = "std")]
+fn overlapping_voxels<Real, F>(
+    aabb: &[Real; 6],
+    (nx, ny, nz): (usize, usize, usize),
+    obj_aabb: &[Real; 6],
+    is_intersect: F,
+) -> Vec<usize>
+where
+    Real: num_traits::Float,
+    F: Fn(&[Real; 6]) -> bool,
+{
+    let n = [nx, ny, nz];
+    let range = |i: usize| {
+        let s = Real::from(n[i]).unwrap() / (aabb[i + 3] - aabb[i]);
+        let lo = ((obj_aabb[i] - aabb[i]) * s).floor().max(Real::zero());
+        let hi = ((obj_aabb[i + 3] - aabb[i]) * s).floor();
+        let hi = hi.min(Real::from(n[i]).unwrap() - Real::one());
+        if lo > hi {
+            return 0..0;
+        }
+        lo.to_usize().unwrap()..hi.to_usize().unwrap() + 1
+    };
+    let mut res = vec![];
+    for iz in range(2) {
+        for iy in range(1) {
+            for ix in range(0) {
+                let vox = aabb_of_voxel(aabb, (nx, ny, nz), (ix, iy, iz));
+                if is_intersect(&vox) {
+                    res.push((iz * ny + iy) * nx + ix);
+                }
+            }
+        }
+    }
+    res
+}
+
+/// list the voxels overlapping with a triangle
+#[cfg(feature = "std")]
+pub fn voxels_overlapping_tri3<Real>(
+    aabb: &[Real; 6],
+    shape: (usize, usize, usize),
+    p0: &[Real; 3],
+    p1: &[Real; 3],
+    p2: &[Real; 3],
+) -> Vec<usize>
+where
+    Real: num_traits::Float,
+{
+    let obj_aabb: [Real; 6] = core::array::from_fn(|i| match i {
+        0..3 => p0[i].min(p1[i]).min(p2[i]),
+        _ => p0[i - 3].max(p1[i - 3]).max(p2[i - 3]),
+    });
+    overlapping_voxels(aabb, shape, &obj_aabb, |vox| {
+        crate::tri3::is_intersect_aabb3(p0, p1, p2, vox)
+    })
+}
+
+/// list the voxels overlapping with a line segment
+#[cfg(feature = "std")]
+pub fn voxels_overlapping_edge3<Real>(
+    aabb: &[Real; 6],
+    shape: (usize, usize, usize),
+    p0: &[Real; 3],
+    p1: &[Real; 3],
+) -> Vec<usize>
+where
+    Real: num_traits::Float,
+{
+    let obj_aabb: [Real; 6] = core::array::from_fn(|i| match i {
+        0..3 => p0[i].min(p1[i]),
+        _ => p0[i - 3].max(p1[i - 3]),
+    });
+    overlapping_voxels(aabb, shape, &obj_aabb, |vox| {
+        crate::edge3::is_intersect_aabb3(p0, p1, vox)
+    })
+}
+
+/// list the voxels overlapping with a solid sphere
+#[cfg(feature = "std")]
+pub fn voxels_overlapping_sphere<Real>(
+    aabb: &[Real; 6],
+    shape: (usize, usize, usize),
+    center: &[Real; 3],
+    rad: Real,
+) -> Vec<usize>
+where
+    Real: num_traits::Float,
+{
+    let obj_aabb: [Real; 6] = core::array::from_fn(|i| match i {
+        0..3 => center[i] - rad,
+        _ => center[i - 3] + rad,
+    });
+    overlapping_voxels(aabb, shape, &obj_aabb, |vox| {
+        crate::sphere::is_intersect_aabb3(rad, center, vox)
+    })
+}
+
+/// exact traversal of a regular grid along a ray (Amanatides & Woo 1987).
+/// the cell `idx` covers `origin[i] + cell_size[i] * [idx[i], idx[i] + 1]`.
+/// when the ray passes exactly through a corner of the cells, the cells touching the corner are
+/// also listed with the same entry and exit parameters.
+/// # return
+/// the cells intersected by the ray `src + t * dir` for `t` in `t_range`
+/// with the parameters where the ray enters and exits the cell
+#[cfg(feature = "std")]
+pub(crate) fn traverse_grid<Real, const N: usize>(
+    origin: &[Real; N],
+    cell_size: &[Real; N],
+    shape: &[usize; N],
+    src: &[Real; N],
+    dir: &[Real; N],
+    (t_min, t_max): (Real, Real),
+) -> Vec<([usize; N], Real, Real)>
+where
+    Real: num_traits::Float,
+{
+    let zero = Real::zero();
+    // clip the parameter range by the slabs of the grid
+    let (mut t0, mut t1) = (t_min, t_max);
+    for i in 0..N {
+        let lo = origin[i];
+        let hi = origin[i] + cell_size[i] * Real::from(shape[i]).unwrap();
+        if dir[i] == zero {
+            if src[i] < lo || src[i] > hi {
+                return vec![];
+            }
+            continue;
+        }
+        let ta = (lo - src[i]) / dir[i];
+        let tb = (hi - src[i]) / dir[i];
+        t0 = t0.max(ta.min(tb));
+        t1 = t1.min(ta.max(tb));
+    }
+    if t0 > t1 || t0.is_nan() || t1.is_nan() || shape.contains(&0) {
+        return vec![];
+    }
+    let mut idx = [0usize; N];
+    let mut step = [0i64; N];
+    let mut t_next = [Real::infinity(); N];
+    let mut t_delta = [Real::infinity(); N];
+    for i in 0..N {
+        let x = (src[i] + dir[i] * t0 - origin[i]) / cell_size[i];
+        let last = Real::from(shape[i] - 1).unwrap();
+        // the cell is determined by the direction at the boundary of the cells
+        let x = if dir[i] < zero {
+            x.ceil() - Real::one()
+        } else {
+            x.floor()
+        };
+        idx[i] = x.max(zero).min(last).to_usize().unwrap();
+        if dir[i] == zero {
+            continue;
+        }
+        t_delta[i] = cell_size[i] / dir[i].abs();
+        let i_boundary = if dir[i] > zero { idx[i] + 1 } else { idx[i] };
+        let boundary = origin[i] + cell_size[i] * Real::from(i_boundary).unwrap();
+        t_next[i] = (boundary - src[i]) / dir[i];
+        step[i] = if dir[i] > zero { 1 } else { -1 };
+    }
+    let mut res = vec![];
+    let mut t_enter = t0;
+    loop {
+        let (a, &ta) = t_next
+            .iter()
+            .enumerate()
+            .min_by(|x, y| x.1.partial_cmp(y.1).unwrap_or(core::cmp::Ordering::Equal))
+            .unwrap();
+        let t_exit = ta.min(t1);
+        res.push((idx, t_enter, t_exit));
+        if ta >= t1 {
+            break;
+        }
+        let i_next = idx[a] as i64 + step[a];
+        if i_next < 0 || i_next >= shape[a] as i64 {
+            break;
+        }
+        idx[a] = i_next as usize;
+        t_next[a] = t_next[a] + t_delta[a];
+        t_enter = t_exit;
+    }
+    res
+}
+
+/// list the voxels intersected by the ray `src + t * dir` for `t` in `t_range`
+/// # return
+/// voxel indices with the parameters where the ray enters and exits the voxels, ordered along the ray
+#[cfg(feature = "std")]
+pub fn voxels_along_ray<Real>(
+    aabb: &[Real; 6],
+    (nx, ny, nz): (usize, usize, usize),
+    src: &[Real; 3],
+    dir: &[Real; 3],
+    t_range: (Real, Real),
+) -> Vec<(usize, Real, Real)>
+where
+    Real: num_traits::Float,
+{
+    let shape = [nx, ny, nz];
+    let origin = [aabb[0], aabb[1], aabb[2]];
+    let cell_size =
+        core::array::from_fn(|i| (aabb[i + 3] - aabb[i]) / Real::from(shape[i]).unwrap());
+    traverse_grid(&origin, &cell_size, &shape, src, dir, t_range)
+        .into_iter()
+        .map(|(idx, t0, t1)| ((idx[2] * ny + idx[1]) * nx + idx[0], t0, t1))
+        .collect()
+}
+
+/// list the voxels intersected by the line segment.
+/// see [`voxels_along_ray`] for the return value. the parameter is `0` at `p0` and `1` at `p1`
+#[cfg(feature = "std")]
+pub fn voxels_along_edge3<Real>(
+    aabb: &[Real; 6],
+    shape: (usize, usize, usize),
+    p0: &[Real; 3],
+    p1: &[Real; 3],
+) -> Vec<(usize, Real, Real)>
+where
+    Real: num_traits::Float,
+{
+    let dir = core::array::from_fn(|i| p1[i] - p0[i]);
+    voxels_along_ray(aabb, shape, p0, &dir, (Real::zero(), Real::one()))
+}
+
+#[test]
+fn test_voxels_along_ray() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb = [-1.0, -0.5, 0.0, 1.0, 1.5, 1.0f64];
+    let shape = (8, 10, 5);
+    for _itr in 0..100 {
+        let p: [[f64; 3]; 2] = core::array::from_fn(|_| {
+            core::array::from_fn(|i| {
+                aabb[i] - 0.3 + rng.gen::<f64>() * (aabb[i + 3] - aabb[i] + 0.6)
+            })
+        });
+        let cells = voxels_along_edge3(&aabb, shape, &p[0], &p[1]);
+        // same as the voxels overlapping with the segment
+        let mut a: Vec<usize> = cells.iter().map(|v| v.0).collect();
+        a.sort();
+        a.dedup();
+        assert_eq!(a.len(), cells.len());
+        let mut b = voxels_overlapping_edge3(&aabb, shape, &p[0], &p[1]);
+        b.sort();
+        assert_eq!(a, b);
+        // the parameters are continuous along the ray and the midpoint is inside the voxel
+        for (i, &(i_vox, t0, t1)) in cells.iter().enumerate() {
+            assert!(t0 <= t1 && (0. ..=1.).contains(&t0) && (0. ..=1.).contains(&t1));
+            if i > 0 {
+                assert_eq!(cells[i - 1].2, t0);
+            }
+            let t = (t0 + t1) * 0.5;
+            let q: [f64; 3] = core::array::from_fn(|k| p[0][k] + (p[1][k] - p[0][k]) * t);
+            let ivs = (i_vox % 8, (i_vox / 8) % 10, i_vox / 80);
+            let vox = aabb_of_voxel(&aabb, shape, ivs);
+            assert!((0..3).all(|k| vox[k] - 1.0e-10 <= q[k] && q[k] <= vox[k + 3] + 1.0e-10));
+        }
+    }
+    // axis-aligned ray
+    let cells = voxels_along_ray(&aabb, shape, &[-2., 0.1, 0.5], &[1., 0., 0.], (0., 10.));
+    assert_eq!(cells.len(), 8);
+    assert!((cells[0].1 - 1.0).abs() < 1.0e-10 && (cells[7].2 - 3.0).abs() < 1.0e-10);
+    let cells = voxels_along_ray(&aabb, shape, &[2., 0.1, 0.5], &[1., 0., 0.], (0., 10.));
+    assert!(cells.is_empty());
+}
+
+#[test]
+fn test_voxelization() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let aabb = [-1.0, -0.5, 0.0, 1.0, 1.5, 1.0f64];
+    let shape = (8, 10, 5);
+    for _itr in 0..30 {
+        let p: [[f64; 3]; 3] = core::array::from_fn(|_| {
+            core::array::from_fn(|i| {
+                aabb[i] - 0.3 + rng.gen::<f64>() * (aabb[i + 3] - aabb[i] + 0.6)
+            })
+        });
+        let vtri: std::collections::BTreeSet<usize> =
+            voxels_overlapping_tri3(&aabb, shape, &p[0], &p[1], &p[2])
+                .into_iter()
+                .collect();
+        let vedge: std::collections::BTreeSet<usize> =
+            voxels_overlapping_edge3(&aabb, shape, &p[0], &p[1])
+                .into_iter()
+                .collect();
+        let vsphere: std::collections::BTreeSet<usize> =
+            voxels_overlapping_sphere(&aabb, shape, &p[2], 0.3)
+                .into_iter()
+                .collect();
+        assert!(vedge.is_subset(&vtri));
+        for _ in 0..1000 {
+            let (r0, r1) = (rng.gen::<f64>(), rng.gen::<f64>());
+            let (r0, r1) = if r0 + r1 > 1. {
+                (1. - r0, 1. - r1)
+            } else {
+                (r0, r1)
+            };
+            let q: [f64; 3] =
+                core::array::from_fn(|i| p[0][i] * (1. - r0 - r1) + p[1][i] * r0 + p[2][i] * r1);
+            if let Some(i_vox) = voxel_containing_point(&aabb, shape, &q) {
+                assert!(vtri.contains(&i_vox));
+            }
+            let q: [f64; 3] = core::array::from_fn(|i| p[0][i] * (1. - r0) + p[1][i] * r0);
+            if let Some(i_vox) = voxel_containing_point(&aabb, shape, &q) {
+                assert!(vedge.contains(&i_vox));
+            }
+            let q: [f64; 3] = core::array::from_fn(|i| p[2][i] + (r0 - 0.5) * 0.3);
+            if let Some(i_vox) = voxel_containing_point(&aabb, shape, &q) {
+                assert!(vsphere.contains(&i_vox));
+            }
+        }
+    }
+    // an axis-aligned triangle inside a slab of the voxels
+    let p = [[-2., -2., 0.6], [4., -2., 0.6], [-2., 4., 0.6]];
+    let vtri = voxels_overlapping_tri3(&aabb, (4, 4, 4), &p[0], &p[1], &p[2]);
+    assert_eq!(vtri, (32..48).collect::<Vec<_>>());
+}
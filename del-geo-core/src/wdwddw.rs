@@ -0,0 +1,173 @@
+//! chain rule of the first and the second derivatives shared by the energy kernels (e.g., `rod3`, `tri3`, `tet`).
+//! The functions return `(w, dw, ddw)`: the value, the gradient and the hessian
+
+use crate::vec3::Vec3;
+
+/// derivatives of the composite function `z(y(x))`
+/// * `jz`, `hz` - Jacobian `dz/dy` and the second derivative `d^2z/dy^2` of `z`
+/// * `jy`, `hy` - Jacobian `dy/dx` and the second derivative `d^2y/dx^2` of `y`
+#[allow(clippy::type_complexity)]
+pub(crate) fn compose<T, const L: usize, const M: usize, const N: usize>(
+    jz: &[[T; M]; L],
+    hz: &[[[T; M]; M]; L],
+    jy: &[[T; N]; M],
+    hy: &[[[T; N]; N]; M],
+) -> ([[T; N]; L], [[[T; N]; N]; L])
+where
+    T: num_traits::Float,
+{
+    let mut j = [[T::zero(); N]; L];
+    let mut h = [[[T::zero(); N]; N]; L];
+    for k in 0..L {
+        for a in 0..M {
+            for i in 0..N {
+                j[k][i] = j[k][i] + jz[k][a] * jy[a][i];
+            }
+            if jz[k][a] != T::zero() {
+                for i in 0..N {
+                    for l in 0..N {
+                        h[k][i][l] = h[k][i][l] + jz[k][a] * hy[a][i][l];
+                    }
+                }
+            }
+            for b in 0..M {
+                if hz[k][a][b] == T::zero() {
+                    continue;
+                }
+                for i in 0..N {
+                    for l in 0..N {
+                        h[k][i][l] = h[k][i][l] + hz[k][a][b] * jy[a][i] * jy[b][l];
+                    }
+                }
+            }
+        }
+    }
+    (j, h)
+}
+
+/// derivatives of the dot product of two slots `a` and `b` (3D vectors) in the vector `y`
+pub(crate) fn dot_slots<T, const N: usize>(
+    y: &[T; N],
+    a: usize,
+    b: usize,
+) -> (T, [T; N], [[T; N]; N])
+where
+    T: num_traits::Float,
+{
+    let mut w = T::zero();
+    let mut dw = [T::zero(); N];
+    let mut ddw = [[T::zero(); N]; N];
+    for i in 0..3 {
+        w = w + y[a + i] * y[b + i];
+        dw[a + i] = dw[a + i] + y[b + i];
+        dw[b + i] = dw[b + i] + y[a + i];
+        ddw[a + i][b + i] = ddw[a + i][b + i] + T::one();
+        ddw[b + i][a + i] = ddw[b + i][a + i] + T::one();
+    }
+    (w, dw, ddw)
+}
+
+/// derivatives of the scalar triple product of three slots `a`, `b` and `c` in the vector `y`
+pub(crate) fn det_slots<T, const N: usize>(
+    y: &[T; N],
+    a: usize,
+    b: usize,
+    c: usize,
+) -> (T, [T; N], [[T; N]; N])
+where
+    T: num_traits::Float,
+{
+    let va = arrayref::array_ref!(y, a, 3);
+    let vb = arrayref::array_ref!(y, b, 3);
+    let vc = arrayref::array_ref!(y, c, 3);
+    let w = crate::vec3::scalar_triple_product(va, vb, vc);
+    let mut dw = [T::zero(); N];
+    let mut ddw = [[T::zero(); N]; N];
+    let (ga, gb, gc) = (vb.cross(vc), vc.cross(va), va.cross(vb));
+    dw[a..a + 3].copy_from_slice(&ga);
+    dw[b..b + 3].copy_from_slice(&gb);
+    dw[c..c + 3].copy_from_slice(&gc);
+    // d(b x c)/db = -[c]x, d(b x c)/dc = [b]x, d(c x a)/dc = -[a]x
+    let skew = |v: &[T; 3]| {
+        let z = T::zero();
+        [[z, -v[2], v[1]], [v[2], z, -v[0]], [-v[1], v[0], z]]
+    };
+    for (s0, s1, m, sign) in [
+        (a, b, skew(vc), -T::one()),
+        (a, c, skew(vb), T::one()),
+        (b, c, skew(va), -T::one()),
+    ] {
+        for i in 0..3 {
+            for j in 0..3 {
+                let v = sign * m[i][j];
+                ddw[s0 + i][s1 + j] = ddw[s0 + i][s1 + j] + v;
+                ddw[s1 + j][s0 + i] = ddw[s1 + j][s0 + i] + v;
+            }
+        }
+    }
+    (w, dw, ddw)
+}
+
+/// derivatives of `atan2(y, x)` where `y` and `x` are given with their derivatives
+#[allow(clippy::type_complexity)]
+pub(crate) fn atan2<T, const N: usize>(
+    y: (T, &[T; N], &[[T; N]; N]),
+    x: (T, &[T; N], &[[T; N]; N]),
+) -> (T, [T; N], [[T; N]; N])
+where
+    T: num_traits::Float,
+{
+    let two = T::one() + T::one();
+    let (y, dy, ddy) = y;
+    let (x, dx, ddx) = x;
+    let r2 = x * x + y * y;
+    let w = y.atan2(x);
+    let (w_x, w_y) = (-y / r2, x / r2);
+    let w_xx = two * x * y / (r2 * r2);
+    let w_xy = (y * y - x * x) / (r2 * r2);
+    let (dw, ddw) = compose(
+        &[[w_x, w_y]],
+        &[[[w_xx, w_xy], [w_xy, -w_xx]]],
+        &[*dx, *dy],
+        &[*ddx, *ddy],
+    );
+    (w, dw[0], ddw[0])
+}
+
+/// compare the derivatives with the central differences
+#[cfg(test)]
+pub(crate) fn check_derivatives<F, const N: usize>(x0: &[f64; N], eval: F)
+where
+    F: Fn(&[f64; N]) -> (f64, [f64; N], [[f64; N]; N]),
+{
+    let (_, dw0, ddw0) = eval(x0);
+    let eps = 1.0e-5;
+    for i in 0..N {
+        let mut xp = *x0;
+        let mut xm = *x0;
+        xp[i] += eps;
+        xm[i] -= eps;
+        let (wp, dwp, _) = eval(&xp);
+        let (wm, dwm, _) = eval(&xm);
+        let v0 = (wp - wm) / (2. * eps);
+        assert!(
+            (v0 - dw0[i]).abs() < 1.0e-5 * (1. + dw0[i].abs()),
+            "{} {}",
+            v0,
+            dw0[i]
+        );
+        for j in 0..N {
+            let v0 = (dwp[j] - dwm[j]) / (2. * eps);
+            let v1 = ddw0[i][j];
+            assert!(
+                (v0 - v1).abs() < 1.0e-5 * (1. + v1.abs()),
+                "{} {} {} {}",
+                i,
+                j,
+                v0,
+                v1
+            );
+            assert!((ddw0[i][j] - ddw0[j][i]).abs() < 1.0e-8);
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! generate the `__device__` C++ headers from the functions in `del-geo-core`.
+//!
+//! The supported subset of Rust is the free functions over the scalar (`num_traits::Float`
+//! generic parameter, `f32` or `f64`), the fixed-size arrays, the tuples and the `Option`.
+//! The generic scalar is translated to `float`.
+//! `&[T; N]` and `&mut [T; N]` parameters are passed as the pointers to the first element.
+//! The generated headers are checked in `del-geo-cpp-headers`, and
+//! the test `test_headers_are_up_to_date` fails if they differ from the Rust source.
+//! Run `cargo run -p del-geo-cpp-codegen` to regenerate them.
+
+mod translate;
+
+/// the modules and the functions to translate. The functions are emitted in this order,
+/// so a function needs to come after the functions in the same module it calls
+pub const MODULES: &[(&str, &[&str])] = &[
+    (
+        "edge2",
+        &[
+            "length",
+            "unit_edge_vector",
+            "culling_intersection",
+            "winding_number",
+        ],
+    ),
+    (
+        "obb3",
+        &[
+            "is_include_point",
+            "unit_axes_and_half_edge_lengths",
+            "nearest_to_point3",
+        ],
+    ),
+    ("sphere", &["intersection_ray", "area", "sample"]),
+    ("spherical_harmonics", &["normalize", "sph_coeff_buffer"]),
+    (
+        "vec2",
+        &[
+            "length",
+            "squared_length",
+            "sub",
+            "add",
+            "scale",
+            "dot",
+            "area_quadrilateral",
+            "axpy",
+        ],
+    ),
+];
+
+pub fn path_core_src() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../del-geo-core/src")
+}
+
+pub fn path_headers_src() -> std::path::PathBuf {
+    std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../del-geo-cpp-headers/src")
+}
+
+/// header of the functions `fns` in the module `module` of the source in `path_src`
+pub fn generate_header(
+    path_src: &std::path::Path,
+    module: &str,
+    fns: &[&str],
+) -> Result<String, String> {
+    let source = translate::Source::new(path_src);
+    let mut includes = std::collections::BTreeSet::new();
+    let mut bodies = vec![];
+    for name in fns {
+        let Some(item) = source.item_fn(module, name)? else {
+            return Err(format!("`{}::{}` is not found", module, name));
+        };
+        let body = translate::translate_fn(&source, module, &item, &mut includes)
+            .map_err(|e| format!("{}::{}: {}", module, name, e))?;
+        bodies.push(body);
+    }
+    let bodies = bodies.join("\n\n");
+    //
+    let mut lines = vec![
+        "#pragma once".to_string(),
+        format!(
+            "// generated from del-geo-core/src/{}.rs by del-geo-cpp-codegen. do not edit",
+            module
+        ),
+    ];
+    for (key, header) in [
+        ("cuda::std::array", "<cuda/std/array>"),
+        ("cuda::std::complex", "<cuda/std/complex>"),
+        ("cuda::std::optional", "<cuda/std/optional>"),
+        ("cuda::std::nullopt", "<cuda/std/optional>"),
+        ("cuda::std::tuple", "<cuda/std/tuple>"),
+        ("cuda::std::make_tuple", "<cuda/std/tuple>"),
+        ("cuda::std::get", "<cuda/std/tuple>"),
+        ("FLT_", "<float.h>"),
+        ("DBL_", "<float.h>"),
+    ] {
+        let line = format!("#include {}", header);
+        if bodies.contains(key) && !lines.contains(&line) {
+            lines.push(line);
+        }
+    }
+    for include in includes {
+        lines.push(format!("#include \"{}.h\"", include));
+    }
+    lines.push(String::new());
+    lines.push(format!("namespace {} {{", module));
+    lines.push(String::new());
+    lines.push(bodies);
+    lines.push(String::new());
+    lines.push("}".to_string());
+    lines.push(String::new());
+    Ok(lines.join("\n"))
+}
+
+/// `(module, header)` of all the modules in `MODULES`
+pub fn generate_all() -> Result<Vec<(&'static str, String)>, String> {
+    let path_src = path_core_src();
+    MODULES
+        .iter()
+        .map(|(module, fns)| Ok((*module, generate_header(&path_src, module, fns)?)))
+        .collect()
+}
+
+#[test]
+fn test_headers_are_up_to_date() {
+    let path_dir = path_headers_src();
+    for (module, header) in generate_all().unwrap() {
+        let path = path_dir.join(format!("{}.h", module));
+        let checked_in = std::fs::read_to_string(&path).unwrap_or_default();
+        assert!(
+            checked_in == header,
+            "{} is out of date. run `cargo run -p del-geo-cpp-codegen`",
+            path.display()
+        );
+    }
+}
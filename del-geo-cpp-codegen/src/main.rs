@@ -0,0 +1,13 @@
+//! write the generated headers into `del-geo-cpp-headers/src` or the directory given as the argument
+
+fn main() {
+    let path_dir = match std::env::args().nth(1) {
+        Some(path) => std::path::PathBuf::from(path),
+        None => del_geo_cpp_codegen::path_headers_src(),
+    };
+    for (module, header) in del_geo_cpp_codegen::generate_all().unwrap() {
+        let path = path_dir.join(format!("{}.h", module));
+        std::fs::write(&path, header).unwrap();
+        println!("{}", path.display());
+    }
+}
@@ -0,0 +1,966 @@
+//! translation of a Rust function to a `__device__` C++ function
+
+use quote::ToTokens;
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+/// C++ type of a Rust value
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum Ty {
+    Float,
+    Double,
+    Int(&'static str),
+    Bool,
+    Unit,
+    Complex,
+    Array(Box<Ty>, usize),
+    /// pointer to the first element of `[Ty; N]` given as `&[Ty; N]` or `&mut [Ty; N]`
+    Ptr(Box<Ty>, usize, bool),
+    /// scalar given as `&mut`
+    Ref(Box<Ty>),
+    Tuple(Vec<Ty>),
+    Optional(Box<Ty>),
+    Unknown,
+}
+
+impl Ty {
+    fn cpp(&self) -> String {
+        match self {
+            Ty::Float => "float".to_string(),
+            Ty::Double => "double".to_string(),
+            Ty::Int(s) => s.to_string(),
+            Ty::Bool => "bool".to_string(),
+            Ty::Unit => "void".to_string(),
+            Ty::Complex => "cuda::std::complex<double>".to_string(),
+            Ty::Array(e, n) => format!("cuda::std::array<{},{}>", e.cpp(), n),
+            Ty::Ptr(e, _, true) => format!("{}*", e.cpp()),
+            Ty::Ptr(e, _, false) => format!("const {}*", e.cpp()),
+            Ty::Ref(e) => format!("{}&", e.cpp()),
+            Ty::Tuple(es) => format!(
+                "cuda::std::tuple<{}>",
+                es.iter().map(|e| e.cpp()).collect::<Vec<_>>().join(", ")
+            ),
+            Ty::Optional(e) => format!("cuda::std::optional<{}>", e.cpp()),
+            Ty::Unknown => "auto".to_string(),
+        }
+    }
+
+    fn len(&self) -> Option<usize> {
+        match self {
+            Ty::Array(_, n) | Ty::Ptr(_, n, _) => Some(*n),
+            _ => None,
+        }
+    }
+
+    fn elem(&self) -> Ty {
+        match self {
+            Ty::Array(e, _) | Ty::Ptr(e, _, _) => (**e).clone(),
+            _ => Ty::Unknown,
+        }
+    }
+
+    /// value type of the scalar given by the reference
+    fn decay(self) -> Ty {
+        match self {
+            Ty::Ref(e) => *e,
+            t => t,
+        }
+    }
+}
+
+/// parsed source files of `del-geo-core`
+pub(crate) struct Source {
+    path_src: std::path::PathBuf,
+    files: std::cell::RefCell<HashMap<String, std::rc::Rc<syn::File>>>,
+}
+
+impl Source {
+    pub(crate) fn new(path_src: &std::path::Path) -> Self {
+        Source {
+            path_src: path_src.to_owned(),
+            files: Default::default(),
+        }
+    }
+
+    fn file(&self, module: &str) -> Result<std::rc::Rc<syn::File>, String> {
+        if let Some(file) = self.files.borrow().get(module) {
+            return Ok(file.clone());
+        }
+        let path = self.path_src.join(format!("{}.rs", module));
+        let Ok(src) = std::fs::read_to_string(&path) else {
+            return Err(format!("cannot read {:?}", path));
+        };
+        let file = std::rc::Rc::new(syn::parse_file(&src).map_err(|e| e.to_string())?);
+        self.files
+            .borrow_mut()
+            .insert(module.to_string(), file.clone());
+        Ok(file)
+    }
+
+    /// module-level function `name` in `module`
+    pub(crate) fn item_fn(&self, module: &str, name: &str) -> Result<Option<syn::ItemFn>, String> {
+        let file = self.file(module)?;
+        Ok(file.items.iter().find_map(|item| match item {
+            syn::Item::Fn(f) if f.sig.ident == name => Some(f.clone()),
+            _ => None,
+        }))
+    }
+}
+
+fn unsupported<T: ToTokens>(what: &str, t: &T) -> String {
+    format!("unsupported {} `{}`", what, t.to_token_stream())
+}
+
+fn generic_names(sig: &syn::Signature) -> Vec<String> {
+    sig.generics
+        .params
+        .iter()
+        .filter_map(|p| match p {
+            syn::GenericParam::Type(t) => Some(t.ident.to_string()),
+            _ => None,
+        })
+        .collect()
+}
+
+fn array_len(len: &syn::Expr) -> Result<usize, String> {
+    match len {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(i),
+            ..
+        }) => i.base10_parse::<usize>().map_err(|e| e.to_string()),
+        _ => Err(unsupported("array length", len)),
+    }
+}
+
+/// C++ type of the Rust type where the generic parameters are the scalar `scalar`
+fn ty_of(ty: &syn::Type, generics: &[String], scalar: &Ty) -> Result<Ty, String> {
+    match ty {
+        syn::Type::Path(p) => {
+            let seg = p.path.segments.last().unwrap();
+            let name = seg.ident.to_string();
+            if generics.contains(&name) {
+                return Ok(scalar.clone());
+            }
+            Ok(match name.as_str() {
+                "f32" => Ty::Float,
+                "f64" => Ty::Double,
+                "bool" => Ty::Bool,
+                "i8" => Ty::Int("int8_t"),
+                "i16" => Ty::Int("int16_t"),
+                "i32" => Ty::Int("int32_t"),
+                "i64" => Ty::Int("int64_t"),
+                "u8" => Ty::Int("uint8_t"),
+                "u16" => Ty::Int("uint16_t"),
+                "u32" => Ty::Int("uint32_t"),
+                "u64" => Ty::Int("uint64_t"),
+                "usize" => Ty::Int("size_t"),
+                "Option" => {
+                    let syn::PathArguments::AngleBracketed(args) = &seg.arguments else {
+                        return Err(unsupported("type", ty));
+                    };
+                    let Some(syn::GenericArgument::Type(t)) = args.args.first() else {
+                        return Err(unsupported("type", ty));
+                    };
+                    Ty::Optional(Box::new(ty_of(t, generics, scalar)?))
+                }
+                _ => return Err(unsupported("type", ty)),
+            })
+        }
+        syn::Type::Array(a) => Ok(Ty::Array(
+            Box::new(ty_of(&a.elem, generics, scalar)?),
+            array_len(&a.len)?,
+        )),
+        syn::Type::Tuple(t) if t.elems.is_empty() => Ok(Ty::Unit),
+        syn::Type::Tuple(t) => Ok(Ty::Tuple(
+            t.elems
+                .iter()
+                .map(|e| ty_of(e, generics, scalar))
+                .collect::<Result<_, _>>()?,
+        )),
+        syn::Type::Paren(p) => ty_of(&p.elem, generics, scalar),
+        _ => Err(unsupported("type", ty)),
+    }
+}
+
+/// C++ type of the function parameter
+fn param_ty_of(ty: &syn::Type, generics: &[String], scalar: &Ty) -> Result<Ty, String> {
+    let syn::Type::Reference(r) = ty else {
+        return ty_of(ty, generics, scalar);
+    };
+    let is_mut = r.mutability.is_some();
+    Ok(match ty_of(&r.elem, generics, scalar)? {
+        Ty::Array(e, n) => Ty::Ptr(e, n, is_mut),
+        t if is_mut => Ty::Ref(Box::new(t)),
+        t => t,
+    })
+}
+
+fn return_ty_of(sig: &syn::Signature, scalar: &Ty) -> Result<Ty, String> {
+    match &sig.output {
+        syn::ReturnType::Default => Ok(Ty::Unit),
+        syn::ReturnType::Type(_, ty) => ty_of(ty, &generic_names(sig), scalar),
+    }
+}
+
+/// mathematical constant in `std::f32::consts` or `num_traits::FloatConst`
+fn math_const(name: &str) -> Option<&'static str> {
+    Some(match name {
+        "PI" => "3.14159265358979323846",
+        "FRAC_1_PI" => "0.318309886183790671538",
+        "FRAC_2_PI" => "0.636619772367581343076",
+        "FRAC_PI_2" => "1.57079632679489661923",
+        "FRAC_PI_4" => "0.785398163397448309616",
+        "TAU" => "6.28318530717958647693",
+        "SQRT_2" => "1.41421356237309504880",
+        "FRAC_1_SQRT_2" => "0.707106781186547524401",
+        "E" => "2.71828182845904523536",
+        "LN_2" => "0.693147180559945309417",
+        _ => return None,
+    })
+}
+
+fn float_lit(digits: &str, ty: &Ty) -> String {
+    let mut s = digits.to_string();
+    if !s.contains(['.', 'e', 'E']) {
+        s.push_str(".0");
+    } else if s.ends_with('.') {
+        s.push('0');
+    }
+    if *ty == Ty::Float {
+        s.push('f');
+    }
+    s
+}
+
+fn is_zero_expr(e: &syn::Expr) -> bool {
+    match e {
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Float(f),
+            ..
+        }) => f.base10_parse::<f64>().map(|v| v == 0.).unwrap_or(false),
+        syn::Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Int(i),
+            ..
+        }) => i.base10_parse::<i64>().map(|v| v == 0).unwrap_or(false),
+        syn::Expr::Call(c) => match &*c.func {
+            syn::Expr::Path(p) => p.path.segments.last().unwrap().ident == "zero",
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+fn path_idents(path: &syn::Path) -> Vec<String> {
+    path.segments.iter().map(|s| s.ident.to_string()).collect()
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Tail {
+    /// the value of the block is returned from the function
+    Return,
+    /// the value of the block is discarded
+    Discard,
+}
+
+const CPP_KEYWORDS: &[&str] = &[
+    "auto",
+    "char",
+    "class",
+    "default",
+    "delete",
+    "double",
+    "float",
+    "int",
+    "long",
+    "namespace",
+    "new",
+    "operator",
+    "private",
+    "public",
+    "short",
+    "signed",
+    "switch",
+    "template",
+    "this",
+    "typename",
+    "union",
+    "unsigned",
+    "void",
+    "volatile",
+];
+
+struct FnTranslator<'a> {
+    source: &'a Source,
+    module: &'a str,
+    generics: Vec<String>,
+    /// type of the generic scalar and the unsuffixed float literals
+    scalar: Ty,
+    ret: Ty,
+    scopes: Vec<HashMap<String, (String, Ty)>>,
+    used_names: HashSet<String>,
+    includes: &'a mut BTreeSet<String>,
+}
+
+impl FnTranslator<'_> {
+    fn declare(&mut self, name: &str, ty: Ty) -> String {
+        let mut cpp = name.to_string();
+        let mut k = 0;
+        while self.used_names.contains(&cpp) || CPP_KEYWORDS.contains(&cpp.as_str()) {
+            k += 1;
+            cpp = format!("{}_{}", name, k);
+        }
+        self.used_names.insert(cpp.clone());
+        self.scopes
+            .last_mut()
+            .unwrap()
+            .insert(name.to_string(), (cpp.clone(), ty));
+        cpp
+    }
+
+    fn lookup(&self, name: &str) -> Option<(String, Ty)> {
+        self.scopes.iter().rev().find_map(|s| s.get(name).cloned())
+    }
+
+    fn scalar_of(&self, name: &str) -> Option<Ty> {
+        if self.generics.iter().any(|g| g == name) {
+            return Some(self.scalar.clone());
+        }
+        match name {
+            "f32" => Some(Ty::Float),
+            "f64" => Some(Ty::Double),
+            _ => None,
+        }
+    }
+
+    // ----------------------------
+    // expressions
+
+    fn expr(&mut self, e: &syn::Expr) -> Result<(String, Ty), String> {
+        use syn::Expr;
+        match e {
+            Expr::Lit(l) => self.lit(&l.lit),
+            Expr::Path(p) => self.path(&p.path),
+            Expr::Paren(p) => {
+                let (s, t) = self.expr(&p.expr)?;
+                Ok((format!("({})", s), t))
+            }
+            Expr::Binary(b) => {
+                let (l, tl) = self.expr(&b.left)?;
+                let (r, tr) = self.expr(&b.right)?;
+                let op = b.op.to_token_stream().to_string();
+                let ty = match op.as_str() {
+                    "==" | "!=" | "<" | "<=" | ">" | ">=" | "&&" | "||" => Ty::Bool,
+                    _ if tl != Ty::Unknown => tl.decay(),
+                    _ => tr.decay(),
+                };
+                Ok((format!("{} {} {}", l, op, r), ty))
+            }
+            Expr::Unary(u) => {
+                let (s, t) = self.expr(&u.expr)?;
+                match u.op {
+                    syn::UnOp::Neg(_) if s.starts_with('-') => Ok((format!("-({})", s), t)),
+                    syn::UnOp::Neg(_) => Ok((format!("-{}", s), t)),
+                    syn::UnOp::Not(_) => Ok((format!("!{}", s), Ty::Bool)),
+                    syn::UnOp::Deref(_) => match t {
+                        Ty::Ptr(e, n, _) => {
+                            let ty = Ty::Array(e, n);
+                            let elems: Vec<_> = (0..n).map(|i| format!("{}[{}]", s, i)).collect();
+                            Ok((format!("{}{{{}}}", ty.cpp(), elems.join(", ")), ty))
+                        }
+                        t => Ok((s, t.decay())),
+                    },
+                    _ => Err(unsupported("expression", e)),
+                }
+            }
+            Expr::Index(i) => {
+                let (s, t) = self.expr(&i.expr)?;
+                let (k, _) = self.expr(&i.index)?;
+                Ok((format!("{}[{}]", s, k), t.elem()))
+            }
+            Expr::Field(f) => {
+                let syn::Member::Unnamed(idx) = &f.member else {
+                    return Err(unsupported("expression", e));
+                };
+                let (s, t) = self.expr(&f.base)?;
+                let k = idx.index as usize;
+                let ty = match t {
+                    Ty::Tuple(ts) => ts.get(k).cloned().unwrap_or(Ty::Unknown),
+                    _ => Ty::Unknown,
+                };
+                Ok((format!("cuda::std::get<{}>({})", k, s), ty))
+            }
+            Expr::Call(c) => self.call(c),
+            Expr::MethodCall(m) => self.method_call(m),
+            Expr::Array(a) => {
+                let elems = a
+                    .elems
+                    .iter()
+                    .map(|e| self.expr_noparen(e))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let te = match elems.first() {
+                    Some((_, t)) if *t != Ty::Unknown => t.clone().decay(),
+                    _ => self.scalar.clone(),
+                };
+                let ty = Ty::Array(Box::new(te), elems.len());
+                let strs: Vec<_> = elems.into_iter().map(|(s, _)| s).collect();
+                Ok((format!("{}{{{}}}", ty.cpp(), strs.join(", ")), ty))
+            }
+            Expr::Repeat(r) => {
+                if !is_zero_expr(&r.expr) {
+                    return Err(unsupported("expression", e));
+                }
+                let (_, te) = self.expr(&r.expr)?;
+                let te = if let Ty::Int(_) = te {
+                    self.scalar.clone()
+                } else {
+                    te
+                };
+                let ty = Ty::Array(Box::new(te), array_len(&r.len)?);
+                Ok((format!("{}{{}}", ty.cpp()), ty))
+            }
+            Expr::Tuple(t) => {
+                let elems = t
+                    .elems
+                    .iter()
+                    .map(|e| self.expr_noparen(e))
+                    .collect::<Result<Vec<_>, _>>()?;
+                let strs: Vec<_> = elems.iter().map(|(s, _)| s.clone()).collect();
+                let ty = Ty::Tuple(elems.into_iter().map(|(_, t)| t).collect());
+                Ok((format!("cuda::std::make_tuple({})", strs.join(", ")), ty))
+            }
+            // the references are resolved at the call site
+            Expr::Reference(r) => self.expr(&r.expr),
+            Expr::Cast(c) => {
+                let (s, _) = self.expr_noparen(&c.expr)?;
+                let ty = ty_of(&c.ty, &self.generics, &self.scalar)?;
+                Ok((format!("{}({})", ty.cpp(), s), ty))
+            }
+            Expr::If(i) => {
+                let (c, _) = self.expr_noparen(&i.cond)?;
+                let (a, ta) = self.single_expr_block(&i.then_branch)?;
+                let Some((_, else_branch)) = &i.else_branch else {
+                    return Err(unsupported("expression", e));
+                };
+                let (b, _) = self.expr(else_branch)?;
+                Ok((format!("({} ? {} : {})", c, a, b), ta))
+            }
+            Expr::Block(b) => self.single_expr_block(&b.block),
+            _ => Err(unsupported("expression", e)),
+        }
+    }
+
+    /// expression without the outer parenthesis
+    fn expr_noparen(&mut self, e: &syn::Expr) -> Result<(String, Ty), String> {
+        match e {
+            syn::Expr::Paren(p) => self.expr_noparen(&p.expr),
+            _ => self.expr(e),
+        }
+    }
+
+    fn single_expr_block(&mut self, b: &syn::Block) -> Result<(String, Ty), String> {
+        match b.stmts.as_slice() {
+            [syn::Stmt::Expr(e, None)] => self.expr(e),
+            _ => Err(unsupported("block expression", b)),
+        }
+    }
+
+    fn lit(&mut self, lit: &syn::Lit) -> Result<(String, Ty), String> {
+        match lit {
+            syn::Lit::Float(f) => {
+                let ty = match f.suffix() {
+                    "f32" => Ty::Float,
+                    "f64" => Ty::Double,
+                    _ => self.scalar.clone(),
+                };
+                Ok((float_lit(f.base10_digits(), &ty), ty))
+            }
+            syn::Lit::Int(i) => match i.suffix() {
+                "f32" => Ok((float_lit(i.base10_digits(), &Ty::Float), Ty::Float)),
+                "f64" => Ok((float_lit(i.base10_digits(), &Ty::Double), Ty::Double)),
+                _ => Ok((i.base10_digits().to_string(), Ty::Int("int"))),
+            },
+            syn::Lit::Bool(b) => Ok((b.value.to_string(), Ty::Bool)),
+            _ => Err(unsupported("literal", lit)),
+        }
+    }
+
+    fn path(&mut self, path: &syn::Path) -> Result<(String, Ty), String> {
+        let idents = path_idents(path);
+        let last = idents.last().unwrap().as_str();
+        if idents.len() == 1 {
+            if let Some(v) = self.lookup(last) {
+                return Ok(v);
+            }
+            if last == "None" {
+                return Ok((
+                    "cuda::std::nullopt".to_string(),
+                    Ty::Optional(Box::new(Ty::Unknown)),
+                ));
+            }
+        }
+        if let Some(v) = math_const(last) {
+            // e.g., `std::f32::consts::PI`
+            let ty = idents
+                .iter()
+                .find_map(|s| self.scalar_of(s))
+                .unwrap_or(self.scalar.clone());
+            return Ok((float_lit(v, &ty), ty));
+        }
+        Err(unsupported("path", path))
+    }
+
+    /// argument of a function call. `&[T;N]` is passed as the pointer to the first element
+    fn arg(&mut self, e: &syn::Expr) -> Result<String, String> {
+        match e {
+            syn::Expr::Reference(r) => {
+                let (s, t) = self.expr(&r.expr)?;
+                Ok(match t {
+                    Ty::Array(..) => format!("{}.data()", s),
+                    _ => s,
+                })
+            }
+            _ => Ok(self.expr_noparen(e)?.0),
+        }
+    }
+
+    fn module_call(
+        &mut self,
+        module: &str,
+        name: &str,
+        args: Vec<String>,
+    ) -> Result<(String, Ty), String> {
+        let Some(item) = self.source.item_fn(module, name)? else {
+            return Err(format!("`{}::{}` is not found", module, name));
+        };
+        let ret = return_ty_of(&item.sig, &self.scalar)?;
+        let func = if module == self.module {
+            name.to_string()
+        } else {
+            self.includes.insert(module.to_string());
+            format!("{}::{}", module, name)
+        };
+        Ok((format!("{}({})", func, args.join(", ")), ret))
+    }
+
+    fn call(&mut self, c: &syn::ExprCall) -> Result<(String, Ty), String> {
+        let syn::Expr::Path(func) = &*c.func else {
+            return Err(unsupported("function call", c));
+        };
+        let idents = path_idents(&func.path);
+        let idents: Vec<&str> = idents.iter().map(|s| s.as_str()).collect();
+        match idents.as_slice() {
+            [t, f] if self.scalar_of(t).is_some() => {
+                let ty = self.scalar_of(t).unwrap();
+                let is_float = ty == Ty::Float;
+                let s = match *f {
+                    "zero" => float_lit("0", &ty),
+                    "one" => float_lit("1", &ty),
+                    "epsilon" if is_float => "FLT_EPSILON".to_string(),
+                    "epsilon" => "DBL_EPSILON".to_string(),
+                    "max_value" if is_float => "FLT_MAX".to_string(),
+                    "max_value" => "DBL_MAX".to_string(),
+                    "min_value" if is_float => "-FLT_MAX".to_string(),
+                    "min_value" => "-DBL_MAX".to_string(),
+                    "from" => format!("{}({})", ty.cpp(), self.expr_noparen(&c.args[0])?.0),
+                    f if math_const(f).is_some() => float_lit(math_const(f).unwrap(), &ty),
+                    "sqrt" | "sin" | "cos" | "tan" | "asin" | "acos" | "atan" | "exp" | "floor"
+                    | "ceil" | "atan2" | "min" | "max" => {
+                        let args = c
+                            .args
+                            .iter()
+                            .map(|a| Ok(self.expr_noparen(a)?.0))
+                            .collect::<Result<Vec<_>, String>>()?;
+                        format!("{}({})", f, args.join(", "))
+                    }
+                    "abs" => format!("fabs({})", self.expr_noparen(&c.args[0])?.0),
+                    _ => return Err(unsupported("function call", c)),
+                };
+                Ok((s, ty))
+            }
+            ["Complex", "new"] => {
+                let a = self.expr_noparen(&c.args[0])?.0;
+                let b = self.expr_noparen(&c.args[1])?.0;
+                Ok((format!("{}({}, {})", Ty::Complex.cpp(), a, b), Ty::Complex))
+            }
+            ["Some"] => {
+                let (s, t) = self.expr_noparen(&c.args[0])?;
+                Ok((s, Ty::Optional(Box::new(t))))
+            }
+            ["crate", module, name] => {
+                let args = c
+                    .args
+                    .iter()
+                    .map(|a| self.arg(a))
+                    .collect::<Result<_, _>>()?;
+                self.module_call(module, name, args)
+            }
+            [name] => {
+                let args = c
+                    .args
+                    .iter()
+                    .map(|a| self.arg(a))
+                    .collect::<Result<_, _>>()?;
+                let module = self.module;
+                self.module_call(module, name, args)
+            }
+            _ => Err(unsupported("function call", c)),
+        }
+    }
+
+    fn method_call(&mut self, m: &syn::ExprMethodCall) -> Result<(String, Ty), String> {
+        let name = m.method.to_string();
+        if name == "unwrap" {
+            // `T::from(x).unwrap()`
+            if let syn::Expr::Call(c) = &*m.receiver {
+                if let syn::Expr::Path(p) = &*c.func {
+                    if p.path.segments.last().unwrap().ident == "from" {
+                        return self.expr(&m.receiver);
+                    }
+                }
+            }
+            return Err(unsupported("method call", m));
+        }
+        let (r, rt) = self.expr(&m.receiver)?;
+        let rt = rt.decay();
+        let (r_noparen, _) = self.expr_noparen(&m.receiver)?;
+        let args = m
+            .args
+            .iter()
+            .map(|a| Ok(self.expr_noparen(a)?.0))
+            .collect::<Result<Vec<_>, String>>()?;
+        let s = match name.as_str() {
+            "sqrt" | "sin" | "cos" | "tan" | "asin" | "acos" | "atan" | "exp" | "floor"
+            | "ceil" | "round" => format!("{}({})", name, r_noparen),
+            "abs" => format!("fabs({})", r_noparen),
+            "ln" => format!("log({})", r_noparen),
+            "atan2" | "min" | "max" | "hypot" | "copysign" => {
+                format!("{}({}, {})", name, r_noparen, args[0])
+            }
+            "powf" => format!("pow({}, {})", r_noparen, args[0]),
+            "powi" if args[0] == "2" => format!("({} * {})", r, r),
+            "powi" => format!("pow({}, {})", r_noparen, args[0]),
+            "mul_add" => format!("fma({}, {}, {})", r_noparen, args[0], args[1]),
+            "clamp" => format!("min(max({}, {}), {})", r_noparen, args[0], args[1]),
+            "is_zero" => return Ok((format!("({} == 0)", r), Ty::Bool)),
+            "re" => return Ok((format!("{}.real()", r), Ty::Double)),
+            "im" => return Ok((format!("{}.imag()", r), Ty::Double)),
+            "as_" => {
+                return Ok((
+                    format!("{}({})", self.scalar.cpp(), r_noparen),
+                    self.scalar.clone(),
+                ))
+            }
+            _ => {
+                // method of the vector trait (e.g., `Vec3::dot`) or of the trait of the module
+                let mut modules = match rt.len() {
+                    Some(2) => vec!["vec2"],
+                    Some(3) => vec!["vec3"],
+                    _ => vec![],
+                };
+                modules.push(self.module);
+                let receiver = match rt {
+                    Ty::Array(..) => format!("{}.data()", r),
+                    Ty::Ptr(..) => r.clone(),
+                    _ => return Err(unsupported("method call", m)),
+                };
+                for module in modules {
+                    if self.source.item_fn(module, &name)?.is_some() {
+                        let mut args_call = vec![receiver];
+                        for a in m.args.iter() {
+                            args_call.push(self.arg(a)?);
+                        }
+                        return self.module_call(module, &name, args_call);
+                    }
+                }
+                return Err(unsupported("method call", m));
+            }
+        };
+        Ok((s, rt))
+    }
+
+    // ----------------------------
+    // statements
+
+    fn block(
+        &mut self,
+        stmts: &[syn::Stmt],
+        indent: usize,
+        tail: Tail,
+        out: &mut Vec<String>,
+    ) -> Result<(), String> {
+        self.scopes.push(HashMap::new());
+        for (i_stmt, stmt) in stmts.iter().enumerate() {
+            let is_last = i_stmt + 1 == stmts.len();
+            match stmt {
+                syn::Stmt::Local(l) => self.local(l, indent, out)?,
+                syn::Stmt::Item(syn::Item::Use(_)) => {}
+                syn::Stmt::Macro(m) => {
+                    // assertions are not checked in the device code
+                    let name = m.mac.path.segments.last().unwrap().ident.to_string();
+                    if !name.contains("assert") {
+                        return Err(unsupported("macro", m));
+                    }
+                }
+                syn::Stmt::Expr(e, None) if is_last && tail == Tail::Return => {
+                    self.tail(e, indent, out)?
+                }
+                syn::Stmt::Expr(e, _) => self.stmt_expr(e, indent, out)?,
+                _ => return Err(unsupported("statement", stmt)),
+            }
+        }
+        self.scopes.pop();
+        Ok(())
+    }
+
+    fn local(
+        &mut self,
+        l: &syn::Local,
+        indent: usize,
+        out: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let ind = "    ".repeat(indent);
+        let Some(init) = &l.init else {
+            return Err(unsupported("statement", l));
+        };
+        if init.diverge.is_some() {
+            return Err(unsupported("statement", l));
+        }
+        let (value, ty_value) = self.expr_noparen(&init.expr)?;
+        let (pat, ty_decl) = match &l.pat {
+            syn::Pat::Type(pt) => (&*pt.pat, Some(ty_of(&pt.ty, &self.generics, &self.scalar)?)),
+            pat => (pat, None),
+        };
+        match pat {
+            syn::Pat::Ident(pi) => {
+                let qualifier = if pi.mutability.is_some() {
+                    ""
+                } else {
+                    "const "
+                };
+                let (cpp_ty, ty) = match ty_decl {
+                    Some(t) => (t.cpp(), t),
+                    None => ("auto".to_string(), ty_value.decay()),
+                };
+                let name = self.declare(&pi.ident.to_string(), ty);
+                out.push(format!(
+                    "{}{}{} {} = {};",
+                    ind, qualifier, cpp_ty, name, value
+                ));
+            }
+            syn::Pat::Tuple(pt) => {
+                let tys = match ty_value {
+                    Ty::Tuple(ts) => ts,
+                    _ => vec![Ty::Unknown; pt.elems.len()],
+                };
+                let mut is_mut = false;
+                let mut names = vec![];
+                for (p, t) in pt.elems.iter().zip(tys) {
+                    let name = match p {
+                        syn::Pat::Ident(pi) => {
+                            is_mut |= pi.mutability.is_some();
+                            pi.ident.to_string()
+                        }
+                        syn::Pat::Wild(_) => "unused".to_string(),
+                        _ => return Err(unsupported("pattern", p)),
+                    };
+                    names.push(self.declare(&name, t));
+                }
+                let qualifier = if is_mut { "" } else { "const " };
+                out.push(format!(
+                    "{}{}auto [{}] = {};",
+                    ind,
+                    qualifier,
+                    names.join(", "),
+                    value
+                ));
+            }
+            _ => return Err(unsupported("pattern", pat)),
+        }
+        Ok(())
+    }
+
+    fn tail(&mut self, e: &syn::Expr, indent: usize, out: &mut Vec<String>) -> Result<(), String> {
+        match e {
+            syn::Expr::If(i) => self.if_stmt(i, indent, Tail::Return, out),
+            syn::Expr::Block(b) => {
+                let ind = "    ".repeat(indent);
+                out.push(format!("{}{{", ind));
+                self.block(&b.block.stmts, indent + 1, Tail::Return, out)?;
+                out.push(format!("{}}}", ind));
+                Ok(())
+            }
+            syn::Expr::Return(_) => self.stmt_expr(e, indent, out),
+            _ => {
+                let (s, _) = self.expr_noparen(e)?;
+                out.push(format!("{}return {};", "    ".repeat(indent), s));
+                Ok(())
+            }
+        }
+    }
+
+    fn if_stmt(
+        &mut self,
+        i: &syn::ExprIf,
+        indent: usize,
+        tail: Tail,
+        out: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let ind = "    ".repeat(indent);
+        if let syn::Expr::Let(_) = &*i.cond {
+            return Err(unsupported("expression", &i.cond));
+        }
+        let (cond, _) = self.expr_noparen(&i.cond)?;
+        out.push(format!("{}if( {} ){{", ind, cond));
+        self.block(&i.then_branch.stmts, indent + 1, tail, out)?;
+        match &i.else_branch {
+            None => out.push(format!("{}}}", ind)),
+            Some((_, e)) => match &**e {
+                syn::Expr::If(ei) => {
+                    let mut lines = vec![];
+                    self.if_stmt(ei, indent, tail, &mut lines)?;
+                    out.push(format!("{}}} else {}", ind, lines[0].trim_start()));
+                    out.extend(lines.into_iter().skip(1));
+                }
+                syn::Expr::Block(b) => {
+                    out.push(format!("{}}} else {{", ind));
+                    self.block(&b.block.stmts, indent + 1, tail, out)?;
+                    out.push(format!("{}}}", ind));
+                }
+                _ => return Err(unsupported("expression", e)),
+            },
+        }
+        Ok(())
+    }
+
+    fn stmt_expr(
+        &mut self,
+        e: &syn::Expr,
+        indent: usize,
+        out: &mut Vec<String>,
+    ) -> Result<(), String> {
+        let ind = "    ".repeat(indent);
+        match e {
+            syn::Expr::If(i) => self.if_stmt(i, indent, Tail::Discard, out)?,
+            syn::Expr::Block(b) => {
+                out.push(format!("{}{{", ind));
+                self.block(&b.block.stmts, indent + 1, Tail::Discard, out)?;
+                out.push(format!("{}}}", ind));
+            }
+            syn::Expr::ForLoop(f) => {
+                let syn::Pat::Ident(pi) = &*f.pat else {
+                    return Err(unsupported("pattern", &f.pat));
+                };
+                let syn::Expr::Range(range) = &*f.expr else {
+                    return Err(unsupported("loop", f));
+                };
+                let (Some(start), Some(end)) = (&range.start, &range.end) else {
+                    return Err(unsupported("loop", f));
+                };
+                let (start, _) = self.expr_noparen(start)?;
+                let (end, _) = self.expr_noparen(end)?;
+                let op = match range.limits {
+                    syn::RangeLimits::HalfOpen(_) => "<",
+                    syn::RangeLimits::Closed(_) => "<=",
+                };
+                self.scopes.push(HashMap::new());
+                let v = self.declare(&pi.ident.to_string(), Ty::Int("int"));
+                out.push(format!(
+                    "{}for(int {v} = {}; {v} {} {}; ++{v}){{",
+                    ind,
+                    start,
+                    op,
+                    end,
+                    v = v
+                ));
+                self.block(&f.body.stmts, indent + 1, Tail::Discard, out)?;
+                out.push(format!("{}}}", ind));
+                self.scopes.pop();
+            }
+            syn::Expr::Return(r) => match &r.expr {
+                None => out.push(format!("{}return;", ind)),
+                Some(v) => {
+                    let (s, _) = self.expr_noparen(v)?;
+                    out.push(format!("{}return {};", ind, s));
+                }
+            },
+            syn::Expr::Assign(a) => {
+                let (l, _) = self.expr(&a.left)?;
+                let (r, _) = self.expr_noparen(&a.right)?;
+                out.push(format!("{}{} = {};", ind, l, r));
+            }
+            _ => {
+                let (s, _) = self.expr_noparen(e)?;
+                out.push(format!("{}{};", ind, s));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// translate the function `item` in `module` into a `__device__` function.
+/// The modules referred from the function are added to `includes`
+pub(crate) fn translate_fn(
+    source: &Source,
+    module: &str,
+    item: &syn::ItemFn,
+    includes: &mut BTreeSet<String>,
+) -> Result<String, String> {
+    let sig = &item.sig;
+    let generics = generic_names(sig);
+    let scalar = if !generics.is_empty() || !sig.to_token_stream().to_string().contains("f64") {
+        Ty::Float
+    } else {
+        Ty::Double
+    };
+    let ret = return_ty_of(sig, &scalar)?;
+    let mut t = FnTranslator {
+        source,
+        module,
+        generics,
+        scalar,
+        ret,
+        scopes: vec![HashMap::new()],
+        used_names: HashSet::new(),
+        includes,
+    };
+    let mut params = vec![];
+    for input in sig.inputs.iter() {
+        let syn::FnArg::Typed(pt) = input else {
+            return Err(unsupported("parameter", input));
+        };
+        let syn::Pat::Ident(pi) = &*pt.pat else {
+            return Err(unsupported("parameter", input));
+        };
+        let ty = param_ty_of(&pt.ty, &t.generics, &t.scalar)?;
+        let name = t.declare(&pi.ident.to_string(), ty.clone());
+        params.push(format!("    {} {}", ty.cpp(), name));
+    }
+    let mut body = vec![];
+    let tail = if t.ret == Ty::Unit {
+        Tail::Discard
+    } else {
+        Tail::Return
+    };
+    t.block(&item.block.stmts, 1, tail, &mut body)?;
+    //
+    let mut lines = vec![];
+    for attr in item.attrs.iter() {
+        if let syn::Meta::NameValue(nv) = &attr.meta {
+            if nv.path.is_ident("doc") {
+                if let syn::Expr::Lit(syn::ExprLit {
+                    lit: syn::Lit::Str(s),
+                    ..
+                }) = &nv.value
+                {
+                    lines.push(format!("///{}", s.value()).trim_end().to_string());
+                }
+            }
+        }
+    }
+    lines.push("__device__".to_string());
+    lines.push(format!("auto {}(", sig.ident));
+    lines.push(format!("{}) -> {}", params.join(",\n"), t.ret.cpp()));
+    lines.push("{".to_string());
+    lines.extend(body);
+    lines.push("}".to_string());
+    Ok(lines.join("\n"))
+}
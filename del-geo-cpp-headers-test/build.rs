@@ -0,0 +1,24 @@
+fn main() {
+    let path_host = std::path::Path::new(&std::env::var("CARGO_MANIFEST_DIR").unwrap())
+        .join("../del-geo-cpp-headers/host");
+    let headers = del_geo_cpp_headers::HEADERS;
+    let path_include = headers.write_files_to_out_dir().unwrap();
+    // all the headers in one file, to check that they do not conflict with each other
+    let names: Vec<_> = headers.names().collect();
+    std::fs::write(
+        path_include.join("del_geo_amalgamated.h"),
+        headers.amalgamate(&names).unwrap(),
+    )
+    .unwrap();
+    cc::Build::new()
+        .cpp(true)
+        .std("c++17")
+        .include(&path_host)
+        .include(&path_include)
+        .file("src/capi.cpp")
+        .file("src/amalgamated.cpp")
+        .compile("del_geo_cpp_headers_capi");
+    println!("cargo:rerun-if-changed=src/capi.cpp");
+    println!("cargo:rerun-if-changed=src/amalgamated.cpp");
+    println!("cargo:rerun-if-changed=../del-geo-cpp-headers/host");
+}
@@ -0,0 +1,3 @@
+// compile the amalgamated header written by `build.rs`
+
+#include "del_geo_amalgamated.h"
@@ -0,0 +1,167 @@
+// C ABI of the functions in the headers compiled on the host (see `build.rs`)
+
+#include <algorithm>
+#include "aabb3.h"
+#include "tri3.h"
+#include "quaternion.h"
+#include "mat2_sym.h"
+#include "edge2.h"
+#include "obb3.h"
+#include "sphere.h"
+#include "spherical_harmonics.h"
+
+template <typename ARRAY>
+void copy_to(const ARRAY& a, float* out)
+{
+    std::copy(a.begin(), a.end(), out);
+}
+
+extern "C" {
+
+void aabb3_from_point(const float* p, float rad, float* aabb) {
+    copy_to(aabb3::from_point(p, rad), aabb);
+}
+
+void aabb3_set_point(float* aabb, const float* xyz, float rad) {
+    aabb3::set_point(aabb, xyz, rad);
+}
+
+void aabb3_add_point(float* aabb, const float* xyz, float eps) {
+    aabb3::add_point(aabb, xyz, eps);
+}
+
+void aabb3_set_merged_two_aabbs(float* o, const float* i0, const float* i1) {
+    aabb3::set_merged_two_aabbs(o, i0, i1);
+}
+
+bool tri3_intersection_against_ray(
+    const float* p0,
+    const float* p1,
+    const float* p2,
+    const float* ray_org,
+    const float* ray_dir,
+    float* t)
+{
+    const auto res = tri3::intersection_against_ray(p0, p1, p2, ray_org, ray_dir);
+    if( !res ){ return false; }
+    *t = *res;
+    return true;
+}
+
+bool tri3_intersection_against_line_bwd_wrt_tri(
+    const float* p0,
+    const float* p1,
+    const float* p2,
+    const float* org,
+    const float* dir,
+    float d_t,
+    float d_u,
+    float d_v,
+    float* tuv,
+    float* d_p0,
+    float* d_p1,
+    float* d_p2)
+{
+    const auto res = tri3::intersection_against_line_bwd_wrt_tri(p0, p1, p2, org, dir, d_t, d_u, d_v);
+    if( !res ){ return false; }
+    tuv[0] = res->t;
+    tuv[1] = res->u;
+    tuv[2] = res->v;
+    copy_to(res->d_p0, d_p0);
+    copy_to(res->d_p1, d_p1);
+    copy_to(res->d_p2, d_p2);
+    return true;
+}
+
+void quaternion_to_mat3_col_major(const float* q, float* m) {
+    copy_to(quaternion::to_mat3_col_major(q), m);
+}
+
+void mat2_sym_projected_spd_mat3(const float* p_mat, const float* quat, const float* d, float* abc) {
+    copy_to(mat2_sym::projected_spd_mat3(p_mat, quat, d), abc);
+}
+
+void mat2_sym_principal_directions(const float* coeff, float* lam, float* evec) {
+    const auto res = mat2_sym::principal_directions(coeff);
+    copy_to(std::get<0>(res), lam);
+    copy_to(std::get<1>(res)[0], evec);
+    copy_to(std::get<1>(res)[1], evec + 2);
+}
+
+void mat2_sym_safe_inverse(const float* coeff, float* out) {
+    copy_to(mat2_sym::safe_inverse(coeff), out);
+}
+
+void mat2_sym_safe_inverse_preserve_positive_definiteness(const float* abc, float eps, float* out) {
+    copy_to(mat2_sym::safe_inverse_preserve_positive_definiteness(abc, eps), out);
+}
+
+void mat2_sym_aabb2(const float* coeff, float* aabb) {
+    copy_to(mat2_sym::aabb2(coeff), aabb);
+}
+
+float mat2_sym_mult_vec_from_both_sides(const float* m, const float* b, const float* c) {
+    return mat2_sym::mult_vec_from_both_sides(m, b, c);
+}
+
+void edge2_unit_edge_vector(const float* ps, const float* pe, float* out) {
+    copy_to(edge2::unit_edge_vector(ps, pe), out);
+}
+
+float edge2_length(const float* ps, const float* pe) {
+    return edge2::length(ps, pe);
+}
+
+bool edge2_culling_intersection(const float* s0, const float* e0, const float* s1, const float* e1) {
+    return edge2::culling_intersection(s0, e0, s1, e1);
+}
+
+float edge2_winding_number(const float* ps, const float* pe, const float* po) {
+    return edge2::winding_number(ps, pe, po);
+}
+
+bool obb3_is_include_point(const float* obb, const float* p, float eps) {
+    return obb3::is_include_point(obb, p, eps);
+}
+
+void obb3_unit_axes_and_half_edge_lengths(const float* obb, float* axes, float* sizes) {
+    const auto [a, s] = obb3::unit_axes_and_half_edge_lengths(obb);
+    for(int i = 0; i < 3; ++i){ copy_to(a[i], axes + i * 3); }
+    copy_to(s, sizes);
+}
+
+void obb3_nearest_to_point3(const float* obb, const float* p, float* out) {
+    copy_to(obb3::nearest_to_point3(obb, p), out);
+}
+
+bool sphere_intersection_ray(
+    float rad,
+    const float* center,
+    const float* ray_src,
+    const float* ray_dir,
+    float* t)
+{
+    const auto res = sphere::intersection_ray(rad, center, ray_src, ray_dir);
+    if( !res ){ return false; }
+    *t = *res;
+    return true;
+}
+
+float sphere_area(float r) {
+    return sphere::area(r);
+}
+
+void sphere_sample(const float* rnd, float* out) {
+    copy_to(sphere::sample(rnd), out);
+}
+
+double spherical_harmonics_normalize(double* x, double* y, double* z) {
+    return spherical_harmonics::normalize(*x, *y, *z);
+}
+
+void spherical_harmonics_sph_coeff_buffer(int8_t n, double x, double y, double z, double* out) {
+    const auto res = spherical_harmonics::sph_coeff_buffer(n, x, y, z);
+    std::copy(res.begin(), res.end(), out);
+}
+
+}
@@ -0,0 +1,332 @@
+//! functions of `del-geo-cpp-headers` compiled as plain C++ on the host and called through the C ABI.
+//! used to test that the headers agree with `del-geo-core` (see `tests`)
+
+mod ffi {
+    extern "C" {
+        pub fn aabb3_from_point(p: *const f32, rad: f32, aabb: *mut f32);
+        pub fn aabb3_set_point(aabb: *mut f32, xyz: *const f32, rad: f32);
+        pub fn aabb3_add_point(aabb: *mut f32, xyz: *const f32, eps: f32);
+        pub fn aabb3_set_merged_two_aabbs(o: *mut f32, i0: *const f32, i1: *const f32);
+        pub fn tri3_intersection_against_ray(
+            p0: *const f32,
+            p1: *const f32,
+            p2: *const f32,
+            ray_org: *const f32,
+            ray_dir: *const f32,
+            t: *mut f32,
+        ) -> bool;
+        pub fn tri3_intersection_against_line_bwd_wrt_tri(
+            p0: *const f32,
+            p1: *const f32,
+            p2: *const f32,
+            org: *const f32,
+            dir: *const f32,
+            d_t: f32,
+            d_u: f32,
+            d_v: f32,
+            tuv: *mut f32,
+            d_p0: *mut f32,
+            d_p1: *mut f32,
+            d_p2: *mut f32,
+        ) -> bool;
+        pub fn quaternion_to_mat3_col_major(q: *const f32, m: *mut f32);
+        pub fn mat2_sym_projected_spd_mat3(
+            p_mat: *const f32,
+            quat: *const f32,
+            d: *const f32,
+            abc: *mut f32,
+        );
+        pub fn mat2_sym_principal_directions(coeff: *const f32, lam: *mut f32, evec: *mut f32);
+        pub fn mat2_sym_safe_inverse(coeff: *const f32, out: *mut f32);
+        pub fn mat2_sym_safe_inverse_preserve_positive_definiteness(
+            abc: *const f32,
+            eps: f32,
+            out: *mut f32,
+        );
+        pub fn mat2_sym_aabb2(coeff: *const f32, aabb: *mut f32);
+        pub fn mat2_sym_mult_vec_from_both_sides(
+            m: *const f32,
+            b: *const f32,
+            c: *const f32,
+        ) -> f32;
+        pub fn edge2_length(ps: *const f32, pe: *const f32) -> f32;
+        pub fn edge2_unit_edge_vector(ps: *const f32, pe: *const f32, out: *mut f32);
+        pub fn edge2_culling_intersection(
+            s0: *const f32,
+            e0: *const f32,
+            s1: *const f32,
+            e1: *const f32,
+        ) -> bool;
+        pub fn edge2_winding_number(ps: *const f32, pe: *const f32, po: *const f32) -> f32;
+        pub fn obb3_is_include_point(obb: *const f32, p: *const f32, eps: f32) -> bool;
+        pub fn obb3_unit_axes_and_half_edge_lengths(
+            obb: *const f32,
+            axes: *mut f32,
+            sizes: *mut f32,
+        );
+        pub fn obb3_nearest_to_point3(obb: *const f32, p: *const f32, out: *mut f32);
+        pub fn sphere_intersection_ray(
+            rad: f32,
+            center: *const f32,
+            ray_src: *const f32,
+            ray_dir: *const f32,
+            t: *mut f32,
+        ) -> bool;
+        pub fn sphere_area(r: f32) -> f32;
+        pub fn sphere_sample(rnd: *const f32, out: *mut f32);
+        pub fn spherical_harmonics_normalize(x: *mut f64, y: *mut f64, z: *mut f64) -> f64;
+        pub fn spherical_harmonics_sph_coeff_buffer(n: i8, x: f64, y: f64, z: f64, out: *mut f64);
+    }
+}
+
+/// `aabb3.h`
+pub mod aabb3 {
+    pub fn from_point(p: &[f32; 3], rad: f32) -> [f32; 6] {
+        let mut aabb = [0f32; 6];
+        unsafe { super::ffi::aabb3_from_point(p.as_ptr(), rad, aabb.as_mut_ptr()) };
+        aabb
+    }
+
+    pub fn set_point(aabb: &mut [f32; 6], xyz: &[f32; 3], rad: f32) {
+        unsafe { super::ffi::aabb3_set_point(aabb.as_mut_ptr(), xyz.as_ptr(), rad) };
+    }
+
+    pub fn add_point(aabb: &mut [f32; 6], xyz: &[f32; 3], eps: f32) {
+        unsafe { super::ffi::aabb3_add_point(aabb.as_mut_ptr(), xyz.as_ptr(), eps) };
+    }
+
+    pub fn set_merged_two_aabbs(i0: &[f32; 6], i1: &[f32; 6]) -> [f32; 6] {
+        let mut o = [0f32; 6];
+        unsafe { super::ffi::aabb3_set_merged_two_aabbs(o.as_mut_ptr(), i0.as_ptr(), i1.as_ptr()) };
+        o
+    }
+}
+
+/// `tri3.h`
+pub mod tri3 {
+    pub fn intersection_against_ray(
+        p0: &[f32; 3],
+        p1: &[f32; 3],
+        p2: &[f32; 3],
+        ray_org: &[f32; 3],
+        ray_dir: &[f32; 3],
+    ) -> Option<f32> {
+        let mut t = 0f32;
+        let res = unsafe {
+            super::ffi::tri3_intersection_against_ray(
+                p0.as_ptr(),
+                p1.as_ptr(),
+                p2.as_ptr(),
+                ray_org.as_ptr(),
+                ray_dir.as_ptr(),
+                &mut t,
+            )
+        };
+        res.then_some(t)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    #[allow(clippy::type_complexity)]
+    pub fn intersection_against_line_bwd_wrt_tri(
+        p0: &[f32; 3],
+        p1: &[f32; 3],
+        p2: &[f32; 3],
+        org: &[f32; 3],
+        dir: &[f32; 3],
+        d_t: f32,
+        d_u: f32,
+        d_v: f32,
+    ) -> Option<(f32, f32, f32, [f32; 3], [f32; 3], [f32; 3])> {
+        let mut tuv = [0f32; 3];
+        let mut d_p0 = [0f32; 3];
+        let mut d_p1 = [0f32; 3];
+        let mut d_p2 = [0f32; 3];
+        let res = unsafe {
+            super::ffi::tri3_intersection_against_line_bwd_wrt_tri(
+                p0.as_ptr(),
+                p1.as_ptr(),
+                p2.as_ptr(),
+                org.as_ptr(),
+                dir.as_ptr(),
+                d_t,
+                d_u,
+                d_v,
+                tuv.as_mut_ptr(),
+                d_p0.as_mut_ptr(),
+                d_p1.as_mut_ptr(),
+                d_p2.as_mut_ptr(),
+            )
+        };
+        res.then_some((tuv[0], tuv[1], tuv[2], d_p0, d_p1, d_p2))
+    }
+}
+
+/// `quaternion.h`
+pub mod quaternion {
+    pub fn to_mat3_col_major(q: &[f32; 4]) -> [f32; 9] {
+        let mut m = [0f32; 9];
+        unsafe { super::ffi::quaternion_to_mat3_col_major(q.as_ptr(), m.as_mut_ptr()) };
+        m
+    }
+}
+
+/// `mat2_sym.h`
+pub mod mat2_sym {
+    pub fn projected_spd_mat3(p_mat: &[f32; 6], quat: &[f32; 4], d: &[f32; 3]) -> [f32; 3] {
+        let mut abc = [0f32; 3];
+        unsafe {
+            super::ffi::mat2_sym_projected_spd_mat3(
+                p_mat.as_ptr(),
+                quat.as_ptr(),
+                d.as_ptr(),
+                abc.as_mut_ptr(),
+            )
+        };
+        abc
+    }
+
+    pub fn principal_directions(coeff: &[f32; 3]) -> ([f32; 2], [[f32; 2]; 2]) {
+        let mut lam = [0f32; 2];
+        let mut evec = [0f32; 4];
+        unsafe {
+            super::ffi::mat2_sym_principal_directions(
+                coeff.as_ptr(),
+                lam.as_mut_ptr(),
+                evec.as_mut_ptr(),
+            )
+        };
+        (lam, [[evec[0], evec[1]], [evec[2], evec[3]]])
+    }
+
+    pub fn safe_inverse(coeff: &[f32; 3]) -> [f32; 3] {
+        let mut out = [0f32; 3];
+        unsafe { super::ffi::mat2_sym_safe_inverse(coeff.as_ptr(), out.as_mut_ptr()) };
+        out
+    }
+
+    pub fn safe_inverse_preserve_positive_definiteness(abc: &[f32; 3], eps: f32) -> [f32; 3] {
+        let mut out = [0f32; 3];
+        unsafe {
+            super::ffi::mat2_sym_safe_inverse_preserve_positive_definiteness(
+                abc.as_ptr(),
+                eps,
+                out.as_mut_ptr(),
+            )
+        };
+        out
+    }
+
+    pub fn aabb2(coeff: &[f32; 3]) -> [f32; 4] {
+        let mut aabb = [0f32; 4];
+        unsafe { super::ffi::mat2_sym_aabb2(coeff.as_ptr(), aabb.as_mut_ptr()) };
+        aabb
+    }
+
+    pub fn mult_vec_from_both_sides(m: &[f32; 3], b: &[f32; 2], c: &[f32; 2]) -> f32 {
+        unsafe { super::ffi::mat2_sym_mult_vec_from_both_sides(m.as_ptr(), b.as_ptr(), c.as_ptr()) }
+    }
+}
+
+/// `edge2.h`
+pub mod edge2 {
+    pub fn length(ps: &[f32; 2], pe: &[f32; 2]) -> f32 {
+        unsafe { super::ffi::edge2_length(ps.as_ptr(), pe.as_ptr()) }
+    }
+
+    pub fn unit_edge_vector(ps: &[f32; 2], pe: &[f32; 2]) -> [f32; 2] {
+        let mut out = [0f32; 2];
+        unsafe { super::ffi::edge2_unit_edge_vector(ps.as_ptr(), pe.as_ptr(), out.as_mut_ptr()) };
+        out
+    }
+
+    pub fn culling_intersection(
+        s0: &[f32; 2],
+        e0: &[f32; 2],
+        s1: &[f32; 2],
+        e1: &[f32; 2],
+    ) -> bool {
+        unsafe {
+            super::ffi::edge2_culling_intersection(
+                s0.as_ptr(),
+                e0.as_ptr(),
+                s1.as_ptr(),
+                e1.as_ptr(),
+            )
+        }
+    }
+
+    pub fn winding_number(ps: &[f32; 2], pe: &[f32; 2], po: &[f32; 2]) -> f32 {
+        unsafe { super::ffi::edge2_winding_number(ps.as_ptr(), pe.as_ptr(), po.as_ptr()) }
+    }
+}
+
+/// `obb3.h`
+pub mod obb3 {
+    pub fn is_include_point(obb: &[f32; 12], p: &[f32; 3], eps: f32) -> bool {
+        unsafe { super::ffi::obb3_is_include_point(obb.as_ptr(), p.as_ptr(), eps) }
+    }
+
+    pub fn unit_axes_and_half_edge_lengths(obb: &[f32; 12]) -> ([[f32; 3]; 3], [f32; 3]) {
+        let mut axes = [[0f32; 3]; 3];
+        let mut sizes = [0f32; 3];
+        unsafe {
+            super::ffi::obb3_unit_axes_and_half_edge_lengths(
+                obb.as_ptr(),
+                axes.as_flattened_mut().as_mut_ptr(),
+                sizes.as_mut_ptr(),
+            )
+        };
+        (axes, sizes)
+    }
+
+    pub fn nearest_to_point3(obb: &[f32; 12], p: &[f32; 3]) -> [f32; 3] {
+        let mut out = [0f32; 3];
+        unsafe { super::ffi::obb3_nearest_to_point3(obb.as_ptr(), p.as_ptr(), out.as_mut_ptr()) };
+        out
+    }
+}
+
+/// `sphere.h`
+pub mod sphere {
+    pub fn intersection_ray(
+        rad: f32,
+        center: &[f32; 3],
+        ray_src: &[f32; 3],
+        ray_dir: &[f32; 3],
+    ) -> Option<f32> {
+        let mut t = 0f32;
+        let res = unsafe {
+            super::ffi::sphere_intersection_ray(
+                rad,
+                center.as_ptr(),
+                ray_src.as_ptr(),
+                ray_dir.as_ptr(),
+                &mut t,
+            )
+        };
+        res.then_some(t)
+    }
+
+    pub fn area(r: f32) -> f32 {
+        unsafe { super::ffi::sphere_area(r) }
+    }
+
+    pub fn sample(rnd: &[f32; 2]) -> [f32; 3] {
+        let mut out = [0f32; 3];
+        unsafe { super::ffi::sphere_sample(rnd.as_ptr(), out.as_mut_ptr()) };
+        out
+    }
+}
+
+/// `spherical_harmonics.h`
+pub mod spherical_harmonics {
+    pub fn normalize(x: &mut f64, y: &mut f64, z: &mut f64) -> f64 {
+        unsafe { super::ffi::spherical_harmonics_normalize(x, y, z) }
+    }
+
+    pub fn sph_coeff_buffer(n: i8, x: f64, y: f64, z: f64) -> [f64; 100] {
+        let mut out = [0f64; 100];
+        unsafe { super::ffi::spherical_harmonics_sph_coeff_buffer(n, x, y, z, out.as_mut_ptr()) };
+        out
+    }
+}
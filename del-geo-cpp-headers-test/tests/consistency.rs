@@ -0,0 +1,253 @@
+//! compare the headers in `del-geo-cpp-headers` with the corresponding functions in `del-geo-core`
+
+use del_geo_cpp_headers_test as cpp;
+use rand::{Rng, SeedableRng};
+
+const NUM_SAMPLE: usize = 1000;
+
+fn random_array<const N: usize>(rng: &mut rand_chacha::ChaChaRng) -> [f32; N] {
+    std::array::from_fn(|_| rng.gen::<f32>() * 2. - 1.)
+}
+
+/// random symmetric positive definite matrix `[a,b,c]`
+fn random_spd(rng: &mut rand_chacha::ChaChaRng) -> [f32; 3] {
+    let a = rng.gen::<f32>() + 0.1;
+    let c = rng.gen::<f32>() + 0.1;
+    let b = (rng.gen::<f32>() * 2. - 1.) * (a * c).sqrt() * 0.9;
+    [a, b, c]
+}
+
+fn assert_close(a: &[f32], b: &[f32], tol: f32) {
+    assert_eq!(a.len(), b.len());
+    let scale = a.iter().fold(1f32, |s, v| s.max(v.abs()));
+    for (va, vb) in a.iter().zip(b.iter()) {
+        assert!((va - vb).abs() <= tol * scale, "{:?} {:?}", a, b);
+    }
+}
+
+#[test]
+fn test_aabb3() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _ in 0..NUM_SAMPLE {
+        let p = random_array::<3>(&mut rng);
+        let rad = rng.gen::<f32>();
+        let mut aabb0 = [0f32; 6];
+        del_geo_core::aabb3::set_as_cube(&mut aabb0, &p, rad);
+        assert_eq!(cpp::aabb3::from_point(&p, rad), aabb0);
+        let mut aabb1 = [0f32; 6];
+        cpp::aabb3::set_point(&mut aabb1, &p, rad);
+        assert_eq!(aabb1, aabb0);
+        //
+        let q = random_array::<3>(&mut rng);
+        let eps = rng.gen::<f32>() * 0.1;
+        del_geo_core::aabb3::add_point(&mut aabb0, &q, eps);
+        cpp::aabb3::add_point(&mut aabb1, &q, eps);
+        assert_eq!(aabb1, aabb0);
+        //
+        let mut aabb2 = [0f32; 6];
+        del_geo_core::aabb3::set_as_cube(&mut aabb2, &random_array(&mut rng), rng.gen());
+        assert_eq!(
+            cpp::aabb3::set_merged_two_aabbs(&aabb0, &aabb2),
+            del_geo_core::aabb3::from_two_aabbs(&aabb0, &aabb2)
+        );
+    }
+}
+
+#[test]
+fn test_tri3() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _ in 0..NUM_SAMPLE {
+        let p0 = random_array::<3>(&mut rng);
+        let p1 = random_array::<3>(&mut rng);
+        let p2 = random_array::<3>(&mut rng);
+        let org = random_array::<3>(&mut rng);
+        let dir = random_array::<3>(&mut rng);
+        let t0 = del_geo_core::tri3::intersection_against_line(&p0, &p1, &p2, &org, &dir);
+        let t1 = cpp::tri3::intersection_against_ray(&p0, &p1, &p2, &org, &dir);
+        assert_eq!(t0.is_some(), t1.is_some());
+        if let (Some(t0), Some(t1)) = (t0, t1) {
+            assert_close(&[t0], &[t1], 1.0e-5);
+        }
+        //
+        let (d_t, d_u, d_v) = (rng.gen::<f32>(), rng.gen::<f32>(), rng.gen::<f32>());
+        let Some(res1) = cpp::tri3::intersection_against_line_bwd_wrt_tri(
+            &p0, &p1, &p2, &org, &dir, d_t, d_u, d_v,
+        ) else {
+            continue;
+        };
+        num_hit += 1;
+        let res0 = del_geo_core::tri3::intersection_against_line_bwd_wrt_tri(
+            &p0, &p1, &p2, &org, &dir, d_t, d_u, d_v,
+        );
+        assert_close(&[res0.0, res0.1, res0.2], &[res1.0, res1.1, res1.2], 1.0e-5);
+        assert_close(&res0.3, &res1.3, 1.0e-4);
+        assert_close(&res0.4, &res1.4, 1.0e-4);
+        assert_close(&res0.5, &res1.5, 1.0e-4);
+    }
+    assert!(num_hit > 10);
+}
+
+#[test]
+fn test_quaternion() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _ in 0..NUM_SAMPLE {
+        let q = del_geo_core::quaternion::normalized(&random_array::<4>(&mut rng));
+        assert_close(
+            &cpp::quaternion::to_mat3_col_major(&q),
+            &del_geo_core::quaternion::to_mat3_col_major(&q),
+            1.0e-6,
+        );
+    }
+}
+
+#[test]
+fn test_mat2_sym() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _ in 0..NUM_SAMPLE {
+        let p_mat = random_array::<6>(&mut rng);
+        let quat = del_geo_core::quaternion::normalized(&random_array::<4>(&mut rng));
+        let d = random_array::<3>(&mut rng);
+        assert_close(
+            &cpp::mat2_sym::projected_spd_mat3(&p_mat, &quat, &d),
+            &del_geo_core::mat2_sym::wdw_projected_spd_mat3(&p_mat, &quat, &d).0,
+            1.0e-5,
+        );
+        //
+        let abc = random_array::<3>(&mut rng);
+        let (lam0, evec0) = del_geo_core::mat2_sym::principal_directions(&abc);
+        let (lam1, evec1) = cpp::mat2_sym::principal_directions(&abc);
+        assert_close(&lam0, &lam1, 1.0e-5);
+        assert_close(evec0.as_flattened(), evec1.as_flattened(), 1.0e-5);
+        assert_close(
+            &cpp::mat2_sym::safe_inverse(&abc),
+            &del_geo_core::mat2_sym::safe_inverse(&abc),
+            1.0e-5,
+        );
+        //
+        let spd = random_spd(&mut rng);
+        assert_close(
+            &cpp::mat2_sym::safe_inverse_preserve_positive_definiteness(&spd, 1.0e-3),
+            &del_geo_core::mat2_sym::safe_inverse_preserve_positive_definiteness(&spd, 1.0e-3),
+            1.0e-5,
+        );
+        assert_close(
+            &cpp::mat2_sym::aabb2(&spd),
+            &del_geo_core::mat2_sym::aabb2(&spd),
+            1.0e-5,
+        );
+        let b = random_array::<2>(&mut rng);
+        let c = random_array::<2>(&mut rng);
+        assert_close(
+            &[cpp::mat2_sym::mult_vec_from_both_sides(&abc, &b, &c)],
+            &[del_geo_core::mat2_sym::mult_vec_from_both_sides(
+                &abc, &b, &c,
+            )],
+            1.0e-6,
+        );
+    }
+}
+
+#[test]
+fn test_edge2() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _ in 0..NUM_SAMPLE {
+        let ps = random_array::<2>(&mut rng);
+        let pe = random_array::<2>(&mut rng);
+        let po = random_array::<2>(&mut rng);
+        let pf = random_array::<2>(&mut rng);
+        assert_close(
+            &[cpp::edge2::length(&ps, &pe)],
+            &[del_geo_core::edge2::length(&ps, &pe)],
+            1.0e-6,
+        );
+        assert_close(
+            &cpp::edge2::unit_edge_vector(&ps, &pe),
+            &del_geo_core::edge2::unit_edge_vector(&ps, &pe),
+            1.0e-6,
+        );
+        assert_eq!(
+            cpp::edge2::culling_intersection(&ps, &pe, &po, &pf),
+            del_geo_core::edge2::culling_intersection(&ps, &pe, &po, &pf)
+        );
+        assert_close(
+            &[cpp::edge2::winding_number(&ps, &pe, &po)],
+            &[del_geo_core::edge2::winding_number(&ps, &pe, &po)],
+            1.0e-6,
+        );
+    }
+}
+
+#[test]
+fn test_obb3() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _ in 0..NUM_SAMPLE {
+        let obb = del_geo_core::obb3::from_random::<_, f32>(&mut rng);
+        let p = random_array::<3>(&mut rng);
+        let eps = rng.gen::<f32>() * 0.1;
+        assert_eq!(
+            cpp::obb3::is_include_point(&obb, &p, eps),
+            del_geo_core::obb3::is_include_point(&obb, &p, eps)
+        );
+        let (axes0, sizes0) = del_geo_core::obb3::unit_axes_and_half_edge_lengths(&obb);
+        let (axes1, sizes1) = cpp::obb3::unit_axes_and_half_edge_lengths(&obb);
+        assert_close(axes0.as_flattened(), axes1.as_flattened(), 1.0e-6);
+        assert_close(&sizes0, &sizes1, 1.0e-6);
+        assert_close(
+            &cpp::obb3::nearest_to_point3(&obb, &p),
+            &del_geo_core::obb3::nearest_to_point3(&obb, &p),
+            1.0e-5,
+        );
+    }
+}
+
+#[test]
+fn test_sphere() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    let mut num_hit = 0;
+    for _ in 0..NUM_SAMPLE {
+        let rad = rng.gen::<f32>();
+        let center = random_array::<3>(&mut rng);
+        let ray_src = random_array::<3>(&mut rng);
+        let ray_dir = random_array::<3>(&mut rng);
+        let t0 = del_geo_core::sphere::intersection_ray(rad, &center, &ray_src, &ray_dir);
+        let t1 = cpp::sphere::intersection_ray(rad, &center, &ray_src, &ray_dir);
+        assert_eq!(t0.is_some(), t1.is_some());
+        if let (Some(t0), Some(t1)) = (t0, t1) {
+            num_hit += 1;
+            assert_close(&[t0], &[t1], 1.0e-5);
+        }
+        assert_close(
+            &[cpp::sphere::area(rad)],
+            &[del_geo_core::sphere::area(rad)],
+            1.0e-6,
+        );
+        let rnd = [rng.gen::<f32>(), rng.gen::<f32>()];
+        assert_close(
+            &cpp::sphere::sample(&rnd),
+            &del_geo_core::sphere::sample(&rnd),
+            1.0e-5,
+        );
+    }
+    assert!(num_hit > 10);
+}
+
+#[test]
+fn test_spherical_harmonics() {
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0u64);
+    for _ in 0..NUM_SAMPLE {
+        let (mut x0, mut y0, mut z0) = (rng.gen::<f64>(), rng.gen::<f64>(), rng.gen::<f64>());
+        let (mut x1, mut y1, mut z1) = (x0, y0, z0);
+        let r0 = del_geo_core::spherical_harmonics::normalize(&mut x0, &mut y0, &mut z0);
+        let r1 = cpp::spherical_harmonics::normalize(&mut x1, &mut y1, &mut z1);
+        assert!((r0 - r1).abs() < 1.0e-12);
+        assert!((x0 - x1).abs() + (y0 - y1).abs() + (z0 - z1).abs() < 1.0e-12);
+        for n in 0..10 {
+            let c0 = del_geo_core::spherical_harmonics::sph_coeff_buffer(n, x0, y0, z0);
+            let c1 = cpp::spherical_harmonics::sph_coeff_buffer(n, x0, y0, z0);
+            for (v0, v1) in c0.iter().zip(c1.iter()) {
+                assert!((v0 - v1).abs() < 1.0e-10, "{} {}", v0, v1);
+            }
+        }
+    }
+}
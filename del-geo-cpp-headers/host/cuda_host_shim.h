@@ -0,0 +1,20 @@
+#pragma once
+// shim to compile the headers as plain C++ on the host without the CUDA toolkit.
+// put the directory `host` in the include path.
+
+#include <algorithm>
+#include <cmath>
+#include <cstdint>
+
+#ifndef __device__
+#define __device__
+#endif
+
+using std::min;
+using std::max;
+using std::sqrt;
+using std::fabs;
+using std::atan2;
+using std::acos;
+using std::cos;
+using std::sin;
@@ -0,0 +1,74 @@
+#pragma once
+// generated from del-geo-core/src/edge2.rs by del-geo-cpp-codegen. do not edit
+#include <cuda/std/array>
+#include "vec2.h"
+
+namespace edge2 {
+
+__device__
+auto length(
+    const float* ps,
+    const float* pe) -> float
+{
+    const auto dx = ps[0] - pe[0];
+    const auto dy = ps[1] - pe[1];
+    return sqrt(dx * dx + dy * dy);
+}
+
+__device__
+auto unit_edge_vector(
+    const float* ps,
+    const float* pe) -> cuda::std::array<float,2>
+{
+    const auto dx = pe[0] - ps[0];
+    const auto dy = pe[1] - ps[1];
+    const float linv = 1.0f / sqrt(dx * dx + dy * dy);
+    return cuda::std::array<float,2>{dx * linv, dy * linv};
+}
+
+__device__
+auto culling_intersection(
+    const float* po_s0,
+    const float* po_e0,
+    const float* po_s1,
+    const float* po_e1) -> bool
+{
+    const auto min0x = min(po_s0[0], po_e0[0]);
+    const auto max0x = max(po_s0[0], po_e0[0]);
+    const auto min1x = min(po_s1[0], po_e1[0]);
+    const auto max1x = max(po_s1[0], po_e1[0]);
+    const auto min0y = min(po_s0[1], po_e0[1]);
+    const auto max0y = max(po_s0[1], po_e0[1]);
+    const auto min1y = min(po_s1[1], po_e1[1]);
+    const auto max1y = max(po_s1[1], po_e1[1]);
+    const auto len = ((max0x - min0x) + (max0y - min0y) + (max1x - min1x) + (max1y - min1y)) * float(0.0001);
+    if( max1x + len < min0x ){
+        return false;
+    }
+    if( max0x + len < min1x ){
+        return false;
+    }
+    if( max1y + len < min0y ){
+        return false;
+    }
+    if( max0y + len < min1y ){
+        return false;
+    }
+    return true;
+}
+
+__device__
+auto winding_number(
+    const float* ps,
+    const float* pe,
+    const float* po) -> float
+{
+    const auto half = 1.0f / (1.0f + 1.0f);
+    const auto p0 = vec2::sub(ps, po);
+    const auto p1 = vec2::sub(pe, po);
+    const float y = p1[1] * p0[0] - p1[0] * p0[1];
+    const float x = p0[0] * p1[0] + p0[1] * p1[1];
+    return atan2(y, x) * 0.318309886183790671538f * half;
+}
+
+}
@@ -1,33 +1,169 @@
+//! CUDA headers of the functions in `del-geo-core`
+
+/// `(name, content)` of the headers
+const LIST: &[(&str, &str)] = &[
+    ("aabb.h", include_str!("aabb.h")),
+    ("aabb2.h", include_str!("aabb2.h")),
+    ("aabb3.h", include_str!("aabb3.h")),
+    ("mat2_sym.h", include_str!("mat2_sym.h")),
+    ("mat2x3_col_major.h", include_str!("mat2x3_col_major.h")),
+    ("mat3_col_major.h", include_str!("mat3_col_major.h")),
+    ("mat4_col_major.h", include_str!("mat4_col_major.h")),
+    ("quaternion.h", include_str!("quaternion.h")),
+    ("tri3.h", include_str!("tri3.h")),
+    ("vec3.h", include_str!("vec3.h")),
+    // generated by `del-geo-cpp-codegen`
+    ("edge2.h", include_str!("edge2.h")),
+    ("obb3.h", include_str!("obb3.h")),
+    ("sphere.h", include_str!("sphere.h")),
+    (
+        "spherical_harmonics.h",
+        include_str!("spherical_harmonics.h"),
+    ),
+    ("vec2.h", include_str!("vec2.h")),
+];
+
 pub struct Headers {}
 
 pub const HEADERS: Headers = Headers {};
 
 impl Headers {
-    pub fn get(&self, idx: u32) -> Option<(&str, &str)> {
-        match idx {
-            0 => Some(("aabb.h", include_str!("aabb.h"))),
-            1 => Some(("aabb2.h", include_str!("aabb2.h"))),
-            2 => Some(("aabb3.h", include_str!("aabb3.h"))),
-            3 => Some(("mat2_sym.h", include_str!("mat2_sym.h"))),
-            4 => Some(("mat2x3_col_major.h", include_str!("mat2x3_col_major.h"))),
-            5 => Some(("mat3_col_major.h", include_str!("mat3_col_major.h"))),
-            6 => Some(("mat4_col_major.h", include_str!("mat4_col_major.h"))),
-            7 => Some(("quaternion.h", include_str!("quaternion.h"))),
-            8 => Some(("tri3.h", include_str!("tri3.h"))),
-            9 => Some(("vec3.h", include_str!("vec3.h"))),
-            _ => None,
+    pub fn get(&self, idx: u32) -> Option<(&'static str, &'static str)> {
+        LIST.get(idx as usize).copied()
+    }
+
+    /// names of all the headers (e.g., `vec3.h`)
+    pub fn names(&self) -> impl Iterator<Item = &'static str> {
+        LIST.iter().map(|v| v.0)
+    }
+
+    /// content of the header `name` (e.g., `vec3.h`)
+    pub fn get_by_name(&self, name: &str) -> Option<&'static str> {
+        LIST.iter().find(|v| v.0 == name).map(|v| v.1)
+    }
+
+    /// headers in this crate included by the header `name` with `#include "..."`
+    pub fn dependencies(&self, name: &str) -> Option<Vec<&'static str>> {
+        let content = self.get_by_name(name)?;
+        Some(content.lines().filter_map(local_include).collect())
+    }
+
+    /// the headers `names` and all their dependencies where a header comes after its dependencies.
+    /// `None` if any of the headers is not found
+    pub fn resolve(&self, names: &[&str]) -> Option<Vec<&'static str>> {
+        fn visit(name: &str, order: &mut Vec<&'static str>) -> Option<()> {
+            let (name, _) = LIST.iter().find(|v| v.0 == name)?;
+            if order.contains(name) {
+                return Some(());
+            }
+            for dep in HEADERS.dependencies(name)? {
+                visit(dep, order)?;
+            }
+            order.push(name);
+            Some(())
+        }
+        let mut order = vec![];
+        for name in names {
+            visit(name, &mut order)?;
         }
+        Some(order)
     }
 
-    pub fn write_files(&self, path_out_dir: &std::path::Path) {
-        for header_idx in 0.. {
-            use std::io::Write;
-            let Some((header_name, header_content)) = self.get(header_idx) else {
-                break;
-            };
-            let path = path_out_dir.join(header_name);
-            let mut output = std::fs::File::create(path).unwrap();
-            write!(output, "{}", header_content).unwrap();
+    /// single header containing the headers `names` and their dependencies.
+    /// The system includes are gathered at the top
+    pub fn amalgamate(&self, names: &[&str]) -> Option<String> {
+        let mut system_includes: Vec<&str> = vec![];
+        let mut bodies = vec![];
+        for name in self.resolve(names)? {
+            let mut body = format!("// ---- {}\n", name);
+            for line in self.get_by_name(name)?.lines() {
+                let trimmed = line.trim();
+                if trimmed == "#pragma once" || local_include(line).is_some() {
+                    continue;
+                }
+                if trimmed.starts_with("#include") {
+                    if !system_includes.contains(&trimmed) {
+                        system_includes.push(trimmed);
+                    }
+                    continue;
+                }
+                body.push_str(line);
+                body.push('\n');
+            }
+            bodies.push(body);
+        }
+        let mut out = "#pragma once\n".to_string();
+        for include in system_includes {
+            out.push_str(include);
+            out.push('\n');
         }
+        for body in bodies {
+            out.push('\n');
+            out.push_str(&body);
+        }
+        Some(out)
+    }
+
+    pub fn write_files(&self, path_out_dir: &std::path::Path) -> std::io::Result<()> {
+        for (name, content) in LIST {
+            std::fs::write(path_out_dir.join(name), content)?;
+        }
+        Ok(())
+    }
+
+    /// write the headers into `$OUT_DIR/del-geo-cpp-headers` and return the include path.
+    /// to be called from `build.rs`
+    pub fn write_files_to_out_dir(&self) -> std::io::Result<std::path::PathBuf> {
+        let Some(out_dir) = std::env::var_os("OUT_DIR") else {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "OUT_DIR is not set. call this from build.rs",
+            ));
+        };
+        let path_dir = std::path::Path::new(&out_dir).join("del-geo-cpp-headers");
+        std::fs::create_dir_all(&path_dir)?;
+        self.write_files(&path_dir)?;
+        Ok(path_dir)
+    }
+}
+
+/// `vec3.h` for the line `#include "vec3.h"`
+fn local_include(line: &str) -> Option<&'static str> {
+    let name = line
+        .trim()
+        .strip_prefix("#include")?
+        .trim()
+        .strip_prefix('"')?
+        .strip_suffix('"')?;
+    LIST.iter().find(|v| v.0 == name).map(|v| v.0)
+}
+
+#[test]
+fn test_dependencies() {
+    assert_eq!(HEADERS.names().count(), LIST.len());
+    // all the local includes are in this crate
+    for (name, content) in LIST {
+        let num_include = content
+            .lines()
+            .filter(|l| l.trim().starts_with("#include \""))
+            .count();
+        assert_eq!(
+            HEADERS.dependencies(name).unwrap().len(),
+            num_include,
+            "{}",
+            name
+        );
     }
+    assert_eq!(HEADERS.dependencies("tri3.h").unwrap(), vec!["vec3.h"]);
+    assert_eq!(
+        HEADERS.resolve(&["mat4_col_major.h", "tri3.h"]).unwrap(),
+        vec!["vec3.h", "mat3_col_major.h", "mat4_col_major.h", "tri3.h"]
+    );
+    assert!(HEADERS.resolve(&["foo.h"]).is_none());
+    let all: Vec<_> = HEADERS.names().collect();
+    let src = HEADERS.amalgamate(&all).unwrap();
+    assert_eq!(src.matches("#pragma once").count(), 1);
+    assert_eq!(src.matches("#include <cuda/std/array>").count(), 1);
+    assert!(!src.contains("#include \""));
+    assert!(src.find("namespace vec3 {").unwrap() < src.find("namespace tri3 {").unwrap());
 }
@@ -1,3 +1,4 @@
+#pragma once
 #include <cuda/std/array>
 #include <cuda/std/tuple>
 #include <float.h>
@@ -62,7 +63,7 @@ auto safe_inverse(
     const float b = coeff[1];
     const float c = coeff[2];
     const float det = a * c - b * b;
-    if( det <= FLT_EPSILON ){
+    if( fabs(det) <= FLT_EPSILON ){
         float l = 1.f / FLT_EPSILON;
         float a1 = a + FLT_EPSILON;
         float c1 = c + FLT_EPSILON;
@@ -0,0 +1,73 @@
+#pragma once
+// generated from del-geo-core/src/obb3.rs by del-geo-cpp-codegen. do not edit
+#include <cuda/std/array>
+#include <cuda/std/tuple>
+#include "vec3.h"
+
+namespace obb3 {
+
+__device__
+auto is_include_point(
+    const float* obb,
+    const float* p,
+    float eps) -> bool
+{
+    const auto s = 1.0f + eps;
+    const auto d = cuda::std::array<float,3>{p[0] - obb[0], p[1] - obb[1], p[2] - obb[2]};
+    {
+        const auto lx = obb[3] * obb[3] + obb[4] * obb[4] + obb[5] * obb[5];
+        const auto dx = obb[3] * d[0] + obb[4] * d[1] + obb[5] * d[2];
+        if( fabs(dx) > lx * s ){
+            return false;
+        }
+    }
+    {
+        const auto ly = obb[6] * obb[6] + obb[7] * obb[7] + obb[8] * obb[8];
+        const auto dy = obb[6] * d[0] + obb[7] * d[1] + obb[8] * d[2];
+        if( fabs(dy) > ly * s ){
+            return false;
+        }
+    }
+    {
+        const auto lz = obb[9] * obb[9] + obb[10] * obb[10] + obb[11] * obb[11];
+        const auto dz = obb[9] * d[0] + obb[10] * d[1] + obb[11] * d[2];
+        if( fabs(dz) > lz * s ){
+            return false;
+        }
+    }
+    return true;
+}
+
+/// return the normalized axes and the magnitude of each axis
+__device__
+auto unit_axes_and_half_edge_lengths(
+    const float* obb) -> cuda::std::tuple<cuda::std::array<cuda::std::array<float,3>,3>, cuda::std::array<float,3>>
+{
+    const auto l0 = sqrt(obb[3] * obb[3] + obb[4] * obb[4] + obb[5] * obb[5]);
+    const auto l1 = sqrt(obb[6] * obb[6] + obb[7] * obb[7] + obb[8] * obb[8]);
+    const auto l2 = sqrt(obb[9] * obb[9] + obb[10] * obb[10] + obb[11] * obb[11]);
+    const auto l0_inv = 1.0f / l0;
+    const auto l1_inv = 1.0f / l1;
+    const auto l2_inv = 1.0f / l2;
+    const auto axes = cuda::std::array<cuda::std::array<float,3>,3>{cuda::std::array<float,3>{obb[3] * l0_inv, obb[4] * l0_inv, obb[5] * l0_inv}, cuda::std::array<float,3>{obb[6] * l1_inv, obb[7] * l1_inv, obb[8] * l1_inv}, cuda::std::array<float,3>{obb[9] * l2_inv, obb[10] * l2_inv, obb[11] * l2_inv}};
+    const auto sizes = cuda::std::array<float,3>{l0, l1, l2};
+    return cuda::std::make_tuple(axes, sizes);
+}
+
+__device__
+auto nearest_to_point3(
+    const float* obb,
+    const float* p) -> cuda::std::array<float,3>
+{
+    if( is_include_point(obb, p, 0.0f) ){
+        return cuda::std::array<float,3>{p[0], p[1], p[2]};
+    }
+    const auto [axes, hlen] = unit_axes_and_half_edge_lengths(obb);
+    const auto d = cuda::std::array<float,3>{p[0] - obb[0], p[1] - obb[1], p[2] - obb[2]};
+    const auto t0 = min(max(vec3::dot(axes[0].data(), d.data()), -hlen[0]), hlen[0]);
+    const auto t1 = min(max(vec3::dot(axes[1].data(), d.data()), -hlen[1]), hlen[1]);
+    const auto t2 = min(max(vec3::dot(axes[2].data(), d.data()), -hlen[2]), hlen[2]);
+    return cuda::std::array<float,3>{obb[0] + t0 * axes[0][0] + t1 * axes[1][0] + t2 * axes[2][0], obb[1] + t0 * axes[0][1] + t1 * axes[1][1] + t2 * axes[2][1], obb[2] + t0 * axes[0][2] + t1 * axes[1][2] + t2 * axes[2][2]};
+}
+
+}
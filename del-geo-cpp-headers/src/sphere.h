@@ -0,0 +1,51 @@
+#pragma once
+// generated from del-geo-core/src/sphere.rs by del-geo-cpp-codegen. do not edit
+#include <cuda/std/array>
+#include <cuda/std/optional>
+#include "vec3.h"
+
+namespace sphere {
+
+__device__
+auto intersection_ray(
+    float rad,
+    const float* center,
+    const float* ray_src,
+    const float* ray_dir) -> cuda::std::optional<float>
+{
+    const auto op = vec3::sub(ray_src, center);
+    const auto a = vec3::dot(ray_dir, ray_dir);
+    const auto b = vec3::dot(op.data(), ray_dir);
+    const auto c = vec3::dot(op.data(), op.data()) - rad * rad;
+    const float det = b * b - c * a;
+    if( det < 0.0f ){
+        return cuda::std::nullopt;
+    } else {
+        const auto det_1 = sqrt(det);
+        if( -b - det_1 >= 0.0f ){
+            return (-b - det_1) / a;
+        } else if( -b + det_1 >= 0.0f ){
+            return (-b + det_1) / a;
+        }
+        return cuda::std::nullopt;
+    }
+}
+
+__device__
+auto area(
+    float r) -> float
+{
+    return r * r * 4.0f * 3.14159265358979323846f;
+}
+
+/// <https://corysimon.github.io/articles/uniformdistn-on-sphere/>
+__device__
+auto sample(
+    const float* rnd) -> cuda::std::array<float,3>
+{
+    const auto phi = acos(1.0f - 2.0f * rnd[0]);
+    const auto theta = 2.0f * 3.14159265358979323846f * rnd[1];
+    return cuda::std::array<float,3>{cos(theta) * sin(phi), sin(theta) * sin(phi), cos(phi)};
+}
+
+}
@@ -0,0 +1,235 @@
+#pragma once
+// generated from del-geo-core/src/spherical_harmonics.rs by del-geo-cpp-codegen. do not edit
+#include <cuda/std/array>
+#include <cuda/std/complex>
+
+namespace spherical_harmonics {
+
+/// Calculate the normalization of the vector.
+__device__
+auto normalize(
+    double& x,
+    double& y,
+    double& z) -> double
+{
+    const auto r = sqrt(x * x + y * y + z * z);
+    const auto invr = 1.0 / r;
+    x *= invr;
+    y *= invr;
+    z *= invr;
+    return r;
+}
+
+/// Calculate the coefficients of the spherical harmonics for l <= 9 and store them in an array buffer.
+/// Try to access the coefficient Y_l^m by the index: base + l + m, where base = l^2.
+__device__
+auto sph_coeff_buffer(
+    int8_t n,
+    double x,
+    double y,
+    double z) -> cuda::std::array<double,100>
+{
+    const auto inv_pi = 1.0 / 3.14159265358979323846;
+    const auto ep = cuda::std::complex<double>(x, y);
+    auto res = cuda::std::array<double,100>{};
+    res[0] = 0.5 * sqrt(inv_pi);
+    if( n == 0 ){
+        return res;
+    }
+    const auto r1 = ep.real();
+    const auto i1 = ep.imag();
+    const auto v1 = -0.5 * sqrt(1.5 * inv_pi);
+    res[1] = v1 * i1;
+    res[2] = 0.5 * sqrt(3.0 * inv_pi) * z;
+    res[3] = v1 * r1;
+    if( n == 1 ){
+        return res;
+    }
+    const auto ep2 = ep * ep;
+    const auto r2 = ep2.real();
+    const auto i2 = ep2.imag();
+    const auto v1_1 = -0.5 * sqrt(7.5 * inv_pi) * z;
+    const auto v2 = 0.25 * sqrt(7.5 * inv_pi);
+    res[4] = v2 * i2;
+    res[5] = v1_1 * i1;
+    res[6] = 0.25 * sqrt(5.0 * inv_pi) * (2.0 * z * z - x * x - y * y);
+    res[7] = v1_1 * r1;
+    res[8] = v2 * r2;
+    if( n == 2 ){
+        return res;
+    }
+    const auto ep3 = ep2 * ep;
+    const auto r3 = ep3.real();
+    const auto i3 = ep3.imag();
+    const auto v1_2 = -0.125 * sqrt(21.0 * inv_pi) * (4.0 * z * z - x * x - y * y);
+    const auto v2_1 = 0.250 * sqrt(52.5 * inv_pi) * z;
+    const auto v3 = -0.125 * sqrt(35.0 * inv_pi);
+    res[9] = v3 * i3;
+    res[10] = v2_1 * i2;
+    res[11] = v1_2 * i1;
+    res[12] = 0.250 * sqrt(7.0 * inv_pi) * z * (-3.0 * x * x - 3.0 * y * y + 2.0 * z * z);
+    res[13] = v1_2 * r1;
+    res[14] = v2_1 * r2;
+    res[15] = v3 * r3;
+    if( n == 3 ){
+        return res;
+    }
+    const auto ep4 = ep3 * ep;
+    const auto r4 = ep4.real();
+    const auto i4 = ep4.imag();
+    const auto z2 = z * z;
+    const auto v1_3 = -3.0 / 8.00 * sqrt(5.0 * inv_pi) * z * (7.0 * z2 - 3.0);
+    const auto v2_2 = 3.0 / 8.00 * sqrt(5.0 * 0.5 * inv_pi) * (7.0 * z2 - 1.0);
+    const auto v3_1 = -3.0 / 8.00 * sqrt(35.0 * inv_pi) * z;
+    const auto v4 = 3.0 / 16.0 * sqrt(35.0 * 0.5 * inv_pi);
+    res[16] = v4 * i4;
+    res[17] = v3_1 * i3;
+    res[18] = v2_2 * i2;
+    res[19] = v1_3 * i1;
+    res[20] = 3.0 / 16.0 * sqrt(inv_pi) * (35.0 * z2 * z2 - 30.0 * z2 + 3.0);
+    res[21] = v1_3 * r1;
+    res[22] = v2_2 * r2;
+    res[23] = v3_1 * r3;
+    res[24] = v4 * r4;
+    if( n == 4 ){
+        return res;
+    }
+    const auto r5 = (ep4 * ep).real();
+    const auto i5 = (ep4 * ep).imag();
+    const auto z4 = z2 * z2;
+    const auto v1_4 = -1.0 / 16.0 * sqrt(82.5 * inv_pi) * (21.0 * z4 - 14.0 * z2 + 1.0);
+    const auto v2_3 = 1.0 / 8.00 * sqrt(577.5 * inv_pi) * z * (3.0 * z2 - 1.0);
+    const auto v3_2 = -1.0 / 32.0 * sqrt(385.0 * inv_pi) * (9.0 * z2 - 1.0);
+    const auto v4_1 = 3.0 / 16.0 * sqrt(192.5 * inv_pi) * z;
+    const auto v5 = -3.0 / 32.0 * sqrt(77.0 * inv_pi);
+    res[25] = v5 * i5;
+    res[26] = v4_1 * i4;
+    res[27] = v3_2 * i3;
+    res[28] = v2_3 * i2;
+    res[29] = v1_4 * i1;
+    res[30] = 1.0 / 16.0 * sqrt(11.0 * inv_pi) * z * (63.0 * z4 - 70.0 * z2 + 15.0);
+    res[31] = v1_4 * r1;
+    res[32] = v2_3 * r2;
+    res[33] = v3_2 * r3;
+    res[34] = v4_1 * r4;
+    res[35] = v5 * r5;
+    if( n == 5 ){
+        return res;
+    }
+    const auto r6 = (ep4 * ep2).real();
+    const auto i6 = (ep4 * ep2).imag();
+    const auto v1_5 = -1.0 / 16.0 * sqrt(273.0 * 0.5 * inv_pi) * z * (33.0 * z4 - 30.0 * z2 + 5.0);
+    const auto v2_4 = 1.0 / 64.0 * sqrt(1365.0 * inv_pi) * (33.0 * z4 - 18.0 * z2 + 1.0);
+    const auto v3_3 = -1.0 / 32.0 * sqrt(1365.0 * inv_pi) * z * (11.0 * z2 - 3.0);
+    const auto v4_2 = 3.0 / 32.0 * sqrt(91.0 * 0.5 * inv_pi) * (11.0 * z2 - 1.0);
+    const auto v5_1 = -3.0 / 32.0 * sqrt(1001.0 * inv_pi) * z;
+    const auto v6 = 1.0 / 64.0 * sqrt(3003.0 * inv_pi);
+    res[36] = v6 * i6;
+    res[37] = v5_1 * i5;
+    res[38] = v4_2 * i4;
+    res[39] = v3_3 * i3;
+    res[40] = v2_4 * i2;
+    res[41] = v1_5 * i1;
+    res[42] = 1.0 / 32.0 * sqrt(13.0 * inv_pi) * (231.0 * z4 * z2 - 315.0 * z4 + 105.0 * z2 - 5.0);
+    res[43] = v1_5 * r1;
+    res[44] = v2_4 * r2;
+    res[45] = v3_3 * r3;
+    res[46] = v4_2 * r4;
+    res[47] = v5_1 * r5;
+    res[48] = v6 * r6;
+    if( n == 6 ){
+        return res;
+    }
+    const auto r7 = (ep4 * ep2 * ep).real();
+    const auto i7 = (ep4 * ep2 * ep).imag();
+    const auto v1_6 = -1.0 / 64.0 * sqrt(105.0 * 0.5 * inv_pi) * (429.0 * z4 * z2 - 495.0 * z4 + 135.0 * z2 - 5.0);
+    const auto v2_5 = 3.0 / 64.0 * sqrt(35.0 * inv_pi) * (143.0 * z4 * z - 110.0 * z2 * z + 15.0 * z);
+    const auto v3_4 = -3.0 / 64.0 * sqrt(35.0 * 0.5 * inv_pi) * (143.0 * z4 - 66.0 * z2 + 3.0);
+    const auto v4_3 = 3.0 / 32.0 * sqrt(385.0 * 0.5 * inv_pi) * (13.0 * z2 * z - 3.0 * z);
+    const auto v5_2 = -3.0 / 64.0 * sqrt(385.0 * 0.5 * inv_pi) * (13.0 * z2 - 1.0);
+    const auto v6_1 = 3.0 / 64.0 * sqrt(5005.0 * inv_pi) * z;
+    const auto v7 = -3.0 / 128.0 * sqrt(1430.0 * inv_pi);
+    res[49] = v7 * i7;
+    res[50] = v6_1 * i6;
+    res[51] = v5_2 * i5;
+    res[52] = v4_3 * i4;
+    res[53] = v3_4 * i3;
+    res[54] = v2_5 * i2;
+    res[55] = v1_6 * i1;
+    res[56] = 1.0 / 32.0 * sqrt(15.0 * inv_pi) * (429.0 * z4 * z2 * z - 693.0 * z4 * z + 315.0 * z2 * z - 35.0 * z);
+    res[57] = v1_6 * r1;
+    res[58] = v2_5 * r2;
+    res[59] = v3_4 * r3;
+    res[60] = v4_3 * r4;
+    res[61] = v5_2 * r5;
+    res[62] = v6_1 * r6;
+    res[63] = v7 * r7;
+    if( n == 7 ){
+        return res;
+    }
+    const auto ep8 = ep4 * ep4;
+    const auto r8 = ep8.real();
+    const auto i8 = ep8.imag();
+    const auto z8 = z4 * z4;
+    const auto v1_7 = -3.0 / 64.00 * sqrt(17.0 * 0.5 * inv_pi) * (715.0 * z4 * z2 * z - 1001.0 * z4 * z + 385.0 * z2 * z - 35.0 * z);
+    const auto v2_6 = 3.0 / 128.0 * sqrt(595.0 * inv_pi) * (143.0 * z4 * z2 - 143.0 * z4 + 33.0 * z2 - 1.0);
+    const auto v3_5 = -1.0 / 64.00 * sqrt(19635.0 * 0.5 * inv_pi) * (39.0 * z4 * z - 26.0 * z2 * z + 3.0 * z);
+    const auto v4_4 = 3.0 / 128.0 * sqrt(1309.0 * 0.5 * inv_pi) * (65.0 * z4 - 26.0 * z2 + 1.0);
+    const auto v5_3 = -3.0 / 64.00 * sqrt(17017.0 * 0.5 * inv_pi) * (5.0 * z2 * z - z);
+    const auto v6_2 = 1.0 / 128.0 * sqrt(7293.0 * inv_pi) * (15.0 * z2 - 1.0);
+    const auto v7_1 = -3.0 / 64.00 * sqrt(12155.0 * 0.5 * inv_pi) * z;
+    const auto v8 = 3.0 / 256.0 * sqrt(12155.0 * 0.5 * inv_pi);
+    res[64] = v8 * i8;
+    res[65] = v7_1 * i7;
+    res[66] = v6_2 * i6;
+    res[67] = v5_3 * i5;
+    res[68] = v4_4 * i4;
+    res[69] = v3_5 * i3;
+    res[70] = v2_6 * i2;
+    res[71] = v1_7 * i1;
+    res[72] = 1.0 / 256.0 * sqrt(17.0 * inv_pi) * (6435.0 * z8 - 12012.0 * z4 * z2 + 6930.0 * z4 - 1260.0 * z2 + 35.0);
+    res[73] = v1_7 * r1;
+    res[74] = v2_6 * r2;
+    res[75] = v3_5 * r3;
+    res[76] = v4_4 * r4;
+    res[77] = v5_3 * r5;
+    res[78] = v6_2 * r6;
+    res[79] = v7_1 * r7;
+    res[80] = v8 * r8;
+    if( n == 8 ){
+        return res;
+    }
+    const auto r9 = (ep8 * ep).real();
+    const auto i9 = (ep8 * ep).imag();
+    const auto v1_8 = -3.0 / 256.0 * sqrt(95.0 * 0.5 * inv_pi) * (2431.0 * z8 - 4004.0 * z4 * z2 + 2002.0 * z4 - 308.0 * z2 + 7.0);
+    const auto v2_7 = 3.0 / 128.0 * sqrt(1045.0 * inv_pi) * z * (221.0 * z4 * z2 - 273.0 * z4 + 91.0 * z2 - 7.0);
+    const auto v3_6 = -1.0 / 256.0 * sqrt(21945.0 * inv_pi) * (221.0 * z4 * z2 - 195.0 * z4 + 39.0 * z2 - 1.0);
+    const auto v4_5 = 3.0 / 256.0 * sqrt(95095.0 * 2.0 * inv_pi) * z * (17.0 * z4 - 10.0 * z2 + 1.0);
+    const auto v5_4 = -3.0 / 256.0 * sqrt(2717.0 * inv_pi) * (85.0 * z4 - 30.0 * z2 + 1.0);
+    const auto v6_3 = 1.0 / 128.0 * sqrt(40755.0 * inv_pi) * z * (17.0 * z2 - 3.0);
+    const auto v7_2 = -3.0 / 512.0 * sqrt(13585.0 * inv_pi) * (17.0 * z2 - 1.0);
+    const auto v8_1 = 3.0 / 512.0 * sqrt(230945.0 * 2.0 * inv_pi) * z;
+    const auto v9 = -1.0 / 512.0 * sqrt(230945.0 * inv_pi);
+    res[81] = v9 * i9;
+    res[82] = v8_1 * i8;
+    res[83] = v7_2 * i7;
+    res[84] = v6_3 * i6;
+    res[85] = v5_4 * i5;
+    res[86] = v4_5 * i4;
+    res[87] = v3_6 * i3;
+    res[88] = v2_7 * i2;
+    res[89] = v1_8 * i1;
+    res[90] = 1.0 / 256.0 * sqrt(19.0 * inv_pi) * z * (12155.0 * z8 - 25740.0 * z4 * z2 + 18018.0 * z4 - 4620.0 * z2 + 315.0);
+    res[91] = v1_8 * r1;
+    res[92] = v2_7 * r2;
+    res[93] = v3_6 * r3;
+    res[94] = v4_5 * r4;
+    res[95] = v5_4 * r5;
+    res[96] = v6_3 * r6;
+    res[97] = v7_2 * r7;
+    res[98] = v8_1 * r8;
+    res[99] = v9 * r9;
+    return res;
+}
+
+}
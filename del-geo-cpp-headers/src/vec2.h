@@ -0,0 +1,70 @@
+#pragma once
+// generated from del-geo-core/src/vec2.rs by del-geo-cpp-codegen. do not edit
+#include <cuda/std/array>
+
+namespace vec2 {
+
+__device__
+auto length(
+    const float* p) -> float
+{
+    return sqrt(p[0] * p[0] + p[1] * p[1]);
+}
+
+__device__
+auto squared_length(
+    const float* p) -> float
+{
+    return p[0] * p[0] + p[1] * p[1];
+}
+
+__device__
+auto sub(
+    const float* a,
+    const float* b) -> cuda::std::array<float,2>
+{
+    return cuda::std::array<float,2>{a[0] - b[0], a[1] - b[1]};
+}
+
+__device__
+auto add(
+    const float* a,
+    const float* b) -> cuda::std::array<float,2>
+{
+    return cuda::std::array<float,2>{a[0] + b[0], a[1] + b[1]};
+}
+
+__device__
+auto scale(
+    const float* a,
+    float s) -> cuda::std::array<float,2>
+{
+    return cuda::std::array<float,2>{a[0] * s, a[1] * s};
+}
+
+__device__
+auto dot(
+    const float* a,
+    const float* b) -> float
+{
+    return a[0] * b[0] + a[1] * b[1];
+}
+
+__device__
+auto area_quadrilateral(
+    const float* a,
+    const float* b) -> float
+{
+    return a[0] * b[1] - a[1] * b[0];
+}
+
+__device__
+auto axpy(
+    float alpha,
+    const float* x,
+    const float* y) -> cuda::std::array<float,2>
+{
+    return cuda::std::array<float,2>{alpha * x[0] + y[0], alpha * x[1] + y[1]};
+}
+
+}
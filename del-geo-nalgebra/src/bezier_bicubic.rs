@@ -0,0 +1,234 @@
+//! functions for bicubic Bezier patch
+//! the control points are stored as `cps[i*4+j]` where `i` is the index along `u` and `j` is along `v`
+
+use num_traits::AsPrimitive;
+
+fn bernstein3<Real>(t: Real) -> ([Real; 4], [Real; 4])
+where
+    Real: nalgebra::RealField + Copy,
+{
+    let one = Real::one();
+    let two = one + one;
+    let three = two + one;
+    let s = one - t;
+    let b = [s * s * s, three * t * s * s, three * t * t * s, t * t * t];
+    let db = [
+        -three * s * s,
+        three * s * s - (two * three) * t * s,
+        (two * three) * t * s - three * t * t,
+        three * t * t,
+    ];
+    (b, db)
+}
+
+pub fn eval<Real>(cps: &[nalgebra::Vector3<Real>; 16], u: Real, v: Real) -> nalgebra::Vector3<Real>
+where
+    Real: nalgebra::RealField + Copy,
+{
+    eval_with_derivatives(cps, u, v).0
+}
+
+/// position and its derivatives w.r.t. `u` and `v`
+pub fn eval_with_derivatives<Real>(
+    cps: &[nalgebra::Vector3<Real>; 16],
+    u: Real,
+    v: Real,
+) -> (
+    nalgebra::Vector3<Real>,
+    nalgebra::Vector3<Real>,
+    nalgebra::Vector3<Real>,
+)
+where
+    Real: nalgebra::RealField + Copy,
+{
+    let (bu, dbu) = bernstein3(u);
+    let (bv, dbv) = bernstein3(v);
+    let mut p = nalgebra::Vector3::<Real>::zeros();
+    let mut dpdu = nalgebra::Vector3::<Real>::zeros();
+    let mut dpdv = nalgebra::Vector3::<Real>::zeros();
+    for i in 0..4 {
+        for j in 0..4 {
+            let c = &cps[i * 4 + j];
+            p += c.scale(bu[i] * bv[j]);
+            dpdu += c.scale(dbu[i] * bv[j]);
+            dpdv += c.scale(bu[i] * dbv[j]);
+        }
+    }
+    (p, dpdu, dpdv)
+}
+
+/// axis-aligned bounding box of the control points, which bounds the patch
+/// by the convex hull property
+pub fn aabb3<Real>(cps: &[nalgebra::Vector3<Real>; 16]) -> [Real; 6]
+where
+    Real: nalgebra::RealField + Copy,
+{
+    let (pmin, pmax) = cps
+        .iter()
+        .skip(1)
+        .fold((cps[0], cps[0]), |(a, b), p| (a.inf(p), b.sup(p)));
+    [pmin.x, pmin.y, pmin.z, pmax.x, pmax.y, pmax.z]
+}
+
+/// split the patch into four sub-patches at `u=0.5` and `v=0.5` using de Casteljau's algorithm
+/// # return
+/// sub-patches in the order of `[u-lower v-lower, u-lower v-upper, u-upper v-lower, u-upper v-upper]`
+pub fn split<Real>(cps: &[nalgebra::Vector3<Real>; 16]) -> [[nalgebra::Vector3<Real>; 16]; 4]
+where
+    Real: nalgebra::RealField + Copy,
+    f64: AsPrimitive<Real>,
+{
+    let half: Real = 0.5f64.as_();
+    let split_curve = |c: [nalgebra::Vector3<Real>; 4]| {
+        let c01 = c[0].lerp(&c[1], half);
+        let c12 = c[1].lerp(&c[2], half);
+        let c23 = c[2].lerp(&c[3], half);
+        let c012 = c01.lerp(&c12, half);
+        let c123 = c12.lerp(&c23, half);
+        let c0123 = c012.lerp(&c123, half);
+        ([c[0], c01, c012, c0123], [c0123, c123, c23, c[3]])
+    };
+    // split along u for each column j
+    let mut ul = [nalgebra::Vector3::<Real>::zeros(); 16];
+    let mut uu = [nalgebra::Vector3::<Real>::zeros(); 16];
+    for j in 0..4 {
+        let (a, b) = split_curve(std::array::from_fn(|i| cps[i * 4 + j]));
+        for i in 0..4 {
+            ul[i * 4 + j] = a[i];
+            uu[i * 4 + j] = b[i];
+        }
+    }
+    // split along v for each row i
+    let split_v = |cps: &[nalgebra::Vector3<Real>; 16]| {
+        let mut vl = [nalgebra::Vector3::<Real>::zeros(); 16];
+        let mut vu = [nalgebra::Vector3::<Real>::zeros(); 16];
+        for i in 0..4 {
+            let (a, b) = split_curve(std::array::from_fn(|j| cps[i * 4 + j]));
+            vl[i * 4..i * 4 + 4].copy_from_slice(&a);
+            vu[i * 4..i * 4 + 4].copy_from_slice(&b);
+        }
+        (vl, vu)
+    };
+    let (p00, p01) = split_v(&ul);
+    let (p10, p11) = split_v(&uu);
+    [p00, p01, p10, p11]
+}
+
+/// intersection between a ray and a bicubic Bezier patch.
+/// The patch is subdivided recursively while culling the sub-patches with their bounding boxes,
+/// then Newton's method is applied from the center of each remaining sub-patch.
+/// * `ray_dir` - direction of the ray (not necessarily a unit vector)
+/// * `max_depth` - number of recursive subdivision
+/// * `epsilon` - tolerance of the residual distance in Newton's method
+/// # return
+/// `Some((t, u, v))` where `ray_org + t * ray_dir = eval(cps, u, v)`
+pub fn intersection_against_ray<Real>(
+    cps: &[nalgebra::Vector3<Real>; 16],
+    ray_org: &nalgebra::Vector3<Real>,
+    ray_dir: &nalgebra::Vector3<Real>,
+    max_depth: usize,
+    epsilon: Real,
+) -> Option<(Real, Real, Real)>
+where
+    Real: nalgebra::RealField + Copy + num_traits::Float,
+    f64: AsPrimitive<Real>,
+{
+    let half: Real = 0.5f64.as_();
+    let org = [ray_org.x, ray_org.y, ray_org.z];
+    let dir = [ray_dir.x, ray_dir.y, ray_dir.z];
+    let mut res: Option<(Real, Real, Real)> = None;
+    let mut stack = vec![(*cps, Real::zero(), Real::zero(), Real::one(), 0)];
+    while let Some((sub, u0, v0, size, depth)) = stack.pop() {
+        let aabb = aabb3(&sub);
+        let Some((tmin, tmax)) = del_geo_core::aabb::intersections_against_ray(&aabb, &org, &dir)
+        else {
+            continue;
+        };
+        if let Some((t, _, _)) = res {
+            if tmin > t {
+                continue;
+            }
+        }
+        if depth < max_depth {
+            let hsize = size * half;
+            let [p00, p01, p10, p11] = split(&sub);
+            stack.push((p00, u0, v0, hsize, depth + 1));
+            stack.push((p01, u0, v0 + hsize, hsize, depth + 1));
+            stack.push((p10, u0 + hsize, v0, hsize, depth + 1));
+            stack.push((p11, u0 + hsize, v0 + hsize, hsize, depth + 1));
+            continue;
+        }
+        // Newton's method for (u, v, t) solving eval(u,v) - ray_org - t * ray_dir = 0
+        let mut u = u0 + size * half;
+        let mut v = v0 + size * half;
+        let mut t = num_traits::Float::max(tmin, Real::zero()) * half + tmax * half;
+        let mut is_converged = false;
+        for _itr in 0..16 {
+            let (p, dpdu, dpdv) = eval_with_derivatives(cps, u, v);
+            let r = p - ray_org - ray_dir.scale(t);
+            if r.norm() < epsilon {
+                is_converged = true;
+                break;
+            }
+            let jac = nalgebra::Matrix3::<Real>::from_columns(&[dpdu, dpdv, -ray_dir]);
+            let Some(jac_inv) = jac.try_inverse() else {
+                break;
+            };
+            let d = jac_inv * r;
+            u -= d.x;
+            v -= d.y;
+            t -= d.z;
+        }
+        if !is_converged {
+            continue;
+        }
+        // accept the solution only inside the sub-patch to avoid reporting the same hit twice
+        let tol: Real = size * 1.0e-3f64.as_();
+        if u < u0 - tol || u > u0 + size + tol || v < v0 - tol || v > v0 + size + tol {
+            continue;
+        }
+        if u < Real::zero() || u > Real::one() || v < Real::zero() || v > Real::one() {
+            continue;
+        }
+        if t < Real::zero() {
+            continue;
+        }
+        if res.is_none() || t < res.unwrap().0 {
+            res = Some((t, u, v));
+        }
+    }
+    res
+}
+
+#[test]
+fn test_intersection_against_ray() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..100 {
+        // height field patch where x = u and y = v
+        let cps: [nalgebra::Vector3<f64>; 16] = std::array::from_fn(|k| {
+            let (i, j) = (k / 4, k % 4);
+            nalgebra::Vector3::<f64>::new(i as f64 / 3., j as f64 / 3., rng.gen::<f64>() - 0.5)
+        });
+        {
+            let (p, dpdu, dpdv) = eval_with_derivatives(&cps, 0.3, 0.6);
+            let eps = 1.0e-6;
+            let du = (eval(&cps, 0.3 + eps, 0.6) - p) / eps;
+            let dv = (eval(&cps, 0.3, 0.6 + eps) - p) / eps;
+            assert!((du - dpdu).norm() < 1.0e-4);
+            assert!((dv - dpdv).norm() < 1.0e-4);
+        }
+        let (u0, v0) = (rng.gen::<f64>(), rng.gen::<f64>());
+        let org = nalgebra::Vector3::<f64>::new(u0, v0, 2.);
+        let dir = nalgebra::Vector3::<f64>::new(0., 0., -0.5);
+        let (t, u, v) = intersection_against_ray(&cps, &org, &dir, 3, 1.0e-10).unwrap();
+        assert!((u - u0).abs() < 1.0e-8);
+        assert!((v - v0).abs() < 1.0e-8);
+        let p = eval(&cps, u0, v0);
+        assert!((org + dir.scale(t) - p).norm() < 1.0e-8);
+        // ray outside the patch
+        let org = nalgebra::Vector3::<f64>::new(1.1, v0, 2.);
+        assert!(intersection_against_ray(&cps, &org, &dir, 3, 1.0e-10).is_none());
+    }
+}
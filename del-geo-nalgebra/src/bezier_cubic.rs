@@ -2,6 +2,7 @@
 
 use num_traits::AsPrimitive;
 
+#[derive(Clone, Copy)]
 pub struct ControlPoints<'a, Real, const N: usize> {
     pub p0: &'a nalgebra::SVector<Real, N>,
     pub p1: &'a nalgebra::SVector<Real, N>,
@@ -171,3 +172,570 @@ fn test() {
         assert!(dev < 0.007, "{}", dev);
     }
 }
+
+/// split the curve at the parameter `t` using de Casteljau's algorithm
+/// # return
+/// control points of the curves in `[0,t]` and `[t,1]`
+#[allow(clippy::type_complexity)]
+pub fn split<Real, const N: usize>(
+    cps: &ControlPoints<Real, N>,
+    t: Real,
+) -> (
+    [nalgebra::SVector<Real, N>; 4],
+    [nalgebra::SVector<Real, N>; 4],
+)
+where
+    Real: nalgebra::RealField + Copy,
+{
+    let lerp = |a: &nalgebra::SVector<Real, N>, b: &nalgebra::SVector<Real, N>| a.lerp(b, t);
+    let p01 = lerp(cps.p0, cps.p1);
+    let p12 = lerp(cps.p1, cps.p2);
+    let p23 = lerp(cps.p2, cps.p3);
+    let p012 = lerp(&p01, &p12);
+    let p123 = lerp(&p12, &p23);
+    let p0123 = lerp(&p012, &p123);
+    ([*cps.p0, p01, p012, p0123], [p0123, p123, p23, *cps.p3])
+}
+
+/// tight axis-aligned bounding box of the curve.
+/// extrema are computed from the roots of the derivative for each coordinate.
+/// # return
+/// `(min, max)` corners of the box
+pub fn aabb<Real, const N: usize>(
+    cps: &ControlPoints<Real, N>,
+) -> (nalgebra::SVector<Real, N>, nalgebra::SVector<Real, N>)
+where
+    Real: nalgebra::RealField + Copy + num_traits::Float + std::fmt::Debug,
+    i64: AsPrimitive<Real>,
+{
+    let mut pmin = cps.p0.inf(cps.p3);
+    let mut pmax = cps.p0.sup(cps.p3);
+    let two = Real::one() + Real::one();
+    for i_dim in 0..N {
+        // derivative divided by three: a*(1-t)^2 + 2*b*(1-t)*t + c*t^2
+        let a = cps.p1[i_dim] - cps.p0[i_dim];
+        let b = cps.p2[i_dim] - cps.p1[i_dim];
+        let c = cps.p3[i_dim] - cps.p2[i_dim];
+        let roots =
+            del_geo_core::polynomial_root::quadratic_roots(a, two * (b - a), a - two * b + c);
+        for t in roots {
+            if t <= Real::zero() || t >= Real::one() {
+                continue;
+            }
+            let x = eval(cps.p0, cps.p1, cps.p2, cps.p3, t)[i_dim];
+            pmin[i_dim] = num_traits::Float::min(pmin[i_dim], x);
+            pmax[i_dim] = num_traits::Float::max(pmax[i_dim], x);
+        }
+    }
+    (pmin, pmax)
+}
+
+#[test]
+fn test_aabb() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let ps: [nalgebra::Vector3<f64>; 4] =
+            std::array::from_fn(|_| crate::vec3::sample_unit_cube(&mut rng));
+        let cps = ControlPoints {
+            p0: &ps[0],
+            p1: &ps[1],
+            p2: &ps[2],
+            p3: &ps[3],
+        };
+        let (pmin, pmax) = aabb(&cps);
+        let qs = sample_uniform_param(1000, &ps[0], &ps[1], &ps[2], &ps[3], true, true);
+        let mut qmin = qs[0];
+        let mut qmax = qs[0];
+        for q in qs.iter() {
+            assert!(q.iter().zip(pmin.iter()).all(|(a, b)| a >= &(b - 1.0e-10)));
+            assert!(q.iter().zip(pmax.iter()).all(|(a, b)| a <= &(b + 1.0e-10)));
+            qmin = qmin.inf(q);
+            qmax = qmax.sup(q);
+        }
+        assert!((qmin - pmin).norm() < 1.0e-5);
+        assert!((qmax - pmax).norm() < 1.0e-5);
+    }
+}
+
+/// intersection between a ray and a strand swept along the curve with a constant width
+/// (e.g., a hair or a fiber). The strand is treated as a ribbon facing the ray.
+/// The curve is subdivided recursively in the ray-centric coordinate
+/// until each segment is nearly straight (Nakamaru and Ohno 2002).
+/// * `width` - width of the strand
+/// * `ray_dir` - direction of the ray (not necessarily a unit vector)
+/// # return
+/// `Some((t, u))` where `ray_org + t * ray_dir` is the hit point at the center line
+/// and `u` is the parameter of the curve
+pub fn intersection_against_ray<Real>(
+    cps: &ControlPoints<Real, 3>,
+    width: Real,
+    ray_org: &nalgebra::Vector3<Real>,
+    ray_dir: &nalgebra::Vector3<Real>,
+) -> Option<(Real, Real)>
+where
+    Real: nalgebra::RealField + Copy,
+    f64: AsPrimitive<Real>,
+{
+    let dir_len = ray_dir.norm();
+    let ez = ray_dir.scale(Real::one() / dir_len);
+    let (ex, ey) = crate::vec3::frame_from_z_vector(ez);
+    // control points in the ray-centric coordinate where the ray is the z-axis
+    let to_ray = |p: &nalgebra::Vector3<Real>| {
+        let d = p - ray_org;
+        nalgebra::Vector3::<Real>::new(ex.dot(&d), ey.dot(&d), ez.dot(&d))
+    };
+    let qs = [
+        to_ray(cps.p0),
+        to_ray(cps.p1),
+        to_ray(cps.p2),
+        to_ray(cps.p3),
+    ];
+    // number of subdivision from the flatness of the curve
+    let max_depth = {
+        let l0 = (0..2)
+            .map(|i| (qs[i] - qs[i + 1].scale(2f64.as_()) + qs[i + 2]).norm())
+            .fold(Real::zero(), |a, b| a.max(b));
+        let eps = width * 0.05f64.as_();
+        let r0 = l0 * (std::f64::consts::SQRT_2 * 6.0 / 8.0).as_() / eps;
+        let mut depth = 0;
+        let mut thres = Real::one();
+        while depth < 10 && r0 > thres {
+            thres *= 4f64.as_();
+            depth += 1;
+        }
+        depth
+    };
+    let half_width = width * 0.5f64.as_();
+    let mut res: Option<(Real, Real)> = None;
+    let mut stack = vec![(qs, Real::zero(), Real::one(), 0)];
+    while let Some((q, u0, u1, depth)) = stack.pop() {
+        let (qmin, qmax) = q
+            .iter()
+            .skip(1)
+            .fold((q[0], q[0]), |(a, b), p| (a.inf(p), b.sup(p)));
+        if qmin.x > half_width || qmax.x < -half_width {
+            continue;
+        }
+        if qmin.y > half_width || qmax.y < -half_width {
+            continue;
+        }
+        if qmax.z < Real::zero() {
+            continue;
+        }
+        if let Some((z, _)) = res {
+            if qmin.z > z {
+                continue;
+            }
+        }
+        if depth < max_depth {
+            let cps = ControlPoints {
+                p0: &q[0],
+                p1: &q[1],
+                p2: &q[2],
+                p3: &q[3],
+            };
+            let (qa, qb) = split(&cps, 0.5f64.as_());
+            let um = (u0 + u1) * 0.5f64.as_();
+            stack.push((qb, um, u1, depth + 1));
+            stack.push((qa, u0, um, depth + 1));
+            continue;
+        }
+        // the origin should be between the planes perpendicular to the tangents at both ends
+        let d0 = q[1].xy() - q[0].xy();
+        if d0.dot(&-q[0].xy()) < Real::zero() {
+            continue;
+        }
+        let d1 = q[3].xy() - q[2].xy();
+        if d1.dot(&q[3].xy()) < Real::zero() {
+            continue;
+        }
+        // nearest point on the segment approximating the curve
+        let seg = q[3].xy() - q[0].xy();
+        let seg_sq = seg.norm_squared();
+        let w = if seg_sq > Real::zero() {
+            (-q[0].xy().dot(&seg) / seg_sq).clamp(Real::zero(), Real::one())
+        } else {
+            Real::zero()
+        };
+        let u = u0 + (u1 - u0) * w;
+        let pc = eval(&qs[0], &qs[1], &qs[2], &qs[3], u);
+        if pc.xy().norm_squared() > half_width * half_width {
+            continue;
+        }
+        if pc.z < Real::zero() {
+            continue;
+        }
+        if res.is_none() || pc.z < res.unwrap().0 {
+            res = Some((pc.z, u));
+        }
+    }
+    res.map(|(z, u)| (z / dir_len, u))
+}
+
+#[test]
+fn test_intersection_against_ray() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    {
+        // straight curve along the x-axis
+        let ps = [0., 1. / 3., 2. / 3., 1.].map(|x| nalgebra::Vector3::<f64>::new(x, 0., 0.));
+        let cps = ControlPoints {
+            p0: &ps[0],
+            p1: &ps[1],
+            p2: &ps[2],
+            p3: &ps[3],
+        };
+        let dir = nalgebra::Vector3::<f64>::new(0., 0., -2.);
+        let org = nalgebra::Vector3::<f64>::new(0.3, 0.01, 1.);
+        let (t, u) = intersection_against_ray(&cps, 0.05, &org, &dir).unwrap();
+        assert!((t - 0.5).abs() < 1.0e-10);
+        assert!((u - 0.3).abs() < 1.0e-10);
+        let org = nalgebra::Vector3::<f64>::new(0.3, 0.1, 1.);
+        assert!(intersection_against_ray(&cps, 0.05, &org, &dir).is_none());
+        let org = nalgebra::Vector3::<f64>::new(1.1, 0.0, 1.);
+        assert!(intersection_against_ray(&cps, 0.05, &org, &dir).is_none());
+    }
+    for _ in 0..100 {
+        let ps: [nalgebra::Vector3<f64>; 4] =
+            std::array::from_fn(|_| crate::vec3::sample_unit_cube(&mut rng));
+        let cps = ControlPoints {
+            p0: &ps[0],
+            p1: &ps[1],
+            p2: &ps[2],
+            p3: &ps[3],
+        };
+        let width = 0.01;
+        let u0 = 0.1 + 0.8 * rng.gen::<f64>();
+        let dir = crate::vec3::sample_unit_cube::<_, f64>(&mut rng)
+            - nalgebra::Vector3::<f64>::new(0.5, 0.5, 0.5);
+        let org = eval(&ps[0], &ps[1], &ps[2], &ps[3], u0) - dir.scale(2.);
+        let Some((t, u)) = intersection_against_ray(&cps, width, &org, &dir) else {
+            panic!("ray should hit the curve");
+        };
+        let p = eval(&ps[0], &ps[1], &ps[2], &ps[3], u);
+        let q = org + dir.scale(t);
+        assert!((p - q).norm() < width * 0.5 + 1.0e-5);
+    }
+}
+
+/// coefficients of the curve in the power basis `a0 + a1*t + a2*t^2 + a3*t^3`
+pub fn coefficients_power_basis<Real, const N: usize>(
+    cps: &ControlPoints<Real, N>,
+) -> [nalgebra::SVector<Real, N>; 4]
+where
+    Real: nalgebra::RealField + Copy,
+{
+    let three = Real::one() + Real::one() + Real::one();
+    [
+        *cps.p0,
+        (cps.p1 - cps.p0).scale(three),
+        (cps.p0 - cps.p1.scale(Real::one() + Real::one()) + cps.p2).scale(three),
+        cps.p3 - cps.p0 + (cps.p1 - cps.p2).scale(three),
+    ]
+}
+
+/// parameter of the nearest point on the curve from the point `q`.
+/// the candidates are the roots of the quintic `(B(t)-q).B'(t) = 0` in `[0,1]` and the end points.
+/// # return
+/// `(t, distance)`
+pub fn nearest_to_point<Real, const N: usize>(
+    cps: &ControlPoints<Real, N>,
+    q: &nalgebra::SVector<Real, N>,
+) -> (Real, Real)
+where
+    Real: nalgebra::RealField + Copy + num_traits::Float,
+{
+    use del_geo_core::polynomial::{add, derivative, mult};
+    let a = coefficients_power_basis(cps);
+    let mut f: Vec<Real> = vec![];
+    for i_dim in 0..N {
+        let b = [
+            a[0][i_dim] - q[i_dim],
+            a[1][i_dim],
+            a[2][i_dim],
+            a[3][i_dim],
+        ];
+        f = add(&f, &mult(&b, &derivative(&b)));
+    }
+    let eps = Real::epsilon() * num_traits::Float::powi(Real::one() + Real::one(), 10);
+    let mut ts =
+        del_geo_core::polynomial_root::roots_in_range_sturm(&f, Real::zero(), Real::one(), eps);
+    ts.push(Real::zero());
+    ts.push(Real::one());
+    ts.iter()
+        .map(|&t| (t, (eval(cps.p0, cps.p1, cps.p2, cps.p3, t) - q).norm()))
+        .fold((Real::zero(), Real::infinity()), |a, b| {
+            if b.1 < a.1 {
+                b
+            } else {
+                a
+            }
+        })
+}
+
+/// parameter of the nearest point on the curve from the point `q` and its derivatives
+/// # return
+/// `(t, dt/dq, [dt/dp0, dt/dp1, dt/dp2, dt/dp3])`.
+/// the derivatives are zero if the nearest point is at the end of the curve.
+#[allow(clippy::type_complexity)]
+pub fn wdw_nearest_to_point<Real, const N: usize>(
+    cps: &ControlPoints<Real, N>,
+    q: &nalgebra::SVector<Real, N>,
+) -> (
+    Real,
+    nalgebra::SVector<Real, N>,
+    [nalgebra::SVector<Real, N>; 4],
+)
+where
+    Real: nalgebra::RealField + Copy + num_traits::Float,
+{
+    let zero = nalgebra::SVector::<Real, N>::zeros();
+    let (t, _dist) = nearest_to_point(cps, q);
+    if t <= Real::zero() || t >= Real::one() {
+        return (t, zero, [zero; 4]);
+    }
+    let one = Real::one();
+    let two = one + one;
+    let three = two + one;
+    let six = three + three;
+    let s = one - t;
+    let b = [s * s * s, three * t * s * s, three * t * t * s, t * t * t];
+    let db = [
+        -three * s * s,
+        three * s * s - six * t * s,
+        six * t * s - three * t * t,
+        three * t * t,
+    ];
+    let ddb = [
+        six * s,
+        six * t - two * six * s,
+        six * s - two * six * t,
+        six * t,
+    ];
+    let ps = [cps.p0, cps.p1, cps.p2, cps.p3];
+    let (mut p, mut dp, mut ddp) = (zero, zero, zero);
+    for i in 0..4 {
+        p += ps[i].scale(b[i]);
+        dp += ps[i].scale(db[i]);
+        ddp += ps[i].scale(ddb[i]);
+    }
+    // g(t, q, p_i) = (B(t) - q).B'(t) = 0
+    let r = p - q;
+    let dgdt = dp.norm_squared() + r.dot(&ddp);
+    if dgdt == Real::zero() {
+        return (t, zero, [zero; 4]);
+    }
+    let dtdq = dp.scale(one / dgdt);
+    let dtdp = std::array::from_fn(|i| (dp.scale(b[i]) + r.scale(db[i])).scale(-one / dgdt));
+    (t, dtdq, dtdp)
+}
+
+#[test]
+fn test_nearest_to_point() {
+    use rand::Rng;
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let ps: [nalgebra::Vector2<f64>; 4] =
+            std::array::from_fn(|_| nalgebra::Vector2::new(rng.gen(), rng.gen()));
+        let q = nalgebra::Vector2::<f64>::new(rng.gen(), rng.gen());
+        let cps = ControlPoints {
+            p0: &ps[0],
+            p1: &ps[1],
+            p2: &ps[2],
+            p3: &ps[3],
+        };
+        let (t, dist) = nearest_to_point(&cps, &q);
+        let ndiv = 1000;
+        for i in 0..ndiv + 1 {
+            let ti = i as f64 / ndiv as f64;
+            let di = (eval(&ps[0], &ps[1], &ps[2], &ps[3], ti) - q).norm();
+            assert!(dist <= di + 1.0e-10);
+        }
+        let (t0, dtdq, dtdp) = wdw_nearest_to_point(&cps, &q);
+        assert_eq!(t, t0);
+        if !(0.01..=0.99).contains(&t) {
+            continue;
+        }
+        let eps = 1.0e-6;
+        for i_dim in 0..2 {
+            let mut q1 = q;
+            q1[i_dim] += eps;
+            let (t1, _) = nearest_to_point(&cps, &q1);
+            if (t1 - t).abs() > 1.0e-2 {
+                continue; // jumped to another branch
+            }
+            assert!(((t1 - t) / eps - dtdq[i_dim]).abs() < 1.0e-3 * (1. + dtdq[i_dim].abs()));
+        }
+        for i_vtx_dim in 0..8 {
+            let (i_vtx, i_dim) = (i_vtx_dim / 2, i_vtx_dim % 2);
+            let mut ps1 = ps;
+            ps1[i_vtx][i_dim] += eps;
+            let cps1 = ControlPoints {
+                p0: &ps1[0],
+                p1: &ps1[1],
+                p2: &ps1[2],
+                p3: &ps1[3],
+            };
+            let (t1, _) = nearest_to_point(&cps1, &q);
+            if (t1 - t).abs() > 1.0e-2 {
+                continue;
+            }
+            let diff = (t1 - t) / eps;
+            let ana = dtdp[i_vtx][i_dim];
+            assert!(
+                (diff - ana).abs() < 1.0e-3 * (1. + ana.abs()),
+                "{} {}",
+                diff,
+                ana
+            );
+        }
+    }
+}
+
+/// nearest points between two cubic Bezier curves.
+/// the pair of sub-curves is pruned if the distance between the bounding boxes of their
+/// control points cannot improve the current best distance by more than `epsilon`
+/// (branch and bound). The remaining pairs after a few subdivisions are refined
+/// by alternating projections.
+/// # return
+/// `(s, t, distance)` where `s` and `t` are the parameters on the curve `a` and `b`
+pub fn nearest_to_bezier_cubic<Real, const N: usize>(
+    cps_a: &ControlPoints<Real, N>,
+    cps_b: &ControlPoints<Real, N>,
+    epsilon: Real,
+) -> (Real, Real, Real)
+where
+    Real: nalgebra::RealField + Copy + num_traits::Float,
+{
+    const MAX_DEPTH: usize = 6;
+    let half = Real::one() / (Real::one() + Real::one());
+    let to_array = |c: &ControlPoints<Real, N>| [*c.p0, *c.p1, *c.p2, *c.p3];
+    let bound = |c: &[nalgebra::SVector<Real, N>; 4]| {
+        c.iter()
+            .skip(1)
+            .fold((c[0], c[0]), |(a, b), p| (a.inf(p), b.sup(p)))
+    };
+    let dist_boxes = |a: &[nalgebra::SVector<Real, N>; 4], b: &[nalgebra::SVector<Real, N>; 4]| {
+        let (amin, amax) = bound(a);
+        let (bmin, bmax) = bound(b);
+        let gap = (amin - bmax)
+            .sup(&(bmin - amax))
+            .sup(&nalgebra::SVector::<Real, N>::zeros());
+        gap.norm()
+    };
+    let split_half = |c: &[nalgebra::SVector<Real, N>; 4]| {
+        split(
+            &ControlPoints {
+                p0: &c[0],
+                p1: &c[1],
+                p2: &c[2],
+                p3: &c[3],
+            },
+            half,
+        )
+    };
+    let alternating_projection = |s: Real, t: Real| {
+        let pa = eval(cps_a.p0, cps_a.p1, cps_a.p2, cps_a.p3, s);
+        let pb = eval(cps_b.p0, cps_b.p1, cps_b.p2, cps_b.p3, t);
+        let (mut s, mut t, mut dist) = (s, t, (pa - pb).norm());
+        for _itr in 0..32 {
+            let pa = eval(cps_a.p0, cps_a.p1, cps_a.p2, cps_a.p3, s);
+            let (t1, _) = nearest_to_point(cps_b, &pa);
+            let pb = eval(cps_b.p0, cps_b.p1, cps_b.p2, cps_b.p3, t1);
+            let (s1, d1) = nearest_to_point(cps_a, &pb);
+            if d1 >= dist {
+                break;
+            }
+            let is_converged = dist - d1 < epsilon * epsilon;
+            (s, t, dist) = (s1, t1, d1);
+            if is_converged {
+                break;
+            }
+        }
+        (s, t, dist)
+    };
+    // upper bound from the end points
+    let mut best = (Real::zero(), Real::zero(), Real::infinity());
+    for (s, pa) in [(Real::zero(), cps_a.p0), (Real::one(), cps_a.p3)] {
+        for (t, pb) in [(Real::zero(), cps_b.p0), (Real::one(), cps_b.p3)] {
+            let d = (pa - pb).norm();
+            if d < best.2 {
+                best = (s, t, d);
+            }
+        }
+    }
+    let mut stack = vec![(
+        to_array(cps_a),
+        Real::zero(),
+        Real::one(),
+        to_array(cps_b),
+        Real::zero(),
+        Real::one(),
+        0,
+    )];
+    while let Some((a, s0, s1, b, t0, t1, depth)) = stack.pop() {
+        if dist_boxes(&a, &b) >= best.2 - epsilon {
+            continue;
+        }
+        let (sm, tm) = ((s0 + s1) * half, (t0 + t1) * half);
+        if depth == MAX_DEPTH {
+            let res = alternating_projection(sm, tm);
+            if res.2 < best.2 {
+                best = res;
+            }
+            continue;
+        }
+        // upper bound from the mid points of sub-curves
+        let pa = eval(cps_a.p0, cps_a.p1, cps_a.p2, cps_a.p3, sm);
+        let pb = eval(cps_b.p0, cps_b.p1, cps_b.p2, cps_b.p3, tm);
+        let d = (pa - pb).norm();
+        if d < best.2 {
+            best = (sm, tm, d);
+        }
+        let (a0, a1) = split_half(&a);
+        let (b0, b1) = split_half(&b);
+        stack.push((a0, s0, sm, b0, t0, tm, depth + 1));
+        stack.push((a0, s0, sm, b1, tm, t1, depth + 1));
+        stack.push((a1, sm, s1, b0, t0, tm, depth + 1));
+        stack.push((a1, sm, s1, b1, tm, t1, depth + 1));
+    }
+    best
+}
+
+#[test]
+fn test_nearest_to_bezier_cubic() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..30 {
+        let pa: [nalgebra::Vector3<f64>; 4] =
+            std::array::from_fn(|_| crate::vec3::sample_unit_cube(&mut rng));
+        let pb: [nalgebra::Vector3<f64>; 4] =
+            std::array::from_fn(|_| crate::vec3::sample_unit_cube(&mut rng));
+        let cps_a = ControlPoints {
+            p0: &pa[0],
+            p1: &pa[1],
+            p2: &pa[2],
+            p3: &pa[3],
+        };
+        let cps_b = ControlPoints {
+            p0: &pb[0],
+            p1: &pb[1],
+            p2: &pb[2],
+            p3: &pb[3],
+        };
+        let (s, t, dist) = nearest_to_bezier_cubic(&cps_a, &cps_b, 1.0e-4);
+        let qa = eval(&pa[0], &pa[1], &pa[2], &pa[3], s);
+        let qb = eval(&pb[0], &pb[1], &pb[2], &pb[3], t);
+        assert!(((qa - qb).norm() - dist).abs() < 1.0e-10);
+        let qs_a = sample_uniform_param(200, &pa[0], &pa[1], &pa[2], &pa[3], true, true);
+        let qs_b = sample_uniform_param(200, &pb[0], &pb[1], &pb[2], &pb[3], true, true);
+        for qa in qs_a.iter() {
+            for qb in qs_b.iter() {
+                assert!(dist <= (qa - qb).norm() + 1.0e-6);
+            }
+        }
+    }
+}
@@ -14,3 +14,144 @@ where
     let t1 = one - t0;
     p0.scale(t1 * t1) + p1.scale(two * t0 * t1) + p2.scale(t0 * t0)
 }
+
+/// parameter of the nearest point on the curve from the point `q`.
+/// the candidates are the roots of the cubic `(B(t)-q).B'(t) = 0` in `[0,1]` and the end points.
+/// # return
+/// `(t, distance)`
+pub fn nearest_to_point<Real, const N: usize>(
+    p0: &nalgebra::SVector<Real, N>,
+    p1: &nalgebra::SVector<Real, N>,
+    p2: &nalgebra::SVector<Real, N>,
+    q: &nalgebra::SVector<Real, N>,
+) -> (Real, Real)
+where
+    Real: nalgebra::RealField + Copy + num_traits::Float + std::fmt::Debug,
+    i64: num_traits::AsPrimitive<Real>,
+    f64: num_traits::AsPrimitive<Real>,
+{
+    let two = Real::one() + Real::one();
+    // B(t) - q = a0 + a1*t + a2*t^2
+    let a0 = p0 - q;
+    let a1 = (p1 - p0).scale(two);
+    let a2 = p0 - p1.scale(two) + p2;
+    // (a0 + a1*t + a2*t^2).(a1 + 2*a2*t)
+    let c0 = a0.dot(&a1);
+    let c1 = a1.dot(&a1) + two * a0.dot(&a2);
+    let c2 = (two + Real::one()) * a1.dot(&a2);
+    let c3 = two * a2.dot(&a2);
+    let mut ts = del_geo_core::polynomial_root::cubic_roots(c0, c1, c2, c3);
+    ts.push(Real::zero());
+    ts.push(Real::one());
+    ts.iter()
+        .filter(|&&t| t >= Real::zero() && t <= Real::one())
+        .map(|&t| (t, (eval(p0, p1, p2, t) - q).norm()))
+        .fold((Real::zero(), Real::infinity()), |a, b| {
+            if b.1 < a.1 {
+                b
+            } else {
+                a
+            }
+        })
+}
+
+/// parameter of the nearest point on the curve from the point `q` and its derivatives
+/// # return
+/// `(t, dt/dq, [dt/dp0, dt/dp1, dt/dp2])`.
+/// the derivatives are zero if the nearest point is at the end of the curve.
+#[allow(clippy::type_complexity)]
+pub fn wdw_nearest_to_point<Real, const N: usize>(
+    p0: &nalgebra::SVector<Real, N>,
+    p1: &nalgebra::SVector<Real, N>,
+    p2: &nalgebra::SVector<Real, N>,
+    q: &nalgebra::SVector<Real, N>,
+) -> (
+    Real,
+    nalgebra::SVector<Real, N>,
+    [nalgebra::SVector<Real, N>; 3],
+)
+where
+    Real: nalgebra::RealField + Copy + num_traits::Float + std::fmt::Debug,
+    i64: num_traits::AsPrimitive<Real>,
+    f64: num_traits::AsPrimitive<Real>,
+{
+    let zero = nalgebra::SVector::<Real, N>::zeros();
+    let (t, _dist) = nearest_to_point(p0, p1, p2, q);
+    if t <= Real::zero() || t >= Real::one() {
+        return (t, zero, [zero; 3]);
+    }
+    let one = Real::one();
+    let two = one + one;
+    let s = one - t;
+    let b = [s * s, two * t * s, t * t];
+    let db = [-two * s, two * (s - t), two * t];
+    let ddb = [two, -two - two, two];
+    let ps = [p0, p1, p2];
+    let (mut p, mut dp, mut ddp) = (zero, zero, zero);
+    for i in 0..3 {
+        p += ps[i].scale(b[i]);
+        dp += ps[i].scale(db[i]);
+        ddp += ps[i].scale(ddb[i]);
+    }
+    // g(t, q, p_i) = (B(t) - q).B'(t) = 0
+    let r = p - q;
+    let dgdt = dp.norm_squared() + r.dot(&ddp);
+    if dgdt == Real::zero() {
+        return (t, zero, [zero; 3]);
+    }
+    let dtdq = dp.scale(one / dgdt);
+    let dtdp = std::array::from_fn(|i| (dp.scale(b[i]) + r.scale(db[i])).scale(-one / dgdt));
+    (t, dtdq, dtdp)
+}
+
+#[test]
+fn test_nearest_to_point() {
+    use rand::SeedableRng;
+    let mut rng = rand_chacha::ChaChaRng::seed_from_u64(0);
+    for _ in 0..100 {
+        let ps: [nalgebra::Vector3<f64>; 3] =
+            std::array::from_fn(|_| crate::vec3::sample_unit_cube(&mut rng));
+        let q = crate::vec3::sample_unit_cube(&mut rng);
+        let (t, dist) = nearest_to_point(&ps[0], &ps[1], &ps[2], &q);
+        let ndiv = 1000;
+        for i in 0..ndiv + 1 {
+            let ti = i as f64 / ndiv as f64;
+            let di = (eval(&ps[0], &ps[1], &ps[2], ti) - q).norm();
+            assert!(dist <= di + 1.0e-10);
+        }
+        let (t0, dtdq, dtdp) = wdw_nearest_to_point(&ps[0], &ps[1], &ps[2], &q);
+        assert_eq!(t, t0);
+        if !(0.01..=0.99).contains(&t) {
+            continue;
+        }
+        let eps = 1.0e-6;
+        for i_dim in 0..3 {
+            let mut q1 = q;
+            q1[i_dim] += eps;
+            let (t1, _) = nearest_to_point(&ps[0], &ps[1], &ps[2], &q1);
+            if (t1 - t).abs() > 1.0e-2 {
+                continue; // jumped to another branch
+            }
+            let diff = (t1 - t) / eps;
+            assert!((diff - dtdq[i_dim]).abs() < 1.0e-3 * (1. + diff.abs()));
+        }
+        for i_vtx in 0..3 {
+            for i_dim in 0..3 {
+                let mut ps1 = ps;
+                ps1[i_vtx][i_dim] += eps;
+                let (t1, _) = nearest_to_point(&ps1[0], &ps1[1], &ps1[2], &q);
+                if (t1 - t).abs() > 1.0e-2 {
+                    continue;
+                }
+                let diff = (t1 - t) / eps;
+                let ana = dtdp[i_vtx][i_dim];
+                assert!(
+                    (diff - ana).abs() < 1.0e-3 * (1. + ana.abs()),
+                    "{} {}",
+                    diff,
+                    ana
+                );
+            }
+        }
+    }
+}
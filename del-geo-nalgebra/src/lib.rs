@@ -6,6 +6,7 @@ tet
 */
 
 pub mod aabb2;
+pub mod bezier_bicubic;
 pub mod bezier_cubic;
 pub mod bezier_quadratic;
 pub mod ccd2;
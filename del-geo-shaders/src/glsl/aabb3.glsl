@@ -0,0 +1,42 @@
+// 3D axis-aligned bounding box `[min_x, min_y, min_z, max_x, max_y, max_z]`. see `del_geo_core::aabb3`
+
+float[6] aabb3_from_point(vec3 xyz, float eps) {
+    return float[6](
+        xyz.x - eps, xyz.y - eps, xyz.z - eps,
+        xyz.x + eps, xyz.y + eps, xyz.z + eps);
+}
+
+void aabb3_add_point(inout float aabb[6], vec3 xyz, float eps) {
+    aabb[0] = min(aabb[0], xyz.x - eps);
+    aabb[3] = max(aabb[3], xyz.x + eps);
+    aabb[1] = min(aabb[1], xyz.y - eps);
+    aabb[4] = max(aabb[4], xyz.y + eps);
+    aabb[2] = min(aabb[2], xyz.z - eps);
+    aabb[5] = max(aabb[5], xyz.z + eps);
+}
+
+float[6] aabb3_from_two_aabbs(float i0[6], float i1[6]) {
+    return float[6](
+        min(i0[0], i1[0]), min(i0[1], i1[1]), min(i0[2], i1[2]),
+        max(i0[3], i1[3]), max(i0[4], i1[4]), max(i0[5], i1[5]));
+}
+
+vec3 aabb3_center(float aabb[6]) {
+    return vec3(aabb[0] + aabb[3], aabb[1] + aabb[4], aabb[2] + aabb[5]) * 0.5;
+}
+
+vec3 aabb3_size(float aabb[6]) {
+    return vec3(aabb[3] - aabb[0], aabb[4] - aabb[1], aabb[5] - aabb[2]);
+}
+
+bool aabb3_is_active(float i0[6]) {
+    return i0[0] <= i0[3];
+}
+
+bool aabb3_is_intersect(float i0[6], float i1[6]) {
+    if( !aabb3_is_active(i0) || !aabb3_is_active(i1) ){
+        return false;
+    }
+    return i0[0] <= i1[3] && i0[1] <= i1[4] && i0[2] <= i1[5]
+        && i0[3] >= i1[0] && i0[4] >= i1[1] && i0[5] >= i1[2];
+}
@@ -0,0 +1,32 @@
+// 4x4 matrix. `mat4` is column major as `del_geo_core::mat4_col_major`
+
+/// the point `x` transformed by the homogeneous transformation `transform`.
+/// the division by zero is not checked
+vec3 mat4_col_major_transform_homogeneous(mat4 transform, vec3 x) {
+    vec4 y = transform * vec4(x, 1.0);
+    return vec3(y.x / y.w, y.y / y.w, y.z / y.w);
+}
+
+vec3 mat4_col_major_transform_direction(mat4 transform, vec3 x) {
+    vec4 y = transform * vec4(x, 0.0);
+    return y.xyz;
+}
+
+mat4 mat4_col_major_mult_mat(mat4 a, mat4 b) {
+    return a * b;
+}
+
+/// derivative of `mat4_col_major_transform_homogeneous` w.r.t. the point `p`
+mat3 mat4_col_major_jacobian_transform(mat4 t, vec3 p) {
+    mat3 a = mat3(t[0].xyz, t[1].xyz, t[2].xyz);
+    vec3 b = t[3].xyz;
+    float d = t[3].w;
+    vec3 c = vec3(t[0].w, t[1].w, t[2].w);
+    float e = 1.0 / (dot(c, p) + d);
+    float ee = e * e;
+    vec3 f = a * p + b;
+    return mat3(
+        a[0] * e - f * (c.x * ee),
+        a[1] * e - f * (c.y * ee),
+        a[2] * e - f * (c.z * ee));
+}
@@ -0,0 +1,46 @@
+// quaternion `(i, j, k, w)` as `vec4`. see `del_geo_core::quaternion`
+
+mat3 quaternion_to_mat3_col_major(vec4 q) {
+    float x2 = q.x * q.x * 2.0;
+    float y2 = q.y * q.y * 2.0;
+    float z2 = q.z * q.z * 2.0;
+    float xy = q.x * q.y * 2.0;
+    float yz = q.y * q.z * 2.0;
+    float zx = q.z * q.x * 2.0;
+    float xw = q.x * q.w * 2.0;
+    float yw = q.y * q.w * 2.0;
+    float zw = q.z * q.w * 2.0;
+    return mat3(
+        1.0 - y2 - z2, xy + zw, zx - yw,
+        xy - zw, 1.0 - z2 - x2, yz + xw,
+        zx + yw, yz - xw, 1.0 - x2 - y2);
+}
+
+vec4 quaternion_normalized(vec4 q) {
+    float invlen = 1.0 / sqrt(q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w);
+    return q * invlen;
+}
+
+vec4 quaternion_inverse(vec4 q) {
+    float sqleninv = 1.0 / (q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w);
+    return vec4(-q.x * sqleninv, -q.y * sqleninv, -q.z * sqleninv, q.w * sqleninv);
+}
+
+vec4 quaternion_mult_quaternion(vec4 p, vec4 q) {
+    return vec4(
+        p.w * q.x + p.x * q.w + p.y * q.z - p.z * q.y,
+        p.w * q.y - p.x * q.z + p.y * q.w + p.z * q.x,
+        p.w * q.z + p.x * q.y - p.y * q.x + p.z * q.w,
+        p.w * q.w - p.x * q.x - p.y * q.y - p.z * q.z);
+}
+
+/// rotation of the axis-angle vector `a`
+vec4 quaternion_from_axisangle(vec3 a) {
+    float sqlen = a.x * a.x + a.y * a.y + a.z * a.z;
+    if( sqlen < 1.1920929e-7 ){
+        return vec4(0.5 * a.x, 0.5 * a.y, 0.5 * a.z, -sqlen * 0.125 + 1.0);
+    }
+    float lena = sqrt(sqlen);
+    float s = sin(lena * 0.5) / lena;
+    return vec4(s * a.x, s * a.y, s * a.z, cos(lena * 0.5));
+}
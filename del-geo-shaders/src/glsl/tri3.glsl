@@ -0,0 +1,34 @@
+// 3D triangle. see `del_geo_core::tri3`
+
+/// Moller-Trumbore intersection of the line `ray_org + t * ray_dir` against the triangle.
+/// returns `false` if there is no intersection, otherwise writes the parameter to `t`
+bool tri3_intersection_against_ray(
+    vec3 p0,
+    vec3 p1,
+    vec3 p2,
+    vec3 ray_org,
+    vec3 ray_dir,
+    out float t)
+{
+    float eps = 1.1920929e-7;
+    vec3 edge1 = p1 - p0;
+    vec3 edge2 = p2 - p0;
+    vec3 pvec = cross(ray_dir, edge2);
+    float det = dot(edge1, pvec);
+    if( det > -eps && det < eps ){
+        return false;
+    }
+    float invdet = 1.0 / det;
+    vec3 tvec = ray_org - p0;
+    float u = invdet * dot(tvec, pvec);
+    if( u < 0.0 || u > 1.0 ){
+        return false;
+    }
+    vec3 qvec = cross(tvec, edge1);
+    float v = invdet * dot(ray_dir, qvec);
+    if( v < 0.0 || u + v > 1.0 ){
+        return false;
+    }
+    t = invdet * dot(edge2, qvec);
+    return true;
+}
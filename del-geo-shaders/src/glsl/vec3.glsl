@@ -0,0 +1,42 @@
+// 3D vector. see `del_geo_core::vec3`
+
+float vec3_norm(vec3 a) {
+    return sqrt(a.x * a.x + a.y * a.y + a.z * a.z);
+}
+
+float vec3_squared_norm(vec3 a) {
+    return a.x * a.x + a.y * a.y + a.z * a.z;
+}
+
+vec3 vec3_add(vec3 a, vec3 b) {
+    return vec3(a.x + b.x, a.y + b.y, a.z + b.z);
+}
+
+vec3 vec3_sub(vec3 a, vec3 b) {
+    return vec3(a.x - b.x, a.y - b.y, a.z - b.z);
+}
+
+vec3 vec3_scale(vec3 a, float s) {
+    return vec3(a.x * s, a.y * s, a.z * s);
+}
+
+float vec3_dot(vec3 a, vec3 b) {
+    return a.x * b.x + a.y * b.y + a.z * b.z;
+}
+
+vec3 vec3_cross(vec3 v1, vec3 v2) {
+    return vec3(
+        v1.y * v2.z - v2.y * v1.z,
+        v1.z * v2.x - v2.z * v1.x,
+        v1.x * v2.y - v2.x * v1.y);
+}
+
+/// `a * x + y`
+vec3 vec3_axpy(float a, vec3 x, vec3 y) {
+    return vec3(a * x.x + y.x, a * x.y + y.y, a * x.z + y.z);
+}
+
+vec3 vec3_normalize(vec3 a) {
+    float invl = 1.0 / vec3_norm(a);
+    return vec3(a.x * invl, a.y * invl, a.z * invl);
+}
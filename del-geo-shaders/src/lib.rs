@@ -0,0 +1,72 @@
+//! shader sources of the functions in `del-geo-core` as the strings to embed.
+//! The functions are prefixed with the module name (e.g., `vec3_cross`) and
+//! each source is self-contained so that they can be concatenated in any order.
+
+pub struct Wgsl {}
+
+pub const WGSL: Wgsl = Wgsl {};
+
+impl Wgsl {
+    pub fn get(&self, idx: u32) -> Option<(&str, &str)> {
+        match idx {
+            0 => Some(("aabb3.wgsl", include_str!("wgsl/aabb3.wgsl"))),
+            1 => Some((
+                "mat4_col_major.wgsl",
+                include_str!("wgsl/mat4_col_major.wgsl"),
+            )),
+            2 => Some(("quaternion.wgsl", include_str!("wgsl/quaternion.wgsl"))),
+            3 => Some((
+                "spherical_harmonics.wgsl",
+                include_str!("wgsl/spherical_harmonics.wgsl"),
+            )),
+            4 => Some(("tri3.wgsl", include_str!("wgsl/tri3.wgsl"))),
+            5 => Some(("vec3.wgsl", include_str!("wgsl/vec3.wgsl"))),
+            _ => None,
+        }
+    }
+
+    pub fn write_files(&self, path_out_dir: &std::path::Path) -> std::io::Result<()> {
+        write_files(|idx| self.get(idx), path_out_dir)
+    }
+}
+
+pub struct Glsl {}
+
+pub const GLSL: Glsl = Glsl {};
+
+impl Glsl {
+    pub fn get(&self, idx: u32) -> Option<(&str, &str)> {
+        match idx {
+            0 => Some(("aabb3.glsl", include_str!("glsl/aabb3.glsl"))),
+            1 => Some((
+                "mat4_col_major.glsl",
+                include_str!("glsl/mat4_col_major.glsl"),
+            )),
+            2 => Some(("quaternion.glsl", include_str!("glsl/quaternion.glsl"))),
+            3 => Some((
+                "spherical_harmonics.glsl",
+                include_str!("glsl/spherical_harmonics.glsl"),
+            )),
+            4 => Some(("tri3.glsl", include_str!("glsl/tri3.glsl"))),
+            5 => Some(("vec3.glsl", include_str!("glsl/vec3.glsl"))),
+            _ => None,
+        }
+    }
+
+    pub fn write_files(&self, path_out_dir: &std::path::Path) -> std::io::Result<()> {
+        write_files(|idx| self.get(idx), path_out_dir)
+    }
+}
+
+fn write_files<'a>(
+    get: impl Fn(u32) -> Option<(&'a str, &'a str)>,
+    path_out_dir: &std::path::Path,
+) -> std::io::Result<()> {
+    for idx in 0.. {
+        let Some((name, content)) = get(idx) else {
+            break;
+        };
+        std::fs::write(path_out_dir.join(name), content)?;
+    }
+    Ok(())
+}
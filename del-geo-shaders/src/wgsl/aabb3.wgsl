@@ -0,0 +1,44 @@
+// 3D axis-aligned bounding box `[min_x, min_y, min_z, max_x, max_y, max_z]`. see `del_geo_core::aabb3`
+
+fn aabb3_from_point(xyz: vec3<f32>, eps: f32) -> array<f32, 6> {
+    return array<f32, 6>(
+        xyz.x - eps, xyz.y - eps, xyz.z - eps,
+        xyz.x + eps, xyz.y + eps, xyz.z + eps,
+    );
+}
+
+fn aabb3_add_point(aabb: ptr<function, array<f32, 6>>, xyz: vec3<f32>, eps: f32) {
+    (*aabb)[0] = min((*aabb)[0], xyz.x - eps);
+    (*aabb)[3] = max((*aabb)[3], xyz.x + eps);
+    (*aabb)[1] = min((*aabb)[1], xyz.y - eps);
+    (*aabb)[4] = max((*aabb)[4], xyz.y + eps);
+    (*aabb)[2] = min((*aabb)[2], xyz.z - eps);
+    (*aabb)[5] = max((*aabb)[5], xyz.z + eps);
+}
+
+fn aabb3_from_two_aabbs(i0: array<f32, 6>, i1: array<f32, 6>) -> array<f32, 6> {
+    return array<f32, 6>(
+        min(i0[0], i1[0]), min(i0[1], i1[1]), min(i0[2], i1[2]),
+        max(i0[3], i1[3]), max(i0[4], i1[4]), max(i0[5], i1[5]),
+    );
+}
+
+fn aabb3_center(aabb: array<f32, 6>) -> vec3<f32> {
+    return vec3<f32>(aabb[0] + aabb[3], aabb[1] + aabb[4], aabb[2] + aabb[5]) * 0.5;
+}
+
+fn aabb3_size(aabb: array<f32, 6>) -> vec3<f32> {
+    return vec3<f32>(aabb[3] - aabb[0], aabb[4] - aabb[1], aabb[5] - aabb[2]);
+}
+
+fn aabb3_is_active(i0: array<f32, 6>) -> bool {
+    return i0[0] <= i0[3];
+}
+
+fn aabb3_is_intersect(i0: array<f32, 6>, i1: array<f32, 6>) -> bool {
+    if !aabb3_is_active(i0) || !aabb3_is_active(i1) {
+        return false;
+    }
+    return i0[0] <= i1[3] && i0[1] <= i1[4] && i0[2] <= i1[5]
+        && i0[3] >= i1[0] && i0[4] >= i1[1] && i0[5] >= i1[2];
+}
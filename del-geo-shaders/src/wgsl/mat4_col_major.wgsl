@@ -0,0 +1,33 @@
+// 4x4 matrix. `mat4x4<f32>` is column major as `del_geo_core::mat4_col_major`
+
+/// the point `x` transformed by the homogeneous transformation `transform`.
+/// the division by zero is not checked
+fn mat4_col_major_transform_homogeneous(transform: mat4x4<f32>, x: vec3<f32>) -> vec3<f32> {
+    let y = transform * vec4<f32>(x, 1.0);
+    return vec3<f32>(y.x / y.w, y.y / y.w, y.z / y.w);
+}
+
+fn mat4_col_major_transform_direction(transform: mat4x4<f32>, x: vec3<f32>) -> vec3<f32> {
+    let y = transform * vec4<f32>(x, 0.0);
+    return y.xyz;
+}
+
+fn mat4_col_major_mult_mat(a: mat4x4<f32>, b: mat4x4<f32>) -> mat4x4<f32> {
+    return a * b;
+}
+
+/// derivative of `mat4_col_major_transform_homogeneous` w.r.t. the point `p`
+fn mat4_col_major_jacobian_transform(t: mat4x4<f32>, p: vec3<f32>) -> mat3x3<f32> {
+    let a = mat3x3<f32>(t[0].xyz, t[1].xyz, t[2].xyz);
+    let b = t[3].xyz;
+    let d = t[3].w;
+    let c = vec3<f32>(t[0].w, t[1].w, t[2].w);
+    let e = 1.0 / (dot(c, p) + d);
+    let ee = e * e;
+    let f = a * p + b;
+    return mat3x3<f32>(
+        a[0] * e - f * (c.x * ee),
+        a[1] * e - f * (c.y * ee),
+        a[2] * e - f * (c.z * ee),
+    );
+}
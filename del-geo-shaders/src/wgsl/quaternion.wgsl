@@ -0,0 +1,48 @@
+// quaternion `(i, j, k, w)` as `vec4<f32>`. see `del_geo_core::quaternion`
+
+fn quaternion_to_mat3_col_major(q: vec4<f32>) -> mat3x3<f32> {
+    let x2 = q.x * q.x * 2.0;
+    let y2 = q.y * q.y * 2.0;
+    let z2 = q.z * q.z * 2.0;
+    let xy = q.x * q.y * 2.0;
+    let yz = q.y * q.z * 2.0;
+    let zx = q.z * q.x * 2.0;
+    let xw = q.x * q.w * 2.0;
+    let yw = q.y * q.w * 2.0;
+    let zw = q.z * q.w * 2.0;
+    return mat3x3<f32>(
+        1.0 - y2 - z2, xy + zw, zx - yw,
+        xy - zw, 1.0 - z2 - x2, yz + xw,
+        zx + yw, yz - xw, 1.0 - x2 - y2,
+    );
+}
+
+fn quaternion_normalized(q: vec4<f32>) -> vec4<f32> {
+    let invlen = 1.0 / sqrt(q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w);
+    return q * invlen;
+}
+
+fn quaternion_inverse(q: vec4<f32>) -> vec4<f32> {
+    let sqleninv = 1.0 / (q.x * q.x + q.y * q.y + q.z * q.z + q.w * q.w);
+    return vec4<f32>(-q.x * sqleninv, -q.y * sqleninv, -q.z * sqleninv, q.w * sqleninv);
+}
+
+fn quaternion_mult_quaternion(p: vec4<f32>, q: vec4<f32>) -> vec4<f32> {
+    return vec4<f32>(
+        p.w * q.x + p.x * q.w + p.y * q.z - p.z * q.y,
+        p.w * q.y - p.x * q.z + p.y * q.w + p.z * q.x,
+        p.w * q.z + p.x * q.y - p.y * q.x + p.z * q.w,
+        p.w * q.w - p.x * q.x - p.y * q.y - p.z * q.z,
+    );
+}
+
+/// rotation of the axis-angle vector `a`
+fn quaternion_from_axisangle(a: vec3<f32>) -> vec4<f32> {
+    let sqlen = a.x * a.x + a.y * a.y + a.z * a.z;
+    if sqlen < 1.1920929e-7 {
+        return vec4<f32>(0.5 * a.x, 0.5 * a.y, 0.5 * a.z, -sqlen * 0.125 + 1.0);
+    }
+    let lena = sqrt(sqlen);
+    let s = sin(lena * 0.5) / lena;
+    return vec4<f32>(s * a.x, s * a.y, s * a.z, cos(lena * 0.5));
+}
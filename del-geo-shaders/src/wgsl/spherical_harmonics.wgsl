@@ -0,0 +1,240 @@
+// spherical harmonics. see `del_geo_core::spherical_harmonics`.
+// computed in `f32` as there is no `f64` in WGSL
+
+/// product of the complex numbers `(re, im)`
+fn complex_mult(a: vec2<f32>, b: vec2<f32>) -> vec2<f32> {
+    return vec2<f32>(a.x * b.x - a.y * b.y, a.x * b.y + a.y * b.x);
+}
+
+/// normalize the vector `(x, y, z)` and return its length
+fn spherical_harmonics_normalize(x: ptr<function, f32>, y: ptr<function, f32>, z: ptr<function, f32>) -> f32 {
+    let r = sqrt(*x * *x + *y * *y + *z * *z);
+    let invr = 1.0 / r;
+    *x *= invr;
+    *y *= invr;
+    *z *= invr;
+    return r;
+}
+
+/// coefficients of the spherical harmonics for l <= 9 of the unit vector `(x, y, z)`.
+/// the coefficient `Y_l^m` is at the index `l * l + l + m`. the entries above the order `n` are zero
+fn spherical_harmonics_sph_coeff_buffer(n: i32, x: f32, y: f32, z: f32) -> array<f32, 100> {
+    let inv_pi = 0.318309886183790671538;
+    let ep = vec2<f32>(x, y);
+    var res: array<f32, 100>;
+
+    // n = 0, single spheric, s orbital
+    res[0] = 0.5 * sqrt(inv_pi);
+    if n == 0 {
+        return res;
+    }
+
+    // n = 1, spindle, p orbital
+    let r1 = ep.x;
+    let i1 = ep.y;
+    var v1 = -0.5 * sqrt(1.5 * inv_pi);
+    res[1] = v1 * i1;
+    res[2] = 0.5 * sqrt(3.0 * inv_pi) * z;
+    res[3] = v1 * r1;
+    if n == 1 {
+        return res;
+    }
+
+    // n = 2, double spindle, d orbital
+    let ep2 = complex_mult(ep, ep);
+    let r2 = ep2.x;
+    let i2 = ep2.y;
+    v1 = -0.5 * sqrt(7.5 * inv_pi) * z;
+    var v2 = 0.25 * sqrt(7.5 * inv_pi);
+    res[4] = v2 * i2;
+    res[5] = v1 * i1;
+    res[6] = 0.25 * sqrt(5.0 * inv_pi) * (2.0 * z * z - x * x - y * y);
+    res[7] = v1 * r1;
+    res[8] = v2 * r2;
+    if n == 2 {
+        return res;
+    }
+
+    // n = 3, triple spindle, f orbital
+    let ep3 = complex_mult(ep2, ep);
+    let r3 = ep3.x;
+    let i3 = ep3.y;
+    v1 = -0.125 * sqrt(21.0 * inv_pi) * (4.0 * z * z - x * x - y * y);
+    v2 = 0.250 * sqrt(52.5 * inv_pi) * z;
+    var v3 = -0.125 * sqrt(35.0 * inv_pi);
+    res[9] = v3 * i3;
+    res[10] = v2 * i2;
+    res[11] = v1 * i1;
+    res[12] = 0.250 * sqrt(7.0 * inv_pi) * z * (-3.0 * x * x - 3.0 * y * y + 2.0 * z * z);
+    res[13] = v1 * r1;
+    res[14] = v2 * r2;
+    res[15] = v3 * r3;
+    if n == 3 {
+        return res;
+    }
+
+    // n = 4, quadruple spindle, g orbital
+    let ep4 = complex_mult(ep3, ep);
+    let r4 = ep4.x;
+    let i4 = ep4.y;
+    let z2 = z * z;
+    v1 = -3.0 / 8.00 * sqrt(5.0 * inv_pi) * z * (7.0 * z2 - 3.0);
+    v2 = 3.0 / 8.00 * sqrt(5.0 * 0.5 * inv_pi) * (7.0 * z2 - 1.0);
+    v3 = -3.0 / 8.00 * sqrt(35.0 * inv_pi) * z;
+    var v4 = 3.0 / 16.0 * sqrt(35.0 * 0.5 * inv_pi);
+    res[16] = v4 * i4;
+    res[17] = v3 * i3;
+    res[18] = v2 * i2;
+    res[19] = v1 * i1;
+    res[20] = 3.0 / 16.0 * sqrt(inv_pi) * (35.0 * z2 * z2 - 30.0 * z2 + 3.0);
+    res[21] = v1 * r1;
+    res[22] = v2 * r2;
+    res[23] = v3 * r3;
+    res[24] = v4 * r4;
+    if n == 4 {
+        return res;
+    }
+
+    // n = 5, quintuple spindle, h orbital
+    let r5 = complex_mult(ep4, ep).x;
+    let i5 = complex_mult(ep4, ep).y;
+    let z4 = z2 * z2;
+    v1 = -1.0 / 16.0 * sqrt(82.5 * inv_pi) * (21.0 * z4 - 14.0 * z2 + 1.0);
+    v2 = 1.0 / 8.00 * sqrt(577.5 * inv_pi) * z * (3.0 * z2 - 1.0);
+    v3 = -1.0 / 32.0 * sqrt(385.0 * inv_pi) * (9.0 * z2 - 1.0);
+    v4 = 3.0 / 16.0 * sqrt(192.5 * inv_pi) * z;
+    var v5 = -3.0 / 32.0 * sqrt(77.0 * inv_pi);
+    res[25] = v5 * i5;
+    res[26] = v4 * i4;
+    res[27] = v3 * i3;
+    res[28] = v2 * i2;
+    res[29] = v1 * i1;
+    res[30] = 1.0 / 16.0 * sqrt(11.0 * inv_pi) * z * (63.0 * z4 - 70.0 * z2 + 15.0);
+    res[31] = v1 * r1;
+    res[32] = v2 * r2;
+    res[33] = v3 * r3;
+    res[34] = v4 * r4;
+    res[35] = v5 * r5;
+    if n == 5 {
+        return res;
+    }
+
+    // n = 6, sextuple spindle, i orbital
+    let r6 = complex_mult(ep4, ep2).x;
+    let i6 = complex_mult(ep4, ep2).y;
+    v1 = -1.0 / 16.0 * sqrt(273.0 * 0.5 * inv_pi) * z * (33.0 * z4 - 30.0 * z2 + 5.0);
+    v2 = 1.0 / 64.0 * sqrt(1365.0 * inv_pi) * (33.0 * z4 - 18.0 * z2 + 1.0);
+    v3 = -1.0 / 32.0 * sqrt(1365.0 * inv_pi) * z * (11.0 * z2 - 3.0);
+    v4 = 3.0 / 32.0 * sqrt(91.0 * 0.5 * inv_pi) * (11.0 * z2 - 1.0);
+    v5 = -3.0 / 32.0 * sqrt(1001.0 * inv_pi) * z;
+    var v6 = 1.0 / 64.0 * sqrt(3003.0 * inv_pi);
+    res[36] = v6 * i6;
+    res[37] = v5 * i5;
+    res[38] = v4 * i4;
+    res[39] = v3 * i3;
+    res[40] = v2 * i2;
+    res[41] = v1 * i1;
+    res[42] = 1.0 / 32.0 * sqrt(13.0 * inv_pi) * (231.0 * z4 * z2 - 315.0 * z4 + 105.0 * z2 - 5.0);
+    res[43] = v1 * r1;
+    res[44] = v2 * r2;
+    res[45] = v3 * r3;
+    res[46] = v4 * r4;
+    res[47] = v5 * r5;
+    res[48] = v6 * r6;
+    if n == 6 {
+        return res;
+    }
+    // n = 7, septuple spindle, j orbital
+    let r7 = complex_mult(complex_mult(ep4, ep2), ep).x;
+    let i7 = complex_mult(complex_mult(ep4, ep2), ep).y;
+    v1 = -1.0 / 64.0 * sqrt(105.0 * 0.5 * inv_pi) * (429.0 * z4 * z2 - 495.0 * z4 + 135.0 * z2 - 5.0);
+    v2 = 3.0 / 64.0 * sqrt(35.0 * inv_pi) * (143.0 * z4 * z - 110.0 * z2 * z + 15.0 * z);
+    v3 = -3.0 / 64.0 * sqrt(35.0 * 0.5 * inv_pi) * (143.0 * z4 - 66.0 * z2 + 3.0);
+    v4 = 3.0 / 32.0 * sqrt(385.0 * 0.5 * inv_pi) * (13.0 * z2 * z - 3.0 * z);
+    v5 = -3.0 / 64.0 * sqrt(385.0 * 0.5 * inv_pi) * (13.0 * z2 - 1.0);
+    v6 = 3.0 / 64.0 * sqrt(5005.0 * inv_pi) * z;
+    var v7 = -3.0 / 128.0 * sqrt(1430.0 * inv_pi);
+    res[49] = v7 * i7;
+    res[50] = v6 * i6;
+    res[51] = v5 * i5;
+    res[52] = v4 * i4;
+    res[53] = v3 * i3;
+    res[54] = v2 * i2;
+    res[55] = v1 * i1;
+    res[56] = 1.0 / 32.0 * sqrt(15.0 * inv_pi) * (429.0 * z4 * z2 * z - 693.0 * z4 * z + 315.0 * z2 * z - 35.0 * z);
+    res[57] = v1 * r1;
+    res[58] = v2 * r2;
+    res[59] = v3 * r3;
+    res[60] = v4 * r4;
+    res[61] = v5 * r5;
+    res[62] = v6 * r6;
+    res[63] = v7 * r7;
+    if n == 7 {
+        return res;
+    }
+    // n = 8, octuple spindle, k orbital
+    let ep8 = complex_mult(ep4, ep4);
+    let r8 = ep8.x;
+    let i8 = ep8.y;
+    let z8 = z4 * z4;
+    v1 = -3.0 / 64.00 * sqrt(17.0 * 0.5 * inv_pi) * (715.0 * z4 * z2 * z - 1001.0 * z4 * z + 385.0 * z2 * z - 35.0 * z);
+    v2 = 3.0 / 128.0 * sqrt(595.0 * inv_pi) * (143.0 * z4 * z2 - 143.0 * z4 + 33.0 * z2 - 1.0);
+    v3 = -1.0 / 64.00 * sqrt(19635.0 * 0.5 * inv_pi) * (39.0 * z4 * z - 26.0 * z2 * z + 3.0 * z);
+    v4 = 3.0 / 128.0 * sqrt(1309.0 * 0.5 * inv_pi) * (65.0 * z4 - 26.0 * z2 + 1.0);
+    v5 = -3.0 / 64.00 * sqrt(17017.0 * 0.5 * inv_pi) * (5.0 * z2 * z - z);
+    v6 = 1.0 / 128.0 * sqrt(7293.0 * inv_pi) * (15.0 * z2 - 1.0);
+    v7 = -3.0 / 64.00 * sqrt(12155.0 * 0.5 * inv_pi) * z;
+    var v8 = 3.0 / 256.0 * sqrt(12155.0 * 0.5 * inv_pi);
+    res[64] = v8 * i8;
+    res[65] = v7 * i7;
+    res[66] = v6 * i6;
+    res[67] = v5 * i5;
+    res[68] = v4 * i4;
+    res[69] = v3 * i3;
+    res[70] = v2 * i2;
+    res[71] = v1 * i1;
+    res[72] = 1.0 / 256.0 * sqrt(17.0 * inv_pi) * (6435.0 * z8 - 12012.0 * z4 * z2 + 6930.0 * z4 - 1260.0 * z2 + 35.0);
+    res[73] = v1 * r1;
+    res[74] = v2 * r2;
+    res[75] = v3 * r3;
+    res[76] = v4 * r4;
+    res[77] = v5 * r5;
+    res[78] = v6 * r6;
+    res[79] = v7 * r7;
+    res[80] = v8 * r8;
+    if n == 8 {
+        return res;
+    }
+    // n = 9, nonuple spindle, l orbital
+    let r9 = complex_mult(ep8, ep).x;
+    let i9 = complex_mult(ep8, ep).y;
+    v1 = -3.0 / 256.0 * sqrt(95.0 * 0.5 * inv_pi) * (2431.0 * z8 - 4004.0 * z4 * z2 + 2002.0 * z4 - 308.0 * z2 + 7.0);
+    v2 = 3.0 / 128.0 * sqrt(1045.0 * inv_pi) * z * (221.0 * z4 * z2 - 273.0 * z4 + 91.0 * z2 - 7.0);
+    v3 = -1.0 / 256.0 * sqrt(21945.0 * inv_pi) * (221.0 * z4 * z2 - 195.0 * z4 + 39.0 * z2 - 1.0);
+    v4 = 3.0 / 256.0 * sqrt(95095.0 * 2.0 * inv_pi) * z * (17.0 * z4 - 10.0 * z2 + 1.0);
+    v5 = -3.0 / 256.0 * sqrt(2717.0 * inv_pi) * (85.0 * z4 - 30.0 * z2 + 1.0);
+    v6 = 1.0 / 128.0 * sqrt(40755.0 * inv_pi) * z * (17.0 * z2 - 3.0);
+    v7 = -3.0 / 512.0 * sqrt(13585.0 * inv_pi) * (17.0 * z2 - 1.0);
+    v8 = 3.0 / 512.0 * sqrt(230945.0 * 2.0 * inv_pi) * z;
+    let v9 = -1.0 / 512.0 * sqrt(230945.0 * inv_pi);
+    res[81] = v9 * i9;
+    res[82] = v8 * i8;
+    res[83] = v7 * i7;
+    res[84] = v6 * i6;
+    res[85] = v5 * i5;
+    res[86] = v4 * i4;
+    res[87] = v3 * i3;
+    res[88] = v2 * i2;
+    res[89] = v1 * i1;
+    res[90] = 1.0 / 256.0 * sqrt(19.0 * inv_pi) * z * (12155.0 * z8 - 25740.0 * z4 * z2 + 18018.0 * z4 - 4620.0 * z2 + 315.0);
+    res[91] = v1 * r1;
+    res[92] = v2 * r2;
+    res[93] = v3 * r3;
+    res[94] = v4 * r4;
+    res[95] = v5 * r5;
+    res[96] = v6 * r6;
+    res[97] = v7 * r7;
+    res[98] = v8 * r8;
+    res[99] = v9 * r9;
+    return res;
+}
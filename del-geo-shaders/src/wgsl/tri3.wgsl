@@ -0,0 +1,34 @@
+// 3D triangle. see `del_geo_core::tri3`
+
+/// Moller-Trumbore intersection of the line `ray_org + t * ray_dir` against the triangle.
+/// returns `false` if there is no intersection, otherwise writes the parameter to `t`
+fn tri3_intersection_against_ray(
+    p0: vec3<f32>,
+    p1: vec3<f32>,
+    p2: vec3<f32>,
+    ray_org: vec3<f32>,
+    ray_dir: vec3<f32>,
+    t: ptr<function, f32>,
+) -> bool {
+    let eps = 1.1920929e-7;
+    let edge1 = p1 - p0;
+    let edge2 = p2 - p0;
+    let pvec = cross(ray_dir, edge2);
+    let det = dot(edge1, pvec);
+    if det > -eps && det < eps {
+        return false;
+    }
+    let invdet = 1.0 / det;
+    let tvec = ray_org - p0;
+    let u = invdet * dot(tvec, pvec);
+    if u < 0.0 || u > 1.0 {
+        return false;
+    }
+    let qvec = cross(tvec, edge1);
+    let v = invdet * dot(ray_dir, qvec);
+    if v < 0.0 || u + v > 1.0 {
+        return false;
+    }
+    *t = invdet * dot(edge2, qvec);
+    return true;
+}
@@ -0,0 +1,43 @@
+// 3D vector. see `del_geo_core::vec3`
+
+fn vec3_norm(a: vec3<f32>) -> f32 {
+    return sqrt(a.x * a.x + a.y * a.y + a.z * a.z);
+}
+
+fn vec3_squared_norm(a: vec3<f32>) -> f32 {
+    return a.x * a.x + a.y * a.y + a.z * a.z;
+}
+
+fn vec3_add(a: vec3<f32>, b: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(a.x + b.x, a.y + b.y, a.z + b.z);
+}
+
+fn vec3_sub(a: vec3<f32>, b: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(a.x - b.x, a.y - b.y, a.z - b.z);
+}
+
+fn vec3_scale(a: vec3<f32>, s: f32) -> vec3<f32> {
+    return vec3<f32>(a.x * s, a.y * s, a.z * s);
+}
+
+fn vec3_dot(a: vec3<f32>, b: vec3<f32>) -> f32 {
+    return a.x * b.x + a.y * b.y + a.z * b.z;
+}
+
+fn vec3_cross(v1: vec3<f32>, v2: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(
+        v1.y * v2.z - v2.y * v1.z,
+        v1.z * v2.x - v2.z * v1.x,
+        v1.x * v2.y - v2.x * v1.y,
+    );
+}
+
+/// `a * x + y`
+fn vec3_axpy(a: f32, x: vec3<f32>, y: vec3<f32>) -> vec3<f32> {
+    return vec3<f32>(a * x.x + y.x, a * x.y + y.y, a * x.z + y.z);
+}
+
+fn vec3_normalize(a: vec3<f32>) -> vec3<f32> {
+    let invl = 1.0 / vec3_norm(a);
+    return vec3<f32>(a.x * invl, a.y * invl, a.z * invl);
+}
@@ -0,0 +1,48 @@
+//! parse and validate the shader sources with `naga` on the CPU
+
+fn validate(name: &str, module: &naga::Module) {
+    let mut validator = naga::valid::Validator::new(
+        naga::valid::ValidationFlags::all(),
+        naga::valid::Capabilities::all(),
+    );
+    if let Err(e) = validator.validate(module) {
+        panic!("{}: {:?}", name, e);
+    }
+}
+
+#[test]
+fn test_wgsl() {
+    let all = (0..)
+        .map_while(|idx| del_geo_shaders::WGSL.get(idx))
+        .inspect(|(name, src)| {
+            let module = naga::front::wgsl::parse_str(src)
+                .unwrap_or_else(|e| panic!("{}\n{}", name, e.emit_to_string(src)));
+            validate(name, &module);
+        })
+        .map(|(_, src)| src)
+        .collect::<Vec<_>>();
+    assert_eq!(all.len(), 6);
+    // the sources do not conflict with each other
+    let src = all.join("\n");
+    validate("all", &naga::front::wgsl::parse_str(&src).unwrap());
+}
+
+#[test]
+fn test_glsl() {
+    let mut frontend = naga::front::glsl::Frontend::default();
+    let options = naga::front::glsl::Options::from(naga::ShaderStage::Fragment);
+    let wrap = |src: &str| format!("#version 450\n{}\nvoid main() {{}}\n", src);
+    let all = (0..)
+        .map_while(|idx| del_geo_shaders::GLSL.get(idx))
+        .inspect(|(name, src)| {
+            let module = frontend
+                .parse(&options, &wrap(src))
+                .unwrap_or_else(|e| panic!("{}\n{}", name, e.emit_to_string(src)));
+            validate(name, &module);
+        })
+        .map(|(_, src)| src)
+        .collect::<Vec<_>>();
+    assert_eq!(all.len(), 6);
+    let src = all.join("\n");
+    validate("all", &frontend.parse(&options, &wrap(&src)).unwrap());
+}